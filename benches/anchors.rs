@@ -0,0 +1,103 @@
+//! Benchmarks the anchored point lookups of the solver on a 100x100 cloth
+//! pinned along its first row: a full `Cloth::step`, and the sticks
+//! constraint pass looking up the anchored points in the `Cloth::is_anchored`
+//! mask or in the anchors map.
+//!
+//! Run with `cargo bench --bench anchors`
+mod common;
+
+use bevy::{math::Vec3, transform::components::GlobalTransform, utils::HashMap};
+use bevy_silk::{components::cloth::StickId, prelude::*};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+/// Sticks constraint passes mirroring `Cloth::update_sticks` with fixed
+/// sticks, looking up the anchored points with `is_anchored`
+fn stick_passes(
+    points: &mut [Vec3],
+    sticks: &HashMap<StickId, f32>,
+    depth: u8,
+    is_anchored: impl Fn(usize) -> bool,
+) {
+    for _ in 0..depth {
+        for ([id_a, id_b], target_len) in sticks {
+            let (fixed_a, fixed_b) = (is_anchored(*id_a), is_anchored(*id_b));
+            if fixed_a && fixed_b {
+                continue;
+            }
+            let (position_a, position_b) = (points[*id_a], points[*id_b]);
+            let Some(direction) = (position_b - position_a).try_normalize() else {
+                continue;
+            };
+            let direction = direction * *target_len / 2.0;
+            let center = (position_a + position_b) / 2.0;
+            if !fixed_a {
+                points[*id_a] = if fixed_b {
+                    position_b - direction * 2.0
+                } else {
+                    center - direction
+                };
+            }
+            if !fixed_b {
+                points[*id_b] = if fixed_a {
+                    position_a + direction * 2.0
+                } else {
+                    center + direction
+                };
+            }
+        }
+    }
+}
+
+fn anchor_lookups(c: &mut Criterion) {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let anchors: HashMap<usize, VertexAnchor> = cloth
+        .anchors()
+        .map(|(id, anchor, _)| (id, anchor.clone()))
+        .collect();
+    let config = ClothConfig::default();
+    let depth = config.sticks_computation_depth;
+    let mut group = c.benchmark_group("anchor_lookups_100x100");
+    group.bench_function("step", |b| {
+        b.iter_batched_ref(
+            || cloth.clone(),
+            |cloth| {
+                cloth.step(
+                    &config,
+                    |_| Vec3::ZERO,
+                    1.0 / 60.0,
+                    &GlobalTransform::IDENTITY,
+                    |_| None,
+                    |_| None,
+                );
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("sticks_mask", |b| {
+        b.iter_batched_ref(
+            || cloth.current_point_positions.clone(),
+            |points| {
+                stick_passes(points, &cloth.stick_lengths, depth, |id| {
+                    cloth.is_anchored(id)
+                });
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("sticks_hash_map", |b| {
+        b.iter_batched_ref(
+            || cloth.current_point_positions.clone(),
+            |points| {
+                stick_passes(points, &cloth.stick_lengths, depth, |id| {
+                    anchors.contains_key(&id)
+                });
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, anchor_lookups);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+//! Benchmarks the cloth systems in a headless app.
+//!
+//! Run with `cargo bench --features testing --bench cloth_systems`, and with
+//! `--features testing,parallel` to compare the serial and parallel updates.
+mod common;
+
+use bevy::prelude::*;
+use bevy_silk::{prelude::*, test_util::*};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const MODE: &str = if cfg!(feature = "parallel") {
+    "parallel"
+} else {
+    "serial"
+};
+
+#[allow(clippy::cast_precision_loss)]
+fn flags(c: &mut Criterion) {
+    let mut app = TestAppBuilder::new().build();
+    for i in 0..50 {
+        spawn_cloth(
+            &mut app,
+            common::grid_mesh(20),
+            ClothBuilder::new().with_pinned_vertex_ids(0..20),
+            Transform::from_xyz(i as f32 * 3.0, 0.0, 0.0),
+        );
+    }
+    // Initializes the cloths
+    step_cloth_app(&mut app, 2);
+    c.bench_function(&format!("update_50_flags_20x20_{MODE}"), |b| {
+        b.iter(|| app.update());
+    });
+}
+
+criterion_group!(benches, flags);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+//! Benchmarks the backend independent collision solving of a 100x100 cloth
+//! against sphere colliders, with the collider solve iterations and the contact
+//! relaxation passes.
+//!
+//! Run with `cargo bench --bench collisions`
+mod common;
+
+use bevy::math::{BVec3, Vec3};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+/// Solving function pushing the points out of a sphere
+fn sphere(center: Vec3, radius: f32) -> impl Fn(&Vec3, &Vec3) -> Option<Vec3> {
+    move |_previous, point| {
+        let delta = *point - center;
+        (delta.length_squared() < radius * radius)
+            .then(|| center + delta.normalize_or(Vec3::Z) * radius)
+    }
+}
+
+fn solve_iterations(c: &mut Criterion) {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let contacts = [sphere(Vec3::new(5.0, -5.0, 0.5), 3.0)];
+    let mut group = c.benchmark_group("collision_solve_iterations_100x100");
+    for iterations in [1, 2, 4] {
+        group.bench_function(iterations.to_string(), |b| {
+            b.iter_batched_ref(
+                || cloth.clone(),
+                |cloth| cloth.solve_contacts(&contacts, iterations, BVec3::FALSE),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Mirrors the `ClothCollider::contact_relaxation` loop of the collision
+/// handlers, with the cloth pinched between two opposing spheres
+fn contact_relaxation(c: &mut Criterion) {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let contacts = [
+        sphere(Vec3::new(5.0, -5.0, 3.0), 3.0),
+        sphere(Vec3::new(5.0, -5.0, -2.0), 3.0),
+    ];
+    let mut group = c.benchmark_group("collision_contact_relaxation_100x100");
+    for relaxation in [1, 2, 4] {
+        group.bench_function(relaxation.to_string(), |b| {
+            b.iter_batched_ref(
+                || cloth.clone(),
+                |cloth| {
+                    for _ in 0..relaxation {
+                        cloth.solve_contacts(&contacts, 1, BVec3::FALSE);
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solve_iterations, contact_relaxation);
+criterion_main!(benches);
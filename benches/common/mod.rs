@@ -0,0 +1,46 @@
+//! Shared helpers for the benchmarks
+#![allow(dead_code)]
+
+use bevy::{
+    math::{Mat4, Vec3},
+    render::mesh::{Indices, Mesh, VertexAttributeValues},
+    utils::HashMap,
+};
+use bevy_silk::{components::cloth::Cloth, prelude::*};
+
+/// Builds a `size`x`size` vertical grid mesh with a 0.1 vertex spacing
+pub fn grid_mesh(size: usize) -> Mesh {
+    rectangle_mesh((size, size), (Vec3::X * 0.1, -Vec3::Y * 0.1), Vec3::Z)
+}
+
+/// Retrieves the vertex positions and indices of `mesh`
+pub fn mesh_data(mesh: &Mesh) -> (Vec<Vec3>, Vec<u32>) {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("Invalid benchmark mesh positions");
+    };
+    let Some(Indices::U32(indices)) = mesh.indices() else {
+        panic!("Invalid benchmark mesh indices");
+    };
+    (
+        positions.iter().copied().map(Vec3::from).collect(),
+        indices.clone(),
+    )
+}
+
+/// Builds a cloth from `mesh`, pinned along its first row
+pub fn grid_cloth(mesh: &Mesh, size: usize) -> Cloth {
+    let (positions, indices) = mesh_data(mesh);
+    let anchors: HashMap<usize, VertexAnchor> =
+        (0..size).map(|i| (i, VertexAnchor::default())).collect();
+    Cloth::new(
+        &positions,
+        &indices,
+        anchors,
+        StickGeneration::default(),
+        StickLen::default(),
+        StickMode::default(),
+        &Mat4::IDENTITY,
+    )
+}
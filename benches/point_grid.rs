@@ -0,0 +1,44 @@
+//! Benchmarks the cached spatial hash of the cloth points on a 100x100 cloth,
+//! through the `Cloth::points_within` and `Cloth::nearest_points` queries:
+//! the first build, the rebuild after the points moved and the cached
+//! queries.
+//!
+//! Run with `cargo bench --bench point_grid`
+mod common;
+
+use bevy::math::Vec3;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+fn point_grid(c: &mut Criterion) {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let center = Vec3::new(5.0, -5.0, 0.0);
+    c.bench_function("point_grid_new_100x100", |b| {
+        b.iter_batched(
+            || cloth.clone(),
+            |mut cloth| cloth.points_within(black_box(center), 0.3).count(),
+            BatchSize::SmallInput,
+        );
+    });
+    let mut moved = cloth.clone();
+    let _ = moved.points_within(center, 0.3).count();
+    c.bench_function("point_grid_rebuild_100x100", |b| {
+        b.iter(|| {
+            for point in &mut moved.current_point_positions {
+                point.z += 0.01;
+            }
+            moved.invalidate_cache();
+            moved.points_within(black_box(center), 0.3).count()
+        });
+    });
+    let mut cached = cloth.clone();
+    c.bench_function("point_grid_query_radius_100x100", |b| {
+        b.iter(|| cached.points_within(black_box(center), 0.3).count());
+    });
+    c.bench_function("point_grid_query_nearest_100x100", |b| {
+        b.iter(|| cached.nearest_points(black_box(center), 4));
+    });
+}
+
+criterion_group!(benches, point_grid);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+//! Counts the heap allocations of the render path vertex positions and smooth
+//! normals updates, with and without reusing the `ClothRendering` buffers.
+//!
+//! Run with `cargo bench --bench render_allocations`
+mod common;
+
+use bevy::prelude::*;
+use bevy_silk::components::cloth_rendering::{ClothRendering, NormalComputing};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const FRAMES: usize = 100;
+
+fn count_allocations(mut frame: impl FnMut()) -> usize {
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..FRAMES {
+        frame();
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - start
+}
+
+fn main() {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let mut rendering = ClothRendering::init(&mesh, NormalComputing::None).unwrap();
+    let transform = GlobalTransform::default();
+
+    let collected = count_allocations(|| {
+        rendering.update_positions(cloth.compute_vertex_positions(&transform));
+    });
+    let reused = count_allocations(|| {
+        rendering.update_positions_from(&cloth, &transform);
+    });
+    println!("100x100 cloth, {FRAMES} frames of vertex positions updates:");
+    println!("  collected (update_positions):   {collected} allocations");
+    println!("  reused (update_positions_from): {reused} allocations");
+
+    let mut target = mesh.clone();
+    rendering.normal_computing = NormalComputing::SmoothNormals;
+    let computed = count_allocations(|| {
+        rendering.update_positions_from(&cloth, &transform);
+        rendering.apply(&mut target);
+    });
+    let reused = count_allocations(|| {
+        rendering.update_positions_from(&cloth, &transform);
+        rendering.update_normals(1.0 / 60.0);
+        rendering.apply(&mut target);
+    });
+    println!("100x100 cloth, {FRAMES} frames of smooth normals updates:");
+    println!("  computed (apply):               {computed} allocations");
+    println!("  reused (update_normals):        {reused} allocations");
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_silk::components::cloth_rendering::{ClothRendering, NormalComputing};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn smooth_normals(c: &mut Criterion) {
+    let mesh = common::grid_mesh(100);
+    let cloth = common::grid_cloth(&mesh, 100);
+    let transform = GlobalTransform::default();
+    let mut target = mesh.clone();
+    let mut rendering = ClothRendering::init(&mesh, NormalComputing::SmoothNormals).unwrap();
+    rendering.update_positions_from(&cloth, &transform);
+    c.bench_function("smooth_normals_computed_100x100", |b| {
+        b.iter(|| rendering.apply(black_box(&mut target)));
+    });
+    c.bench_function("smooth_normals_reused_100x100", |b| {
+        b.iter(|| {
+            rendering.update_normals(black_box(1.0 / 60.0));
+            rendering.apply(black_box(&mut target));
+        });
+    });
+}
+
+criterion_group!(benches, smooth_normals);
+criterion_main!(benches);
@@ -19,7 +19,7 @@ fn main() {
         .add_plugins(camera_plugin::CameraPlugin)
         .add_plugins(ClothPlugin)
         .insert_resource(ClothConfig {
-            friction: 0.1,
+            velocity_damping: 0.1,
             ..Default::default()
         })
         .add_systems(Startup, (spawn_cloth, setup))
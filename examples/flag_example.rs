@@ -119,10 +119,31 @@ fn spawn_cloth(
     mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
     let cloth = ClothBuilder::new().with_pinned_vertex_color(Color::from(RED));
     commands.spawn((
-        Mesh3d(meshes.add(mesh)),
-        MeshMaterial3d(material),
+        Mesh3d(meshes.add(mesh.clone())),
+        MeshMaterial3d(material.clone()),
         Transform::from_xyz(0.0, 8.0, -10.0),
         cloth,
         Name::new("Colored smooth Flag"),
     ));
+
+    // Stiffness painted flag, stiff near the pole and soft at the end
+    let colors: Vec<[f32; 4]> = (0..size_y)
+        .flat_map(|_| {
+            (0..size_x).map(|v| {
+                let v = v as f32 / size_x as f32;
+                [1.0 - v, 0.0, 0.0, 1.0]
+            })
+        })
+        .collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    let cloth = ClothBuilder::new()
+        .with_pinned_vertex_ids((0..size_y).map(|i| i * size_x))
+        .with_stiffness_from_vertex_color(ColorChannel::Red);
+    commands.spawn((
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(material),
+        Transform::from_xyz(0.0, 8.0, -20.0),
+        cloth,
+        Name::new("Stiffness painted Flag"),
+    ));
 }
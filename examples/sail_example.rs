@@ -0,0 +1,109 @@
+use bevy::{color::palettes::css::BROWN, prelude::*};
+use bevy_inspector_egui::quick::{ResourceInspectorPlugin, WorldInspectorPlugin};
+use bevy_silk::prelude::*;
+
+mod camera_plugin;
+
+#[derive(Debug, Clone, Reflect, Resource)]
+struct BoatAnimation {
+    pub boat_entity: Option<Entity>,
+    pub rotation_speed: f32,
+}
+
+impl Default for BoatAnimation {
+    fn default() -> Self {
+        Self {
+            boat_entity: None,
+            rotation_speed: 0.3,
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .register_type::<BoatAnimation>()
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 500.0,
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WorldInspectorPlugin::default())
+        .add_plugins(ResourceInspectorPlugin::<Winds>::new())
+        .add_plugins(ResourceInspectorPlugin::<ClothConfig>::new())
+        .add_plugins(ResourceInspectorPlugin::<BoatAnimation>::new())
+        .add_plugins(camera_plugin::CameraPlugin)
+        .insert_resource(ClothConfig {
+            sail_mode: true,
+            ..Default::default()
+        })
+        .insert_resource(Winds::from(Wind::ConstantWind {
+            velocity: Vec3::new(0.0, 0.0, 30.0),
+        }))
+        .add_plugins(ClothPlugin)
+        .add_systems(Startup, (spawn_boat, setup))
+        .add_systems(Update, rotate_boat)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_rotation(Quat::from_rotation_y(5.0)),
+    ));
+}
+
+fn spawn_boat(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let wood = materials.add(Color::from(BROWN));
+    let (size_x, size_y) = (20, 30);
+    let mesh = rectangle_mesh((size_x, size_y), (Vec3::X * 0.3, -Vec3::Y * 0.3), Vec3::Z);
+    // The sail is attached to the mast (first column) and the boom (last row)
+    let cloth = ClothBuilder::new()
+        .with_pinned_vertex_ids((0..size_y).map(|i| i * size_x))
+        .with_pinned_vertex_ids((size_y - 1) * size_x..size_y * size_x)
+        .with_stick_generation(StickGeneration::Triangles);
+    let boat_entity = commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(8.0, 1.0, 3.0))),
+            MeshMaterial3d(wood.clone()),
+            Transform::default(),
+            Name::new("Boat"),
+        ))
+        .with_children(|b| {
+            b.spawn((
+                Mesh3d(meshes.add(Cylinder::new(0.1, 10.0))),
+                MeshMaterial3d(wood),
+                Transform::from_xyz(-3.0, 5.0, 0.0),
+                Name::new("Mast"),
+            ));
+            b.spawn((
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    cull_mode: None, // Option required to render back faces correctly
+                    double_sided: true, // Option required to render back faces correctly
+                    ..Default::default()
+                })),
+                Transform::from_xyz(-2.9, 10.0, 0.0),
+                cloth,
+                Name::new("Sail"),
+            ));
+        })
+        .id();
+    commands.insert_resource(BoatAnimation {
+        boat_entity: Some(boat_entity),
+        ..Default::default()
+    });
+}
+
+fn rotate_boat(animation: Res<BoatAnimation>, mut query: Query<&mut Transform>, time: Res<Time>) {
+    let Some(mut transform) = animation.boat_entity.and_then(|e| query.get_mut(e).ok()) else {
+        return;
+    };
+    transform.rotate(Quat::from_rotation_y(
+        time.delta_secs() * animation.rotation_speed,
+    ));
+}
@@ -1,14 +1,19 @@
 use crate::{
+    components::cloth_path::ClothPath,
+    config::{ClothConfig, SolveOrder},
+    error::Error,
+    point_grid::PointGrid,
     stick::{StickGeneration, StickLen, StickMode},
     vertex_anchor::VertexAnchor,
 };
 use bevy::{
     ecs::prelude::Component,
     log,
-    math::{Mat4, Vec3},
+    math::{BVec3, Mat4, Vec3},
     prelude::{Entity, GlobalTransform},
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
+use std::collections::VecDeque;
 
 /// A stick is defined by the two ids of the connectecte points
 pub type StickId = [usize; 2];
@@ -18,13 +23,81 @@ macro_rules! get_point {
         match $points.get($id) {
             None => {
                 log::warn!("Failed to retrieve a Cloth point at index {}", $id);
-                continue;
+                return;
             }
-            Some(p) => (*p, $anchored_points.contains_key(&$id)),
+            Some(p) => (*p, $anchored_points.is_pinned($id)),
         }
     };
 }
 
+/// Constrained axes of every point id, mirroring the cloth anchored points for
+/// constant time lookups in the solver loops. Free points have no constrained
+/// axis (See [`VertexAnchor::constrain_axes`])
+#[derive(Debug, Clone, Default)]
+struct AnchorMask(Vec<BVec3>);
+
+impl AnchorMask {
+    #[inline]
+    fn axes(&self, id: usize) -> BVec3 {
+        self.0.get(id).copied().unwrap_or(BVec3::FALSE)
+    }
+
+    /// Whether the point is anchored on every axis, and left untouched by the
+    /// solver
+    #[inline]
+    fn is_pinned(&self, id: usize) -> bool {
+        self.axes(id).all()
+    }
+
+    fn set(&mut self, id: usize, axes: BVec3) {
+        if id >= self.0.len() {
+            self.0.resize(id + 1, BVec3::FALSE);
+        }
+        self.0[id] = axes;
+    }
+}
+
+/// Solver data derived from the cloth sticks and anchors, kept on the
+/// [`Cloth`] to avoid rebuilding it every frame.
+///
+/// The cached values are invalidated by the [`Cloth`] methods editing the
+/// sticks, the anchored points or the point positions, and rebuilt when the
+/// stick, anchor or point counts change. Manual edits of
+/// [`Cloth::stick_lengths`] keeping the stick count, or of
+/// [`Cloth::current_point_positions`], must call [`Cloth::invalidate_cache`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClothCache {
+    /// Breadth first stick walk from the anchored points used by
+    /// [`Cloth::limit_stretch`]: the origin point, the reached point and
+    /// the stick target length
+    stretch_edges: Vec<(usize, usize, f32)>,
+    /// The stick, anchor and point counts [`Self::stretch_edges`] was built
+    /// from, `None` if invalidated
+    stretch_counts: Option<[usize; 3]>,
+    /// Sticks sorted by point ids, iterated in alternating directions by
+    /// [`Cloth::update_sticks_alternating`]
+    sorted_sticks: Vec<(StickId, f32)>,
+    /// The stick count [`Self::sorted_sticks`] was built from, `None` if
+    /// invalidated
+    sorted_sticks_count: Option<usize>,
+    /// Spatial hash of the current point positions, shared by the neighbor
+    /// queries (See [`Cloth::points_within`])
+    point_grid: Option<PointGrid>,
+    /// Whether the points moved since the last [`Self::point_grid`] build
+    point_grid_stale: bool,
+}
+
+/// Cloth point collision contact (See [`Cloth::solve_collisions_detailed`])
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Contact {
+    /// The solved point position, outside of the collider
+    pub position: Vec3,
+    /// The contact normal, pointing out of the collider
+    pub normal: Vec3,
+    /// The penetration depth of the point in the collider
+    pub depth: f32,
+}
+
 /// Cloth component. Do not insert it directly, use [`ClothBuilder`] instead.
 ///
 /// [`ClothBuilder`]: crate::prelude::ClothBuilder
@@ -35,11 +108,38 @@ pub struct Cloth {
     /// The key is the point index and the value is a tuple with:
     /// - 0: The [`VertexAnchor`] anchor
     /// - 1: The initial local space vertex position
-    pub anchored_points: HashMap<usize, (VertexAnchor, Vec3)>,
+    ///
+    /// Edited through [`Self::pin_vertex`] and [`Self::unpin_vertex`], which
+    /// keep the anchored points mask in sync
+    anchored_points: HashMap<usize, (VertexAnchor, Vec3)>,
+    /// cloth points affected by physics but pulled toward an anchor every
+    /// frame. The key is the point index and the value is a tuple with:
+    /// - 0: The [`VertexAnchor`] anchor
+    /// - 1: The initial local space vertex position
+    /// - 2: The anchor stiffness, between 0.0 (free) and 1.0 (pinned)
+    pub spring_anchored_points: HashMap<usize, (VertexAnchor, Vec3, f32)>,
     /// Current Cloth points 3D positions in world space
     pub current_point_positions: Vec<Vec3>,
     /// Old Cloth points 3D positions in world space
     pub previous_point_positions: Vec<Vec3>,
+    /// Optional cloth point masses, by point index. Empty by default,
+    /// meaning uniform masses.
+    ///
+    /// The masses don't affect the gravity, which accelerates every point
+    /// equally, but weight the stick corrections and the collision responses
+    /// by inverse mass:
+    /// - the stick corrections are split between the two points by inverse
+    ///   mass, a heavy point moving less than its light neighbour
+    /// - only the lightest points are fully pushed out of the colliders,
+    ///   heavier points being moved by `lightest_mass / mass` of the push and
+    ///   gaining as much velocity. They are pushed out over the next updates
+    ///   (See [`Self::solve_swept_collisions_on_free_axes`])
+    ///
+    /// Points missing from the list have a mass of 1.0
+    pub point_masses: Vec<f32>,
+    /// Cloth points rest positions in the local space of the cloth entity,
+    /// the authored pose used by [`ClothConfig::shape_memory`]
+    pub rest_positions: Vec<Vec3>,
     /// Cloth sticks lengths
     ///
     /// * key: array of the two connected points indexes
@@ -52,6 +152,68 @@ pub struct Cloth {
     /// * key: array of the two connected points indexes
     /// * value: the stick mode
     pub stick_modes: HashMap<StickId, StickMode>,
+    /// Cloth sticks stiffness, the ratio of the stick correction applied
+    /// every iteration, between 0.0 (no correction) and 1.0
+    ///
+    /// * key: array of the two connected points indexes
+    /// * value: the stick stiffness
+    ///
+    /// Note: sticks missing from this map are fully stiff (1.0)
+    pub stick_stiffness: HashMap<StickId, f32>,
+    /// Cloth triangles, as point index triples. Used to compute the point
+    /// normals (See [`Self::compute_point_normals`])
+    ///
+    /// Note: this field will be automatically populated from mesh data
+    pub triangles: Vec<[usize; 3]>,
+    /// Cloth connected components: groups of point ids linked together by
+    /// sticks, like the separate pieces of a mesh (See [`Self::components`])
+    ///
+    /// Note: this field is computed at construction, call
+    /// [`Self::update_components`] after manually editing the sticks
+    pub connected_components: Vec<Vec<usize>>,
+    /// Cloth entity world space translation on the last frame, used to
+    /// compute inertia (See [`Self::track_inertia`])
+    pub last_translation: Option<Vec3>,
+    /// Cloth entity world space displacement on the last frame, used to
+    /// compute inertia (See [`Self::track_inertia`])
+    pub last_displacement: Option<Vec3>,
+    /// Whether the cloth was settled on the last update (See
+    /// [`Self::update_settled_state`])
+    pub settled: bool,
+    /// Cloth entity world space scale the stick lengths are computed for (See
+    /// [`Self::follow_scale`])
+    pub rest_scale: Vec3,
+    /// Accumulated simulation time of the cloth, in seconds, used to sample
+    /// the winds (See [`Wind::current_velocity`]).
+    ///
+    /// It only advances while the cloth is simulated, so paused cloths don't
+    /// skip ahead, and replays of the same inputs are deterministic
+    /// regardless of the wall clock.
+    ///
+    /// The winds are sampled at `sim_time` plus the [`Self::phase_offset`]
+    /// and the [`ClothConfig::phase_offset`]. The time is kept when the cloth
+    /// is rebuilt after a `ClothBuilder` change.
+    ///
+    /// Note: cloths used to sample the winds from the global elapsed time.
+    /// Cloths spawned at different times no longer share the same wind phase:
+    /// set the same value on both cloths to synchronize them, or the current
+    /// elapsed time on spawn to restore the previous behaviour.
+    ///
+    /// [`Wind::current_velocity`]: crate::wind::Wind::current_velocity
+    pub sim_time: f32,
+    /// Wind phase of this cloth, in seconds, added to the
+    /// [`ClothConfig::phase_offset`] when sampling the winds. Set from the
+    /// `ClothBuilder` wind phase on initialization.
+    pub phase_offset: f32,
+    /// Anchored point ids released since the last events update, because
+    /// their anchor was pulled harder than its [`VertexAnchor::max_force`]
+    pub released_anchors: Vec<usize>,
+    /// Anchored state of every point, kept in sync with
+    /// [`Self::anchored_points`] (See [`Self::is_anchored`])
+    anchored_mask: AnchorMask,
+    /// Solver data derived from the sticks and anchors, rebuilt automatically
+    /// when they change
+    pub(crate) cache: ClothCache,
 }
 
 impl Cloth {
@@ -74,11 +236,182 @@ impl Cloth {
             .map(move |p| matrix.transform_point3(*p))
     }
 
+    /// Retrieves the local space position of a single cloth point, avoiding
+    /// the computation of every vertex position.
+    ///
+    /// Use [`Self::local_positions`] to query multiple points at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the point id
+    /// * `transform` - the `GlobalTransform` associated to the cloth entity
+    ///
+    /// # Returns
+    ///
+    /// `None` if `id` is out of bounds
+    #[must_use]
+    pub fn local_position(&self, id: usize, transform: &GlobalTransform) -> Option<Vec3> {
+        let point = self.current_point_positions.get(id)?;
+        Some(
+            transform
+                .compute_matrix()
+                .inverse()
+                .transform_point3(*point),
+        )
+    }
+
+    /// Retrieves the local space positions of the given cloth points,
+    /// computing the inverse transform matrix only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - the point ids
+    /// * `transform` - the `GlobalTransform` associated to the cloth entity
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding a local position for each id of `ids`, or `None`
+    /// for out of bounds ids
+    pub fn local_positions<'a>(
+        &'a self,
+        ids: impl IntoIterator<Item = usize> + 'a,
+        transform: &GlobalTransform,
+    ) -> impl Iterator<Item = Option<Vec3>> + 'a {
+        let matrix = transform.compute_matrix().inverse();
+        ids.into_iter().map(move |id| {
+            self.current_point_positions
+                .get(id)
+                .map(|p| matrix.transform_point3(*p))
+        })
+    }
+
+    /// Iterates over the anchored points, in no particular order, for
+    /// debugging and editor tooling.
+    ///
+    /// Each item is composed of the point id, its anchor and its initial local
+    /// space vertex position. The anchor world
+    /// space target can be resolved with [`VertexAnchor::get_position`].
+    pub fn anchors(&self) -> impl Iterator<Item = (usize, &VertexAnchor, Vec3)> + '_ {
+        self.anchored_points
+            .iter()
+            .map(|(id, (anchor, pos))| (*id, anchor, *pos))
+    }
+
+    /// Iterates over the cloth sticks, in no particular order, for custom
+    /// constraint solvers.
+    ///
+    /// Each item is composed of the stick id (the two connected point ids),
+    /// its target length and its [`StickMode`]. The stick stiffness is
+    /// available in [`Self::stick_stiffness`].
+    ///
+    /// To replace the built-in stick solver, set
+    /// [`ClothConfig::sticks_computation_depth`] to 0 so that the cloth
+    /// update only integrates the points and follows the anchors, then solve
+    /// the sticks in a system ordered after [`ClothSystems::Update`] and
+    /// before [`ClothSystems::Render`], and write the result back with
+    /// [`Self::apply_solved_positions`].
+    ///
+    /// [`ClothSystems::Update`]: crate::ClothSystems::Update
+    /// [`ClothSystems::Render`]: crate::ClothSystems::Render
+    pub fn iter_sticks(&self) -> impl Iterator<Item = (StickId, f32, StickMode)> + '_ {
+        self.stick_lengths.iter().map(|(id, len)| {
+            let mode = self.stick_modes.get(id).copied().unwrap_or_default();
+            (*id, *len, mode)
+        })
+    }
+
+    /// Replaces the world space point positions with the result of an
+    /// external solver (See [`Self::iter_sticks`]).
+    ///
+    /// Only [`Self::current_point_positions`] is updated: the
+    /// [`Self::previous_point_positions`] are kept, so the displacement
+    /// applied by the solver is carried over as velocity by the next Verlet
+    /// integration. Anchored points (See [`Self::is_anchored`]) are left
+    /// at their anchor positions, and positions beyond the point count are
+    /// ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - the solved world space positions, by point id
+    pub fn apply_solved_positions(&mut self, positions: &[Vec3]) {
+        self.cache.point_grid_stale = true;
+        for (id, (current, solved)) in self
+            .current_point_positions
+            .iter_mut()
+            .zip(positions)
+            .enumerate()
+        {
+            if !self.anchored_mask.is_pinned(id) {
+                *current = *solved;
+            }
+        }
+    }
+
+    /// Overwrites every world space point position, for example from a baked
+    /// animation, a replay or a network synchronized state.
+    ///
+    /// Unlike [`Self::apply_solved_positions`], the anchored points are
+    /// overwritten too, they will move back to their anchor on the next
+    /// update.
+    ///
+    /// # Arguments
+    ///
+    /// * `world_positions` - the new world space positions, by point id
+    /// * `keep_velocity` - if set to true, the
+    ///   [`Self::previous_point_positions`] are offset along with the points to
+    ///   preserve their velocity, otherwise they are reset to the new
+    ///   positions, stopping the points
+    ///
+    /// # Panics
+    ///
+    /// Panics if `world_positions` doesn't have one position per cloth point
+    pub fn set_positions(&mut self, world_positions: &[Vec3], keep_velocity: bool) {
+        self.cache.point_grid_stale = true;
+        assert_eq!(
+            world_positions.len(),
+            self.current_point_positions.len(),
+            "Cloth positions count mismatch"
+        );
+        if keep_velocity {
+            for ((previous, current), new) in self
+                .previous_point_positions
+                .iter_mut()
+                .zip(&self.current_point_positions)
+                .zip(world_positions)
+            {
+                *previous += *new - *current;
+            }
+        } else {
+            self.previous_point_positions.clear();
+            self.previous_point_positions
+                .extend_from_slice(world_positions);
+        }
+        self.current_point_positions
+            .copy_from_slice(world_positions);
+    }
+
+    /// Computes the new local vertex positions of the cloth mesh into `buf`,
+    /// reusing its allocation.
+    ///
+    /// See [`Self::compute_vertex_positions`] for the allocating version.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - the `GlobalTransform` associated to the cloth entity
+    /// * `buf` - the buffer to clear and fill with the vertex positions
+    pub fn compute_vertex_positions_into(&self, transform: &GlobalTransform, buf: &mut Vec<Vec3>) {
+        buf.clear();
+        buf.extend(self.compute_vertex_positions(transform));
+    }
+
     /// Creates a new cloth from a mesh. Points positions will be directly
     /// extracted from the given vertex positions and the sticks will be
     /// extracted from the given `indices` (triangles) according to
     /// the associated [`StickGeneration`] and [`StickLen`].
     ///
+    /// Sticks with a zero length, typically between coincident vertices, are
+    /// skipped.
+    ///
     /// # Arguments
     ///
     /// * `vertex_positions` - the mesh vertex positions
@@ -101,15 +434,7 @@ impl Cloth {
         stick_mode: StickMode,
         transform_matrix: &Mat4,
     ) -> Self {
-        let anchored_points = anchored_points
-            .into_iter()
-            .map(|(i, anchor)| {
-                let pos = vertex_positions
-                    .get(i)
-                    .unwrap_or_else(|| panic!("Anchored vertex id {i} is out of bounds"));
-                (i, (anchor, *pos))
-            })
-            .collect();
+        let anchored_points = Self::initial_anchored_points(vertex_positions, anchored_points);
         let positions: Vec<Vec3> = vertex_positions
             .iter()
             .map(|p| transform_matrix.transform_point3(*p))
@@ -123,27 +448,302 @@ impl Cloth {
             let [a, b, c] = [truple[0], truple[1], truple[2]];
             let [p_a, p_b, p_c] = [positions[a], positions[b], positions[c]];
             if !stick_lengths.contains_key(&[b, a]) {
-                stick_lengths.insert([a, b], stick_len.get_len(p_a, p_b));
+                stick_lengths.insert([a, b], stick_len.get_stick_len([a, b], p_a, p_b));
             }
             if !stick_lengths.contains_key(&[c, b]) {
-                stick_lengths.insert([b, c], stick_len.get_len(p_b, p_c));
+                stick_lengths.insert([b, c], stick_len.get_stick_len([b, c], p_b, p_c));
             }
             if stick_generation == StickGeneration::Triangles
                 && !stick_lengths.contains_key(&[a, c])
             {
-                stick_lengths.insert([c, a], stick_len.get_len(p_c, p_a));
+                stick_lengths.insert([c, a], stick_len.get_stick_len([c, a], p_c, p_a));
+            }
+        }
+        let mut stick_stiffness = HashMap::default();
+        if let StickGeneration::Full {
+            shear_stiffness,
+            bend_stiffness,
+        } = stick_generation
+        {
+            let (shear_sticks, bend_sticks) = Self::shear_and_bend_sticks(&indices, &positions);
+            for (sticks, stiffness) in [
+                (shear_sticks, shear_stiffness),
+                (bend_sticks, bend_stiffness),
+            ] {
+                for [a, b] in sticks {
+                    if stick_lengths.contains_key(&[a, b]) || stick_lengths.contains_key(&[b, a]) {
+                        continue;
+                    }
+                    stick_lengths.insert(
+                        [a, b],
+                        stick_len.get_stick_len([a, b], positions[a], positions[b]),
+                    );
+                    stick_stiffness.insert([a, b], stiffness);
+                }
+            }
+        }
+        Self::remove_degenerate_sticks(&mut stick_lengths);
+        stick_stiffness.retain(|id, _| stick_lengths.contains_key(id));
+        let stick_modes = stick_lengths.keys().map(|id| (*id, stick_mode)).collect();
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|truple| [truple[0], truple[1], truple[2]])
+            .collect();
+        let mut cloth = Self {
+            anchored_points,
+            spring_anchored_points: HashMap::default(),
+            current_point_positions: positions.clone(),
+            previous_point_positions: positions,
+            point_masses: Vec::new(),
+            rest_positions: vertex_positions.to_vec(),
+            stick_lengths,
+            stick_modes,
+            stick_stiffness,
+            triangles,
+            last_translation: None,
+            last_displacement: None,
+            settled: false,
+            released_anchors: Vec::new(),
+            sim_time: 0.0,
+            phase_offset: 0.0,
+            rest_scale: transform_matrix.to_scale_rotation_translation().0,
+            connected_components: Vec::new(),
+            anchored_mask: AnchorMask::default(),
+            cache: ClothCache::default(),
+        };
+        cloth.update_anchored_mask();
+        cloth.update_components();
+        cloth
+    }
+
+    /// Tracks the cloth entity movement and computes its frame to frame
+    /// acceleration, expressed as a displacement per squared frame like the
+    /// verlet point velocities.
+    ///
+    /// The returned value is zero until the cloth has been tracked for two
+    /// frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `translation` - the current world space translation of the cloth
+    ///   entity
+    pub fn track_inertia(&mut self, translation: Vec3) -> Vec3 {
+        let displacement = self.last_translation.map(|last| translation - last);
+        let acceleration = displacement
+            .zip(self.last_displacement)
+            .map_or(Vec3::ZERO, |(current, last)| current - last);
+        self.last_translation = Some(translation);
+        self.last_displacement = displacement;
+        acceleration
+    }
+
+    /// Computes the shear and bend sticks of a grid-like mesh (See
+    /// [`StickGeneration::Full`]):
+    ///
+    /// * shear sticks are both diagonals of every quad, made of two triangles
+    ///   sharing their third edge
+    /// * bend sticks connect the two structural neighbors of a point that are
+    ///   aligned with it, spanning two cells
+    fn shear_and_bend_sticks(
+        indices: &[usize],
+        positions: &[Vec3],
+    ) -> (Vec<StickId>, Vec<StickId>) {
+        let mut diagonals: HashMap<StickId, Vec<usize>> = HashMap::default();
+        let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::default(); positions.len()];
+        for truple in indices.chunks_exact(3) {
+            let [a, b, c] = [truple[0], truple[1], truple[2]];
+            diagonals.entry([a.min(c), a.max(c)]).or_default().push(b);
+            for [p1, p2] in [[a, b], [b, c]] {
+                neighbors[p1].insert(p2);
+                neighbors[p2].insert(p1);
+            }
+        }
+        let mut shear_sticks = Vec::with_capacity(diagonals.len() * 2);
+        for (diagonal, opposites) in diagonals {
+            shear_sticks.push(diagonal);
+            if let [a, b] = opposites[..] {
+                shear_sticks.push([a, b]);
+            }
+        }
+        let mut bend_sticks = Vec::new();
+        for (center, point_neighbors) in neighbors.iter().enumerate() {
+            for a in point_neighbors {
+                let dir_a = (positions[*a] - positions[center]).normalize_or_zero();
+                for b in point_neighbors.iter().filter(|b| *b > a) {
+                    let dir_b = (positions[*b] - positions[center]).normalize_or_zero();
+                    if dir_a.dot(dir_b) < -0.9 {
+                        bend_sticks.push([*a, *b]);
+                    }
+                }
             }
         }
+        (shear_sticks, bend_sticks)
+    }
+
+    /// Creates a new cloth from explicit point and stick data, without any
+    /// mesh.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_positions` - the local space point positions
+    /// * `sticks` - the point indexes pairs to connect with a stick
+    /// * `anchored_points` - the pinned point indices
+    /// * `stick_len` - The stick length option
+    /// * `stick_mode` - The stick behaviour mode
+    /// * `transform_matrix` - the transform matrix of the associated
+    ///   `GlobalTransform`
+    ///
+    /// Anchors and sticks with an out of bounds point id are skipped, logging
+    /// an error.
+    pub fn from_sticks(
+        point_positions: &[Vec3],
+        sticks: &[StickId],
+        anchored_points: HashMap<usize, VertexAnchor>,
+        stick_len: StickLen,
+        stick_mode: StickMode,
+        transform_matrix: &Mat4,
+    ) -> Self {
+        let anchored_points = Self::initial_anchored_points(point_positions, anchored_points);
+        let positions: Vec<Vec3> = point_positions
+            .iter()
+            .map(|p| transform_matrix.transform_point3(*p))
+            .collect();
+        let in_bounds = |[a, b]: &&StickId| *a < positions.len() && *b < positions.len();
+        let mut stick_lengths: HashMap<StickId, f32> = sticks
+            .iter()
+            .filter(in_bounds)
+            .map(|[a, b]| {
+                (
+                    [*a, *b],
+                    stick_len.get_stick_len([*a, *b], positions[*a], positions[*b]),
+                )
+            })
+            .collect();
+        // Duplicate sticks are merged silently
+        let invalid = sticks.iter().filter(|id| !in_bounds(id)).count();
+        if invalid > 0 {
+            log::error!("Skipped {invalid} sticks with out of bounds point ids");
+        }
+        Self::remove_degenerate_sticks(&mut stick_lengths);
         let stick_modes = stick_lengths.keys().map(|id| (*id, stick_mode)).collect();
-        Self {
+        let mut cloth = Self {
             anchored_points,
+            spring_anchored_points: HashMap::default(),
             current_point_positions: positions.clone(),
             previous_point_positions: positions,
+            point_masses: Vec::new(),
+            rest_positions: point_positions.to_vec(),
             stick_lengths,
             stick_modes,
+            stick_stiffness: HashMap::default(),
+            triangles: Vec::new(),
+            last_translation: None,
+            last_displacement: None,
+            settled: false,
+            released_anchors: Vec::new(),
+            sim_time: 0.0,
+            phase_offset: 0.0,
+            rest_scale: transform_matrix.to_scale_rotation_translation().0,
+            connected_components: Vec::new(),
+            anchored_mask: AnchorMask::default(),
+            cache: ClothCache::default(),
+        };
+        cloth.update_anchored_mask();
+        cloth.update_components();
+        cloth
+    }
+
+    /// Creates a new cloth from quad topology data, generating the sticks from
+    /// the quad corners instead of triangles.
+    ///
+    /// Every quad generates its four edges as structural sticks and its two
+    /// diagonals as shear sticks, avoiding the asymmetric sticks of a
+    /// triangulated grid. The quads are split in two triangles for the normal
+    /// computation.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_positions` - the mesh vertex positions
+    /// * `quads` - the quads vertex ids, in winding order
+    /// * `anchored_points` - the pinned vertex position indices
+    /// * `stick_len` - The stick length option
+    /// * `stick_mode` - The stick behaviour mode
+    /// * `transform_matrix` - the transform matrix of the associated
+    ///   `GlobalTransform`
+    ///
+    /// Anchors and quads with an out of bounds vertex id are skipped, logging
+    /// an error.
+    pub fn from_quads(
+        vertex_positions: &[Vec3],
+        quads: &[[usize; 4]],
+        anchored_points: HashMap<usize, VertexAnchor>,
+        stick_len: StickLen,
+        stick_mode: StickMode,
+        transform_matrix: &Mat4,
+    ) -> Self {
+        let valid_quads: Vec<[usize; 4]> = quads
+            .iter()
+            .filter(|quad| quad.iter().all(|id| *id < vertex_positions.len()))
+            .copied()
+            .collect();
+        if valid_quads.len() < quads.len() {
+            log::error!(
+                "Skipped {} quads with out of bounds vertex ids",
+                quads.len() - valid_quads.len()
+            );
+        }
+        let quads = valid_quads;
+        let mut edges = HashSet::with_capacity(quads.len() * 4);
+        let sticks: Vec<StickId> = quads
+            .iter()
+            .flat_map(|[a, b, c, d]| [[*a, *b], [*b, *c], [*c, *d], [*d, *a], [*a, *c], [*b, *d]])
+            .filter(|[a, b]| edges.insert([*a.min(b), *a.max(b)]))
+            .collect();
+        let mut cloth = Self::from_sticks(
+            vertex_positions,
+            &sticks,
+            anchored_points,
+            stick_len,
+            stick_mode,
+            transform_matrix,
+        );
+        cloth.triangles = quads
+            .iter()
+            .flat_map(|[a, b, c, d]| [[*a, *b, *c], [*a, *c, *d]])
+            .collect();
+        cloth
+    }
+
+    /// Removes the sticks with a zero target length, which can't be solved
+    fn remove_degenerate_sticks(stick_lengths: &mut HashMap<StickId, f32>) {
+        let count = stick_lengths.len();
+        stick_lengths.retain(|_, len| *len > f32::EPSILON);
+        let removed = count - stick_lengths.len();
+        if removed > 0 {
+            log::warn!(
+                "Skipped {removed} zero length sticks, the cloth may have coincident points"
+            );
         }
     }
 
+    /// Associates the initial anchors to their local space vertex position,
+    /// skipping the out of bounds vertex ids
+    fn initial_anchored_points(
+        positions: &[Vec3],
+        anchored_points: HashMap<usize, VertexAnchor>,
+    ) -> HashMap<usize, (VertexAnchor, Vec3)> {
+        anchored_points
+            .into_iter()
+            .filter_map(|(i, anchor)| {
+                let Some(pos) = positions.get(i).copied() else {
+                    log::error!("Skipped anchored vertex id {i}, out of bounds");
+                    return None;
+                };
+                Some((i, (anchor, pos)))
+            })
+            .collect()
+    }
+
     /// Changes the stick behaviour to `new_mode` for `sticks`
     pub fn edit_stick_modes(&mut self, sticks: &[StickId], new_mode: StickMode) {
         log::debug!("Editing {} sticks: {new_mode:#?}", sticks.len());
@@ -159,6 +759,85 @@ impl Cloth {
         }
     }
 
+    /// Scales every stick target length by `factor`.
+    ///
+    /// The current targets are scaled, not the initial mesh lengths, so
+    /// tweening the factor toward a value below 1.0 over several frames
+    /// progressively shrinks the cloth, tightening it around the surfaces it
+    /// rests on (e.g. for a wet cloth effect). Scale it back with
+    /// `factor.recip()` to restore the previous lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - the scale to apply, negative values are clamped to zero
+    pub fn scale_rest_lengths(&mut self, factor: f32) {
+        let factor = factor.max(0.0);
+        for len in self.stick_lengths.values_mut() {
+            *len *= factor;
+        }
+        self.invalidate_cache();
+    }
+
+    /// Follows a change of the cloth entity world space `scale`, scaling the
+    /// stick lengths by the ratio between `scale` and [`Self::rest_scale`].
+    ///
+    /// The stick lengths are computed in world space from the scaled mesh, so
+    /// this keeps the cloth proportions consistent with its authored mesh when
+    /// the entity or one of its parents is scaled at runtime. The points are
+    /// not moved, the sticks pull them to the new size over the next updates.
+    ///
+    /// Only uniform scale changes can be followed: a non uniform change would
+    /// scale every stick differently depending on its direction. In that case
+    /// the stick lengths are kept and a warning is logged once.
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - the cloth entity world space scale
+    pub fn follow_scale(&mut self, scale: Vec3) {
+        if scale == self.rest_scale {
+            return;
+        }
+        if self.rest_scale.min_element() > f32::EPSILON {
+            let ratio = scale / self.rest_scale;
+            if (ratio - ratio.x).abs().max_element() > 1e-4 {
+                log::warn_once!(
+                    "Cloth entity scale changed non uniformly from {} to {}, the cloth keeps its \
+                     previous stick lengths",
+                    self.rest_scale,
+                    scale
+                );
+            } else {
+                self.scale_rest_lengths(ratio.x);
+            }
+        }
+        self.rest_scale = scale;
+    }
+
+    /// Slides the given anchored points by `offset`, adding it to their
+    /// [`VertexAnchor::custom_offset`].
+    ///
+    /// Animating the offset over several frames, for example to gather the
+    /// pinned top vertices of a curtain to one side, moves the anchors
+    /// smoothly and the rest of the cloth follows.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - the anchored point ids to slide. Non anchored ids are ignored
+    /// * `offset` - the offset to add, in the anchor target's space
+    pub fn slide_anchors(&mut self, ids: &[usize], offset: Vec3) {
+        for id in ids {
+            self.anchored_points.get_mut(id).map_or_else(
+                || {
+                    log::warn!("Attempted to slide non anchored point `{id}`");
+                },
+                |(anchor, _)| {
+                    anchor.custom_offset =
+                        Some(anchor.custom_offset.unwrap_or(Vec3::ZERO) + offset);
+                },
+            );
+        }
+    }
+
     /// Adds an extra point to the cloth (Not included in the base mesh) and
     /// returns its id and associated stick ids.
     pub fn add_point(
@@ -172,6 +851,7 @@ impl Cloth {
         let center = transform_matrix.transform_point3(pos);
         self.current_point_positions.push(center);
         self.previous_point_positions.push(center);
+        self.rest_positions.push(pos);
         let id = self.current_point_positions.len().saturating_sub(1);
         let sticks: Vec<_> = self
             .current_point_positions
@@ -189,12 +869,127 @@ impl Cloth {
             "Added custom point {pos:?} and {} sticks with to it: {stick_mode:#?}",
             sticks.len()
         );
+        let axes = anchor.as_ref().map_or(BVec3::FALSE, |a| a.constrain_axes);
         if let Some(anchor) = anchor {
             self.anchored_points.insert(id, (anchor, pos));
         }
+        self.anchored_mask.set(id, axes);
+        self.invalidate_cache();
+        self.update_components();
         (id, sticks)
     }
 
+    /// Merges `other` into this cloth, like sewing two panels together, and
+    /// connects the `seams` vertex pairs with new sticks.
+    ///
+    /// The `other` points are appended after the current points: every point
+    /// id of `other` (in its sticks, anchors, triangles and masses) is offset
+    /// by the current point count. The seam sticks are [`StickMode::Fixed`]
+    /// and their target length is the current distance between their points,
+    /// so move the points together first for a tight seam.
+    ///
+    /// Both cloths must share the same world space, and the same entity local
+    /// space for their anchors and [`Self::rest_positions`]. If the `other`
+    /// [`Self::rest_scale`] is uniformly different, its stick lengths are
+    /// scaled to this cloth rest scale. The cloth entity state (inertia,
+    /// settled state, simulation time) is kept.
+    ///
+    /// Note: the cloth mesh isn't merged. The rendered mesh must be merged
+    /// accordingly by the user, appending the `other` vertices and offsetting
+    /// its indices the same way, or the extra points won't be rendered (See
+    /// [`ClothRendering`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the cloth to merge into this one
+    /// * `seams` - pairs of point ids, from this cloth and from `other`, to
+    ///   connect with a stick. Pairs with an out of bounds id are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MismatchedRestScale`] if the two cloths rest scales
+    /// are not uniformly proportional, leaving this cloth unchanged.
+    ///
+    /// [`ClothRendering`]: crate::components::cloth_rendering::ClothRendering
+    pub fn merge(&mut self, mut other: Self, seams: &[(usize, usize)]) -> Result<(), Error> {
+        if other.rest_scale != self.rest_scale {
+            let ratio = self.rest_scale / other.rest_scale;
+            if !ratio.is_finite() || (ratio - ratio.x).abs().max_element() > 1e-4 {
+                return Err(Error::MismatchedRestScale {
+                    rest_scale: self.rest_scale,
+                    other: other.rest_scale,
+                });
+            }
+            other.scale_rest_lengths(ratio.x);
+        }
+        let offset = self.current_point_positions.len();
+        let other_len = other.current_point_positions.len();
+        let remap = |[a, b]: StickId| [a + offset, b + offset];
+        if !self.point_masses.is_empty() || !other.point_masses.is_empty() {
+            self.point_masses.resize(offset, 1.0);
+            self.point_masses.extend(&other.point_masses);
+        }
+        self.current_point_positions
+            .extend(other.current_point_positions);
+        self.previous_point_positions
+            .extend(other.previous_point_positions);
+        self.rest_positions.extend(other.rest_positions);
+        self.anchored_points.extend(
+            other
+                .anchored_points
+                .into_iter()
+                .map(|(i, anchor)| (i + offset, anchor)),
+        );
+        self.spring_anchored_points.extend(
+            other
+                .spring_anchored_points
+                .into_iter()
+                .map(|(i, anchor)| (i + offset, anchor)),
+        );
+        self.stick_lengths.extend(
+            other
+                .stick_lengths
+                .into_iter()
+                .map(|(id, len)| (remap(id), len)),
+        );
+        self.stick_modes.extend(
+            other
+                .stick_modes
+                .into_iter()
+                .map(|(id, mode)| (remap(id), mode)),
+        );
+        self.stick_stiffness.extend(
+            other
+                .stick_stiffness
+                .into_iter()
+                .map(|(id, stiffness)| (remap(id), stiffness)),
+        );
+        self.triangles.extend(
+            other
+                .triangles
+                .into_iter()
+                .map(|triangle| triangle.map(|i| i + offset)),
+        );
+        self.released_anchors
+            .extend(other.released_anchors.into_iter().map(|i| i + offset));
+        for (a, b) in seams {
+            if *a >= offset || *b >= other_len {
+                log::warn!("Ignoring out of bounds cloth seam {a} - {b}");
+                continue;
+            }
+            let id = [*a, *b + offset];
+            let (p_a, p_b) = (
+                self.current_point_positions[id[0]],
+                self.current_point_positions[id[1]],
+            );
+            self.stick_lengths.insert(id, p_a.distance(p_b));
+            self.stick_modes.insert(id, StickMode::Fixed);
+        }
+        self.update_anchored_mask();
+        self.update_components();
+        Ok(())
+    }
+
     /// Solves cloth points collisions, moving them outside of colliders
     ///
     /// # Arguments
@@ -202,33 +997,895 @@ impl Cloth {
     /// * `solve_point` - function taking a cloth point and returning the new
     ///   solved point
     pub fn solve_collisions(&mut self, solve_point: impl Fn(&Vec3) -> Option<Vec3>) {
-        for (point, new_point) in self
-            .current_point_positions
-            .iter_mut()
-            .enumerate()
-            .filter(|(i, _p)| !self.anchored_points.contains_key(i))
-            .filter_map(|(_i, p)| solve_point(p).map(|np| (p, np)))
-        {
-            *point = new_point;
-        }
+        self.solve_collisions_on_free_axes(BVec3::FALSE, solve_point);
     }
 
-    /// Updates the cloth anchored points
+    /// Solves cloth points collisions, moving them outside of colliders.
+    ///
+    /// The collision response is projected on the free axes: the `locked_axes`
+    /// components of the points are left untouched (See
+    /// [`ClothConfig::locked_axes`])
     ///
     /// # Arguments
     ///
-    /// * `transform` - The `GlobalTransform` associated to the cloth entity
-    /// * `anchor_query` - A function allowing to retrieve the `GlobalTransform`
-    ///   of a given entity
-    pub fn update_anchored_points<'a>(
+    /// * `locked_axes` - the axes on which the points can't move
+    /// * `solve_point` - function taking a cloth point and returning the new
+    ///   solved point
+    pub fn solve_collisions_on_free_axes(
         &mut self,
-        transform: &GlobalTransform,
-        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        locked_axes: BVec3,
+        solve_point: impl Fn(&Vec3) -> Option<Vec3>,
     ) {
-        for (i, (anchor, inital_pos)) in &self.anchored_points {
-            self.current_point_positions[*i] =
-                anchor.get_position(*inital_pos, transform, &anchor_query);
-        }
+        self.solve_swept_collisions_on_free_axes(locked_axes, |_previous, point| {
+            solve_point(point)
+        });
+    }
+
+    /// Solves cloth points collisions along their movement, moving them
+    /// outside of colliders.
+    ///
+    /// Contrary to [`Self::solve_collisions_on_free_axes`], the solving
+    /// function also receives the previous position of each point, allowing
+    /// to test the whole segment travelled during the last step and to catch
+    /// points tunneling through thin colliders.
+    ///
+    /// The push toward the solved position is scaled by the inverse of the
+    /// [`Self::point_masses`], relative to the lightest point, a heavy point
+    /// being pushed out over several updates. With uniform masses every point
+    /// is moved to its solved position.
+    ///
+    /// # Arguments
+    ///
+    /// * `locked_axes` - the axes on which the points can't move
+    /// * `solve_point` - function taking the previous and current positions of
+    ///   a cloth point and returning the new solved point
+    pub fn solve_swept_collisions_on_free_axes(
+        &mut self,
+        locked_axes: BVec3,
+        solve_point: impl Fn(&Vec3, &Vec3) -> Option<Vec3>,
+    ) {
+        let lightest_mass = self.lightest_mass();
+        for i in 0..self.current_point_positions.len() {
+            if self.anchored_mask.is_pinned(i) {
+                continue;
+            }
+            let point = self.current_point_positions[i];
+            let previous = self.previous_point_positions.get(i).unwrap_or(&point);
+            let Some(new_point) = solve_point(previous, &point) else {
+                continue;
+            };
+            let new_point = Vec3::select(locked_axes, point, new_point);
+            self.apply_mass_response(lightest_mass, i, new_point);
+        }
+    }
+
+    /// Solves the cloth collisions against several colliders, each contact
+    /// being solved `iterations` times with a single sticks pass in between
+    /// (See [`Self::solve_swept_collisions_on_free_axes`]), converging the
+    /// collisions and the stick constraints together on deep contacts.
+    ///
+    /// The cost scales as `iterations × contacts` collision passes over the
+    /// cloth points.
+    ///
+    /// # Arguments
+    ///
+    /// * `contacts` - one solving function per contacting collider, taking the
+    ///   previous and current positions of a cloth point and returning the new
+    ///   solved point
+    /// * `iterations` - the number of iterations per contact, at least 1
+    /// * `locked_axes` - the axes on which the points can't move
+    pub fn solve_contacts<F>(&mut self, contacts: &[F], iterations: u8, locked_axes: BVec3)
+    where
+        F: Fn(&Vec3, &Vec3) -> Option<Vec3>,
+    {
+        for solve in contacts {
+            for i in 0..iterations.max(1) {
+                if i > 0 {
+                    self.update_sticks(1);
+                }
+                self.solve_swept_collisions_on_free_axes(locked_axes, solve);
+            }
+        }
+    }
+
+    /// Retrieves the lightest of the [`Self::point_masses`], if any
+    fn lightest_mass(&self) -> Option<f32> {
+        // Points missing from the list have a mass of 1.0
+        let missing = self.point_masses.len() < self.current_point_positions.len();
+        self.point_masses
+            .iter()
+            .copied()
+            .filter(|m| *m > 0.0)
+            .chain(missing.then_some(1.0))
+            .reduce(f32::min)
+    }
+
+    /// Retrieves the mass of point `i`, 1.0 if missing from the
+    /// [`Self::point_masses`]
+    fn point_mass(&self, i: usize) -> f32 {
+        self.point_masses
+            .get(i)
+            .copied()
+            .filter(|m| *m > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Moves point `i` toward its collision response `new_point`, scaling the
+    /// push by its inverse mass, relative to the `lightest_mass`
+    fn apply_mass_response(&mut self, lightest_mass: Option<f32>, i: usize, new_point: Vec3) {
+        self.cache.point_grid_stale = true;
+        let mass = self.point_mass(i);
+        let point = &mut self.current_point_positions[i];
+        *point = match lightest_mass {
+            Some(lightest_mass) if mass > lightest_mass => {
+                point.lerp(new_point, lightest_mass / mass)
+            }
+            _ => new_point,
+        };
+    }
+
+    /// Retrieves the share of a stick correction applied to each of its
+    /// points `a` and `b`, by inverse mass. `None` with uniform masses,
+    /// meaning an even split
+    fn stick_mass_shares(&self, a: usize, b: usize) -> Option<(f32, f32)> {
+        if self.point_masses.is_empty() {
+            return None;
+        }
+        let (mass_a, mass_b) = (self.point_mass(a), self.point_mass(b));
+        let share_a = mass_b / (mass_a + mass_b);
+        Some((share_a, 1.0 - share_a))
+    }
+
+    /// Solves cloth points collisions, moving them outside of colliders and
+    /// reporting every contact.
+    ///
+    /// Contrary to [`Self::solve_collisions`], the solving function returns
+    /// the full [`Contact`] data, allowing custom collision integrations to
+    /// apply friction or emit events from the returned contacts.
+    ///
+    /// The points are moved to the contact positions, gaining a push velocity
+    /// scaled by their inverse mass, like in
+    /// [`Self::solve_swept_collisions_on_free_axes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `solve_point` - function taking a cloth point and returning its
+    ///   contact, if any
+    ///
+    /// # Returns
+    ///
+    /// The point ids and their contact
+    pub fn solve_collisions_detailed(
+        &mut self,
+        mut solve_point: impl FnMut(&Vec3) -> Option<Contact>,
+    ) -> Vec<(usize, Contact)> {
+        let mut contacts = Vec::new();
+        let lightest_mass = self.lightest_mass();
+        for i in 0..self.current_point_positions.len() {
+            if self.anchored_mask.is_pinned(i) {
+                continue;
+            }
+            if let Some(contact) = solve_point(&self.current_point_positions[i]) {
+                self.apply_mass_response(lightest_mass, i, contact.position);
+                contacts.push((i, contact));
+            }
+        }
+        contacts
+    }
+
+    /// Lifts the non anchored points below `height` back to it, on the world
+    /// space Y axis (See [`ClothConfig::floor_height`])
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - the minimum world space height of the points
+    pub fn clamp_to_floor(&mut self, height: f32) {
+        self.cache.point_grid_stale = true;
+        for (i, point) in self.current_point_positions.iter_mut().enumerate() {
+            if point.y < height && !self.anchored_mask.is_pinned(i) {
+                point.y = height;
+            }
+        }
+    }
+
+    /// Restores the `locked_axes` components of the non anchored points to
+    /// their previous value, keeping the cloth on its original plane(s).
+    ///
+    /// # Arguments
+    ///
+    /// * `locked_axes` - the axes on which the points can't move
+    pub fn restore_locked_axes(&mut self, locked_axes: BVec3) {
+        if !locked_axes.any() {
+            return;
+        }
+        self.cache.point_grid_stale = true;
+        for (i, (point, previous)) in self
+            .current_point_positions
+            .iter_mut()
+            .zip(&self.previous_point_positions)
+            .enumerate()
+        {
+            if !self.anchored_mask.is_pinned(i) {
+                *point = Vec3::select(locked_axes, *previous, *point);
+            }
+        }
+    }
+
+    /// Resolves the [`VertexAnchor::bone_name`] of the anchored and spring
+    /// anchored points into their [`VertexAnchor::custom_target`].
+    ///
+    /// `find_bone` is called once per distinct bone name and skeleton root,
+    /// the result being shared by every anchor of that bone. Anchors whose
+    /// bone can't be found keep their current target.
+    ///
+    /// # Arguments
+    ///
+    /// * `find_bone` - function retrieving a bone entity from its name and its
+    ///   optional [`VertexAnchor::skeleton_root`]
+    ///
+    /// # Returns
+    ///
+    /// The names of the bones which couldn't be found
+    pub fn resolve_bone_anchors(
+        &mut self,
+        mut find_bone: impl FnMut(&str, Option<Entity>) -> Option<Entity>,
+    ) -> Vec<String> {
+        let mut bones: HashMap<(String, Option<Entity>), Option<Entity>> = HashMap::default();
+        let anchors = self
+            .anchored_points
+            .values_mut()
+            .map(|(anchor, _)| anchor)
+            .chain(
+                self.spring_anchored_points
+                    .values_mut()
+                    .map(|(anchor, _, _)| anchor),
+            );
+        for anchor in anchors {
+            let Some(name) = &anchor.bone_name else {
+                continue;
+            };
+            let bone = *bones
+                .entry((name.clone(), anchor.skeleton_root))
+                .or_insert_with(|| find_bone(name, anchor.skeleton_root));
+            if bone.is_some() {
+                anchor.custom_target = bone;
+            }
+        }
+        bones
+            .into_iter()
+            .filter(|(_, bone)| bone.is_none())
+            .map(|((name, _), _)| name)
+            .collect()
+    }
+
+    /// Adds spring anchors to the cloth, pulling the points toward their
+    /// anchor position proportionally to their stiffness every frame.
+    ///
+    /// Points already anchored (See [`Self::is_anchored`]) are ignored,
+    /// explicit anchors take precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_positions` - the local space vertex positions
+    /// * `anchors` - the point ids with their anchor and stiffness, between 0.0
+    ///   (free) and 1.0 (pinned)
+    pub fn insert_spring_anchors(
+        &mut self,
+        vertex_positions: &[Vec3],
+        anchors: HashMap<usize, (VertexAnchor, f32)>,
+    ) {
+        for (i, (anchor, stiffness)) in anchors {
+            if self.is_anchored(i) {
+                continue;
+            }
+            let Some(pos) = vertex_positions.get(i) else {
+                log::warn!("Spring anchored vertex id {i} is out of bounds");
+                continue;
+            };
+            self.spring_anchored_points
+                .insert(i, (anchor, *pos, stiffness.clamp(0.0, 1.0)));
+        }
+    }
+
+    /// Updates the cloth anchored points and pulls the spring anchored points
+    /// toward their anchor
+    ///
+    /// Anchored points are snapped to their anchor position, ignoring their
+    /// [`VertexAnchor::follow_speed`] (See [`Self::follow_anchored_points`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The `GlobalTransform` associated to the cloth entity
+    /// * `anchor_query` - A function allowing to retrieve the `GlobalTransform`
+    ///   of a given entity
+    /// * `path_query` - A function allowing to retrieve the [`ClothPath`] of a
+    ///   given entity
+    pub fn update_anchored_points<'a>(
+        &mut self,
+        transform: &GlobalTransform,
+        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        self.move_anchored_points(None, transform, anchor_query, path_query);
+    }
+
+    /// Updates the cloth anchored points and pulls the spring anchored points
+    /// toward their anchor, like [`Self::update_anchored_points`].
+    ///
+    /// Anchored points with a [`VertexAnchor::follow_speed`] are smoothly
+    /// moved toward their anchor position instead of snapping to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_time` - elapsed time since last frame in seconds
+    /// * `transform` - The `GlobalTransform` associated to the cloth entity
+    /// * `anchor_query` - A function allowing to retrieve the `GlobalTransform`
+    ///   of a given entity
+    /// * `path_query` - A function allowing to retrieve the [`ClothPath`] of a
+    ///   given entity
+    pub fn follow_anchored_points<'a>(
+        &mut self,
+        delta_time: f32,
+        transform: &GlobalTransform,
+        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        self.move_anchored_points(Some(delta_time), transform, anchor_query, path_query);
+    }
+
+    /// Moves the anchored points to their anchor, following it if a
+    /// `delta_time` is provided, and pulls the spring anchored points
+    fn move_anchored_points<'a>(
+        &mut self,
+        delta_time: Option<f32>,
+        transform: &GlobalTransform,
+        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        self.release_overloaded_anchors(transform, &anchor_query, &path_query);
+        self.cache.point_grid_stale = true;
+        for (i, (anchor, inital_pos)) in &self.anchored_points {
+            let target = anchor.get_position(*inital_pos, transform, &anchor_query, &path_query);
+            let point = &mut self.current_point_positions[*i];
+            let target = delta_time.map_or(target, |dt| anchor.follow(*point, target, dt));
+            *point = Vec3::select(anchor.constrain_axes, target, *point);
+        }
+        for (i, (anchor, inital_pos, stiffness)) in &self.spring_anchored_points {
+            let target = anchor.get_position(*inital_pos, transform, &anchor_query, &path_query);
+            let point = &mut self.current_point_positions[*i];
+            *point = Vec3::select(
+                anchor.constrain_axes,
+                point.lerp(target, *stiffness),
+                *point,
+            );
+        }
+    }
+
+    /// Retrieves the anchored points with free axes, with their constrained
+    /// axes and current position (See [`VertexAnchor::constrain_axes`])
+    fn axis_anchored_positions(&self) -> Vec<(usize, BVec3, Vec3)> {
+        self.anchored_points
+            .iter()
+            .filter(|(_, (anchor, _))| anchor.is_partial())
+            .filter_map(|(i, (anchor, _))| {
+                Some((
+                    *i,
+                    anchor.constrain_axes,
+                    *self.current_point_positions.get(*i)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Restores the constrained components of the `axis_anchors` positions
+    /// (See [`Self::axis_anchored_positions`])
+    fn restore_axis_anchors(&mut self, axis_anchors: &[(usize, BVec3, Vec3)]) {
+        for (i, axes, anchor_pos) in axis_anchors {
+            let point = &mut self.current_point_positions[*i];
+            *point = Vec3::select(*axes, *anchor_pos, *point);
+        }
+    }
+
+    /// Pins a vertex to `anchor`, making it follow the anchor instead of being
+    /// simulated. The anchor initial local space position is the vertex rest
+    /// position (See [`Self::rest_positions`]).
+    ///
+    /// With free axes (See [`VertexAnchor::constrain_axes`]) the vertex is
+    /// only pinned on the constrained axes, and stays simulated on the others.
+    ///
+    /// Out of bounds ids are ignored, logging a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the point id
+    /// * `anchor` - the anchor to follow
+    ///
+    /// # Returns
+    ///
+    /// The replaced anchor, or `None` if the point wasn't anchored
+    pub fn pin_vertex(&mut self, id: usize, anchor: VertexAnchor) -> Option<VertexAnchor> {
+        let Some(pos) = self.rest_positions.get(id).copied() else {
+            log::warn!("Attempted to pin out of bounds point `{id}`");
+            return None;
+        };
+        self.anchored_mask.set(id, anchor.constrain_axes);
+        self.invalidate_cache();
+        let previous = self.anchored_points.insert(id, (anchor, pos));
+        previous.map(|(anchor, _)| anchor)
+    }
+
+    /// Unpins an anchored vertex, turning it into a regular dynamic point.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the anchored point id
+    /// * `inherit_velocity` - If set to true, the point keeps the velocity of
+    ///   its anchor, estimated from its last two positions, and flies off if
+    ///   the anchor was moving. Otherwise the point starts at rest and drops
+    ///   gently.
+    ///
+    /// # Returns
+    ///
+    /// The removed anchor, or `None` if the point wasn't anchored
+    pub fn unpin_vertex(&mut self, id: usize, inherit_velocity: bool) -> Option<VertexAnchor> {
+        let (anchor, _) = self.anchored_points.remove(&id)?;
+        self.anchored_mask.set(id, BVec3::FALSE);
+        self.invalidate_cache();
+        // Anchored points previous positions are their anchor position on the
+        // last frame
+        if !inherit_velocity {
+            if let Some((previous, current)) = self
+                .previous_point_positions
+                .get_mut(id)
+                .zip(self.current_point_positions.get(id))
+            {
+                *previous = *current;
+            }
+        }
+        Some(anchor)
+    }
+
+    /// Releases the anchored points pulled harder than their
+    /// [`VertexAnchor::max_force`], turning them into regular dynamic points.
+    ///
+    /// The pulling force is estimated from the neighboring sticks tension:
+    /// every stretched stick connected to the anchored point pulls it toward
+    /// its other end by its excess length, measured from the anchor target
+    /// position. The force is the length of the sum of these pulls, which is
+    /// the offset the stick constraints would apply if the point was free.
+    fn release_overloaded_anchors<'a>(
+        &mut self,
+        transform: &GlobalTransform,
+        anchor_query: &impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: &impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        let mut pulls: HashMap<usize, (Vec3, Vec3, f32)> = self
+            .anchored_points
+            .iter()
+            .filter_map(|(i, (anchor, initial_pos))| {
+                let max_force = anchor.max_force?;
+                let target = anchor.get_position(*initial_pos, transform, anchor_query, path_query);
+                Some((*i, (target, Vec3::ZERO, max_force)))
+            })
+            .collect();
+        if pulls.is_empty() {
+            return;
+        }
+        for ([a, b], len) in &self.stick_lengths {
+            for (id, other) in [(a, b), (b, a)] {
+                let Some((target, pull, _)) = pulls.get_mut(id) else {
+                    continue;
+                };
+                let Some(other_pos) = self.current_point_positions.get(*other) else {
+                    continue;
+                };
+                let delta = *other_pos - *target;
+                let excess = delta.length() - len;
+                if excess > 0.0 {
+                    *pull += delta.normalize_or_zero() * excess;
+                }
+            }
+        }
+        for (id, (_, pull, max_force)) in pulls {
+            if pull.length() > max_force {
+                log::debug!("Releasing anchored point {id}, pulled by {}", pull.length());
+                self.unpin_vertex(id, true);
+                self.released_anchors.push(id);
+            }
+        }
+    }
+
+    /// Runs a full simulation step on the cloth, following the configured
+    /// [`SolveOrder`]:
+    ///
+    /// * Point integration (See [`Self::update_points`]), including the inertia
+    ///   pseudo-force (See [`Self::track_inertia`]) and the wind, along the
+    ///   surface normals if [`ClothConfig::sail_mode`] is enabled (See
+    ///   [`Self::apply_sail_point_wind`])
+    /// * Anchors update (See [`Self::follow_anchored_points`])
+    /// * Sticks constraints (See [`Self::update_sticks`]) and optional stretch
+    ///   limit (See [`Self::limit_stretch`])
+    /// * Locked axes restoration (See [`Self::restore_locked_axes`])
+    /// * Floor clamping, if [`ClothConfig::floor_height`] is set (See
+    ///   [`Self::clamp_to_floor`])
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - the cloth configuration
+    /// * `wind` - function returning the wind velocity at a given world space
+    ///   point position
+    /// * `delta_time` - elapsed time since last frame in seconds
+    /// * `transform` - The `GlobalTransform` associated to the cloth entity
+    /// * `anchor_query` - A function allowing to retrieve the `GlobalTransform`
+    ///   of a given entity
+    /// * `path_query` - A function allowing to retrieve the [`ClothPath`] of a
+    ///   given entity
+    pub fn step<'a>(
+        &mut self,
+        config: &ClothConfig,
+        wind: impl Fn(Vec3) -> Vec3,
+        delta_time: f32,
+        transform: &GlobalTransform,
+        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        let inertia = self.track_inertia(transform.translation()) * config.inertia_scale;
+        let gravity = if config.external_forces_only {
+            Vec3::ZERO
+        } else {
+            config.gravity
+        };
+        let acceleration = config.smoothed_acceleration(gravity, delta_time) - inertia;
+        let acceleration = Vec3::select(config.locked_axes, Vec3::ZERO, acceleration);
+        let friction = config.friction_coefficient();
+        let smooth_value = config.smooth_value(delta_time);
+        let wind =
+            |point: Vec3| Vec3::select(config.locked_axes, Vec3::ZERO, wind(point) * smooth_value);
+        match config.solve_order {
+            SolveOrder::PointsThenSticks => {
+                self.update_points(friction, acceleration);
+                if !config.external_forces_only {
+                    self.apply_wind(config.sail_mode, wind);
+                }
+                self.apply_shape_memory(config, smooth_value, transform);
+                self.update_anchors_and_sticks(
+                    config,
+                    delta_time,
+                    transform,
+                    anchor_query,
+                    path_query,
+                );
+                self.restore_locked_axes(config.locked_axes);
+            }
+            SolveOrder::SticksThenPoints => {
+                self.update_anchors_and_sticks(
+                    config,
+                    delta_time,
+                    transform,
+                    anchor_query,
+                    path_query,
+                );
+                self.restore_locked_axes(config.locked_axes);
+                self.update_points(friction, acceleration);
+                if !config.external_forces_only {
+                    self.apply_wind(config.sail_mode, wind);
+                }
+                self.apply_shape_memory(config, smooth_value, transform);
+            }
+        }
+        if let Some(height) = config.floor_height {
+            self.clamp_to_floor(height + config.floor_offset);
+        }
+    }
+
+    /// Retrieves the ids and positions of every cloth point within `radius`
+    /// of the world space `position`, like for point picking.
+    ///
+    /// The points are binned in a cached spatial hash, built on the first
+    /// query after the points moved and shared by every other query until
+    /// they move again. The cloth methods moving the points, like
+    /// [`Self::step`] or [`Self::set_positions`], invalidate it: call
+    /// [`Self::invalidate_cache`] after editing
+    /// [`Self::current_point_positions`] directly.
+    pub fn points_within(
+        &mut self,
+        position: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = (usize, Vec3)> + '_ {
+        self.point_grid().query_radius(position, radius)
+    }
+
+    /// Retrieves the ids and distances of the `count` cloth points nearest
+    /// to the world space `position`, sorted by distance (See
+    /// [`Self::points_within`])
+    pub fn nearest_points(&mut self, position: Vec3, count: usize) -> Vec<(usize, f32)> {
+        self.point_grid().query_nearest(position, count)
+    }
+
+    /// Retrieves the spatial hash of the current point positions, rebuilding
+    /// it if the points moved since the last build
+    fn point_grid(&mut self) -> &PointGrid {
+        let positions = &self.current_point_positions;
+        let cache = &mut self.cache;
+        if let Some(grid) = cache.point_grid.as_mut() {
+            if cache.point_grid_stale || grid.point_count() != positions.len() {
+                grid.rebuild(positions);
+            }
+        }
+        cache.point_grid_stale = false;
+        cache
+            .point_grid
+            .get_or_insert_with(|| PointGrid::from_surface(positions))
+    }
+
+    /// Pulls the non anchored points toward their [`Self::rest_positions`],
+    /// transformed by the current `transform`, with an acceleration
+    /// proportional to their distance (See [`ClothConfig::shape_memory`])
+    fn apply_shape_memory(
+        &mut self,
+        config: &ClothConfig,
+        smooth_value: f32,
+        transform: &GlobalTransform,
+    ) {
+        if config.shape_memory <= 0.0 {
+            return;
+        }
+        self.cache.point_grid_stale = true;
+        let strength = config.shape_memory * smooth_value;
+        let matrix = transform.compute_matrix();
+        for (i, (point, rest)) in self
+            .current_point_positions
+            .iter_mut()
+            .zip(&self.rest_positions)
+            .enumerate()
+        {
+            if self.anchored_mask.is_pinned(i) {
+                continue;
+            }
+            let pull = (matrix.transform_point3(*rest) - *point) * strength;
+            *point += Vec3::select(config.locked_axes, Vec3::ZERO, pull);
+        }
+    }
+
+    /// Applies the smoothed `wind` displacement, either uniformly like the
+    /// other accelerations (See [`Self::update_points`]) or along the surface
+    /// normals in `sail_mode` (See [`Self::apply_sail_wind`])
+    fn apply_wind(&mut self, sail_mode: bool, wind: impl Fn(Vec3) -> Vec3) {
+        if sail_mode {
+            self.apply_sail_point_wind(wind);
+            return;
+        }
+        self.cache.point_grid_stale = true;
+        for (i, point) in self.current_point_positions.iter_mut().enumerate() {
+            if !self.anchored_mask.is_pinned(i) {
+                *point += wind(*point);
+            }
+        }
+    }
+
+    /// Checks if the cloth is at rest: no point moved by more than `threshold`
+    /// during the last update.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - the maximum point displacement, in world units
+    #[must_use]
+    pub fn is_settled(&self, threshold: f32) -> bool {
+        let threshold_squared = threshold * threshold;
+        self.current_point_positions
+            .iter()
+            .zip(&self.previous_point_positions)
+            .all(|(current, previous)| current.distance_squared(*previous) <= threshold_squared)
+    }
+
+    /// Updates [`Self::settled`] from [`Self::is_settled`].
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - the maximum point displacement, in world units
+    ///
+    /// # Returns
+    ///
+    /// The new settled state if it changed, `None` otherwise
+    pub fn update_settled_state(&mut self, threshold: f32) -> Option<bool> {
+        let settled = self.is_settled(threshold);
+        if settled == self.settled {
+            return None;
+        }
+        self.settled = settled;
+        Some(settled)
+    }
+
+    /// Computes the world space normal of every cloth point, averaging the
+    /// normals of the [`Self::triangles`] it belongs to, weighted by their
+    /// area.
+    ///
+    /// Points outside of any triangle have a zero normal.
+    #[must_use]
+    pub fn compute_point_normals(&self) -> Vec<Vec3> {
+        let mut normals = vec![Vec3::ZERO; self.current_point_positions.len()];
+        for [a, b, c] in &self.triangles {
+            let [p_a, p_b, p_c] = [*a, *b, *c].map(|i| self.current_point_positions[i]);
+            // Non normalized, the cross product length is proportional to the area
+            let normal = (p_b - p_a).cross(p_c - p_a);
+            for i in [a, b, c] {
+                normals[*i] += normal;
+            }
+        }
+        for normal in &mut normals {
+            *normal = normal.normalize_or_zero();
+        }
+        normals
+    }
+
+    /// Computes the current world space surface area of the cloth, summing
+    /// the areas of its [`Self::triangles`].
+    ///
+    /// The area follows the cloth deformation: a furled sail or a folded
+    /// parachute has a smaller area than a deployed one, which can be used
+    /// to scale aerodynamic forces.
+    ///
+    /// Note: Cloths without triangles, like cloths built from explicit points,
+    /// have no area
+    #[must_use]
+    pub fn surface_area(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let [p_a, p_b, p_c] = [*a, *b, *c].map(|i| self.current_point_positions[i]);
+                (p_b - p_a).cross(p_c - p_a).length() / 2.0
+            })
+            .sum()
+    }
+
+    /// Applies the `wind` displacement to the non anchored points along their
+    /// surface normal, like a sail: the wind pushes whichever face it hits
+    /// proportionally to the angle between the wind and the surface, and the
+    /// force direction flips when the cloth luffs.
+    ///
+    /// Each point is moved by `n * n.dot(wind)` where `n` is the point normal
+    /// (See [`Self::compute_point_normals`]), so a surface parallel to the
+    /// wind catches nothing.
+    ///
+    /// Note: Without [`Self::triangles`] no wind is applied
+    ///
+    /// # Arguments
+    ///
+    /// * `wind` - the smoothed wind displacement
+    pub fn apply_sail_wind(&mut self, wind: Vec3) {
+        if wind != Vec3::ZERO {
+            self.apply_sail_point_wind(|_| wind);
+        }
+    }
+
+    /// Applies a per point wind displacement along the surface normals (See
+    /// [`Self::apply_sail_wind`])
+    ///
+    /// # Arguments
+    ///
+    /// * `wind` - function returning the smoothed wind displacement at a given
+    ///   world space point position
+    pub fn apply_sail_point_wind(&mut self, wind: impl Fn(Vec3) -> Vec3) {
+        if self.triangles.is_empty() {
+            return;
+        }
+        self.cache.point_grid_stale = true;
+        let normals = self.compute_point_normals();
+        for (i, (point, normal)) in self
+            .current_point_positions
+            .iter_mut()
+            .zip(normals)
+            .enumerate()
+        {
+            if !self.anchored_mask.is_pinned(i) {
+                *point += normal * normal.dot(wind(*point));
+            }
+        }
+    }
+
+    /// Applies a one shot localized wind gust, for scripted events like a door
+    /// opening next to a curtain.
+    ///
+    /// Every non anchored point within `radius` of `center` has its current
+    /// position offset along `direction`, by `strength` at the center and
+    /// linearly decreasing to zero at the `radius`. As the point velocities
+    /// are derived from their previous positions, the offset is carried over
+    /// the next updates as a velocity of `offset / delta_time`.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - the world space center of the gust
+    /// * `direction` - the gust direction, normalized by the method
+    /// * `strength` - the offset applied at the center, in world units
+    /// * `radius` - the gust radius, points further away are not affected
+    pub fn apply_wind_gust_at(
+        &mut self,
+        center: Vec3,
+        direction: Vec3,
+        strength: f32,
+        radius: f32,
+    ) {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO || radius <= 0.0 {
+            return;
+        }
+        self.cache.point_grid_stale = true;
+        for (i, point) in self.current_point_positions.iter_mut().enumerate() {
+            let distance = point.distance(center);
+            if distance < radius && !self.anchored_mask.is_pinned(i) {
+                *point += direction * strength * (1.0 - distance / radius);
+            }
+        }
+    }
+
+    /// Updates the anchored points and solves the sticks, interleaving both
+    /// in [`ClothConfig::anchor_substeps`] substeps.
+    ///
+    /// At this stage the anchored points still hold their previous anchor
+    /// position: every substep linearly interpolates them from that position
+    /// to their new anchor position, then solves the sticks.
+    fn update_anchors_and_sticks<'a>(
+        &mut self,
+        config: &ClothConfig,
+        delta_time: f32,
+        transform: &GlobalTransform,
+        anchor_query: impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: impl Fn(Entity) -> Option<&'a ClothPath>,
+    ) {
+        let substeps = config.anchor_substeps.max(1);
+        if substeps == 1 {
+            self.follow_anchored_points(delta_time, transform, anchor_query, path_query);
+            let axis_anchors = self.axis_anchored_positions();
+            self.solve_sticks(config);
+            self.restore_axis_anchors(&axis_anchors);
+            return;
+        }
+        // Collected before the anchors update moves the points
+        #[allow(clippy::needless_collect)]
+        let previous_positions: Vec<(usize, Vec3)> = self
+            .anchored_points
+            .keys()
+            .filter(|i| self.anchored_mask.is_pinned(**i))
+            .filter_map(|i| Some((*i, *self.current_point_positions.get(*i)?)))
+            .collect();
+        // Spring anchors are pulled once, toward their final target
+        self.follow_anchored_points(delta_time, transform, anchor_query, path_query);
+        let axis_anchors = self.axis_anchored_positions();
+        let anchor_moves: Vec<(usize, Vec3, Vec3)> = previous_positions
+            .into_iter()
+            .filter(|(i, _)| self.anchored_mask.is_pinned(*i))
+            .map(|(i, previous)| (i, previous, self.current_point_positions[i]))
+            .collect();
+        for substep in 1..=substeps {
+            let t = f32::from(substep) / f32::from(substeps);
+            for (i, previous, target) in &anchor_moves {
+                self.current_point_positions[*i] = previous.lerp(*target, t);
+            }
+            self.update_sticks_with_config(config);
+            self.restore_axis_anchors(&axis_anchors);
+        }
+        if let Some(ratio) = config.max_stretch_ratio {
+            self.limit_stretch(ratio);
+            self.restore_axis_anchors(&axis_anchors);
+        }
+    }
+
+    fn update_sticks_with_config(&mut self, config: &ClothConfig) {
+        if config.symmetric_solve {
+            self.update_sorted_sticks_cache();
+        }
+        self.solve_stick_passes(
+            config.effective_sticks_computation_depth(),
+            config.symmetric_solve,
+            config.anchor_stick_softness,
+        );
+    }
+
+    fn solve_sticks(&mut self, config: &ClothConfig) {
+        self.update_sticks_with_config(config);
+        if let Some(ratio) = config.max_stretch_ratio {
+            self.limit_stretch(ratio);
+        }
     }
 
     /// Updates the cloth points according to their own velocity and external
@@ -236,20 +1893,293 @@ impl Cloth {
     ///
     /// # Arguments
     ///
-    /// * `friction` - Friction to apply to the points velocity
-    /// * `acceleration` - Global acceleration force (gravity, wind, etc)
-    pub fn update_points(&mut self, friction: f32, acceleration: Vec3) {
-        let position_cache = self.current_point_positions.clone();
-        for (i, point) in self.current_point_positions.iter_mut().enumerate() {
-            if !self.anchored_points.contains_key(&i) {
-                let velocity = self
-                    .previous_point_positions
-                    .get(i)
-                    .map_or(Vec3::ZERO, |prev| *point - *prev);
-                *point += velocity * friction + acceleration * friction;
+    /// * `friction` - Friction coefficient to apply to the points velocity
+    /// * `acceleration` - Global acceleration force (gravity, wind, etc), not
+    ///   affected by the friction
+    ///
+    /// The current and previous position buffers are swapped and overwritten,
+    /// avoiding any per frame allocation.
+    pub fn update_points(&mut self, friction: f32, acceleration: Vec3) {
+        self.cache.point_grid_stale = true;
+        // Points without a previous position have no velocity
+        let prev_len = self.previous_point_positions.len();
+        if prev_len < self.current_point_positions.len() {
+            self.previous_point_positions
+                .extend_from_slice(&self.current_point_positions[prev_len..]);
+        }
+        self.previous_point_positions
+            .truncate(self.current_point_positions.len());
+        std::mem::swap(
+            &mut self.current_point_positions,
+            &mut self.previous_point_positions,
+        );
+        for (i, (point, current)) in self
+            .current_point_positions
+            .iter_mut()
+            .zip(&self.previous_point_positions)
+            .enumerate()
+        {
+            // `point` holds the previous position at this stage
+            *point = if self.anchored_mask.is_pinned(i) {
+                *current
+            } else {
+                let velocity = *current - *point;
+                *current + velocity * friction + acceleration
+            };
+        }
+    }
+
+    /// Sets the points velocity, offsetting their previous position. Anchored
+    /// points are ignored.
+    ///
+    /// The previous positions follow the verlet integration convention:
+    /// `previous = current - velocity * delta_time`, so a positive velocity
+    /// moves the point along its direction on the next update.
+    ///
+    /// # Arguments
+    ///
+    /// * `velocity` - a function returning the world space velocity, in units
+    ///   per second, from a point id and its world space position
+    /// * `delta_time` - the expected delta time of the next update, in seconds
+    pub fn set_initial_velocity(
+        &mut self,
+        velocity: impl Fn(usize, Vec3) -> Vec3,
+        delta_time: f32,
+    ) {
+        for (i, (previous, current)) in self
+            .previous_point_positions
+            .iter_mut()
+            .zip(&self.current_point_positions)
+            .enumerate()
+        {
+            if !self.anchored_mask.is_pinned(i) {
+                *previous = *current - velocity(i, *current) * delta_time;
+            }
+        }
+    }
+
+    /// Computes the cloth connected components from the stick network: every
+    /// group of points linked together by sticks, directly or not.
+    ///
+    /// Each component lists its point ids in ascending order and the
+    /// components are sorted by their smallest point id. A point without any
+    /// stick is a component on its own.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.stick_adjacency();
+        let mut visited = vec![false; adjacency.len()];
+        let mut components = Vec::new();
+        let mut queue = VecDeque::new();
+        for start in 0..adjacency.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            queue.push_back(start);
+            let mut component = Vec::new();
+            while let Some(id) = queue.pop_front() {
+                component.push(id);
+                for (neighbor, _) in &adjacency[id] {
+                    if !visited[*neighbor] {
+                        visited[*neighbor] = true;
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Recomputes [`Self::connected_components`] (See [`Self::components`])
+    pub fn update_components(&mut self) {
+        self.connected_components = self.components();
+    }
+
+    /// Whether the point `id` is anchored (See [`Self::anchors`]), on every
+    /// axis or only on some of them (See [`VertexAnchor::constrain_axes`]).
+    ///
+    /// Unlike a lookup in the anchored points map, this is a constant time
+    /// check in an internal mask, suited for loops over the cloth points.
+    /// Spring anchored points are not anchored.
+    #[inline]
+    #[must_use]
+    pub fn is_anchored(&self, id: usize) -> bool {
+        self.anchored_mask.axes(id).any()
+    }
+
+    /// Rebuilds the internal anchored points mask from
+    /// [`Self::anchored_points`] (See [`Self::is_anchored`])
+    fn update_anchored_mask(&mut self) {
+        let mask = &mut self.anchored_mask.0;
+        mask.clear();
+        mask.resize(self.current_point_positions.len(), BVec3::FALSE);
+        for (id, (anchor, _)) in &self.anchored_points {
+            self.anchored_mask.set(*id, anchor.constrain_axes);
+        }
+        self.invalidate_cache();
+    }
+
+    /// Retrieves the stick network as an adjacency list: for every point id,
+    /// the connected point ids and the associated stick target length
+    #[must_use]
+    pub fn stick_adjacency(&self) -> Vec<Vec<(usize, f32)>> {
+        let mut adjacency = vec![Vec::new(); self.current_point_positions.len()];
+        for ([a, b], len) in &self.stick_lengths {
+            if let Some(neighbors) = adjacency.get_mut(*a) {
+                neighbors.push((*b, *len));
+            }
+            if let Some(neighbors) = adjacency.get_mut(*b) {
+                neighbors.push((*a, *len));
+            }
+        }
+        adjacency
+    }
+
+    /// Walks the stick network breadth first from the `seeds` and retrieves
+    /// the number of sticks separating every reached point from its closest
+    /// seed.
+    ///
+    /// The seeds have a zero distance, and the walk stops `max_hops` sticks
+    /// away from them: further points are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - the starting point ids. Out of bounds ids are ignored
+    /// * `max_hops` - the maximum number of sticks to walk from the seeds
+    #[must_use]
+    pub fn stick_hops(&self, seeds: &[usize], max_hops: usize) -> HashMap<usize, usize> {
+        let adjacency = self.stick_adjacency();
+        let mut hops: HashMap<usize, usize> = seeds
+            .iter()
+            .filter(|id| **id < adjacency.len())
+            .map(|id| (*id, 0))
+            .collect();
+        let mut queue: VecDeque<usize> = hops.keys().copied().collect();
+        while let Some(id) = queue.pop_front() {
+            let next = hops[&id] + 1;
+            if next > max_hops {
+                continue;
+            }
+            for (neighbor, _) in &adjacency[id] {
+                if !hops.contains_key(neighbor) {
+                    hops.insert(*neighbor, next);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        hops
+    }
+
+    /// Limits the cloth stretch by walking the stick network from the
+    /// anchored points outward (breadth first) and pulling back every point
+    /// further than `max_ratio` times its stick target length from the point
+    /// it was reached from.
+    ///
+    /// Only the sticks present in [`Self::stick_lengths`] propagate the limit,
+    /// so cloth parts disconnected from any anchor are left untouched. The walk
+    /// is cached and rebuilt when the sticks or anchors change (See
+    /// [`Self::invalidate_cache`]): once a stick is removed, for example by a
+    /// custom tearing system, the points beyond it are only limited through
+    /// their remaining paths to an anchor.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_ratio` - the maximum stick stretch ratio
+    pub fn limit_stretch(&mut self, max_ratio: f32) {
+        self.cache.point_grid_stale = true;
+        self.update_stretch_cache();
+        for (origin, neighbor, target_len) in &self.cache.stretch_edges {
+            let origin = self.current_point_positions[*origin];
+            let max_len = target_len * max_ratio;
+            let point = &mut self.current_point_positions[*neighbor];
+            if origin.distance_squared(*point) > max_len * max_len {
+                *point = origin + (*point - origin).normalize_or_zero() * max_len;
+            }
+        }
+    }
+
+    /// Invalidates the cached solver data derived from the sticks, anchors
+    /// and points, rebuilding it when it is next used.
+    ///
+    /// The cloth methods editing the sticks, anchors or points invalidate it,
+    /// and adding or removing sticks is detected automatically. Call this
+    /// after manually editing [`Self::stick_lengths`] without changing the
+    /// stick count, like when replacing a stick or changing its length, or
+    /// after editing [`Self::current_point_positions`] directly.
+    pub const fn invalidate_cache(&mut self) {
+        self.cache.stretch_counts = None;
+        self.cache.sorted_sticks_count = None;
+        self.cache.point_grid_stale = true;
+    }
+
+    /// Rebuilds the cached stick walk of [`Self::limit_stretch`] if the
+    /// sticks or anchors changed
+    fn update_stretch_cache(&mut self) {
+        let counts = [
+            self.stick_lengths.len(),
+            self.anchored_points.len(),
+            self.current_point_positions.len(),
+        ];
+        if self.cache.stretch_counts == Some(counts) {
+            return;
+        }
+        let adjacency = self.stick_adjacency();
+        let mut visited = vec![false; adjacency.len()];
+        let mut seeds: Vec<usize> = self
+            .anchored_points
+            .keys()
+            .copied()
+            .filter(|id| *id < adjacency.len())
+            .collect();
+        seeds.sort_unstable();
+        for id in &seeds {
+            visited[*id] = true;
+        }
+        let mut queue: VecDeque<usize> = seeds.into();
+        let edges = &mut self.cache.stretch_edges;
+        edges.clear();
+        while let Some(id) = queue.pop_front() {
+            for (neighbor, target_len) in &adjacency[id] {
+                if visited.get(*neighbor) == Some(&false) {
+                    visited[*neighbor] = true;
+                    edges.push((id, *neighbor, *target_len));
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        self.cache.stretch_counts = Some(counts);
+    }
+
+    /// Conforms the cloth to a surface, alternating between moving every non
+    /// anchored point to its `project` position and applying the stick
+    /// constraints.
+    ///
+    /// Points which can't reach the surface are left where the last stick
+    /// constraints put them. The previous point positions are then reset,
+    /// making the cloth start at rest.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterations` - Number of projection and stick constraints iterations
+    /// * `depth` - Number of sticks constraint iterations per iteration
+    /// * `project` - function taking a cloth point and returning the closest
+    ///   surface point
+    pub fn shrinkwrap(&mut self, iterations: usize, depth: u8, project: impl Fn(&Vec3) -> Vec3) {
+        for _ in 0..iterations {
+            for (_, point) in self
+                .current_point_positions
+                .iter_mut()
+                .enumerate()
+                .filter(|(i, _)| !self.anchored_mask.is_pinned(*i))
+            {
+                *point = project(point);
             }
+            self.update_sticks(depth);
         }
-        self.previous_point_positions = position_cache;
+        self.previous_point_positions
+            .clone_from(&self.current_point_positions);
     }
 
     /// Applies the cloth sticks constraints
@@ -258,60 +2188,138 @@ impl Cloth {
     ///
     /// * `depth` - Number of sticks constraint iterations
     pub fn update_sticks(&mut self, depth: u8) {
-        for _ in 0..depth {
-            for ([id_a, id_b], target_len) in &self.stick_lengths {
-                let (position_a, fixed_a) =
-                    get_point!(*id_a, self.current_point_positions, self.anchored_points);
-                let (position_b, fixed_b) =
-                    get_point!(*id_b, self.current_point_positions, self.anchored_points);
-                if fixed_a && fixed_b {
-                    continue;
+        self.solve_stick_passes(depth, false, 0.0);
+    }
+
+    /// Applies the stick constraints, alternating the sticks iteration
+    /// direction on every pass.
+    ///
+    /// The sticks are solved one after the other, so the order in which they
+    /// are iterated biases the cloth toward one side under symmetric loads.
+    /// This method sorts the sticks by point ids and iterates them forward,
+    /// then in reverse, cancelling the bias (See
+    /// [`ClothConfig::symmetric_solve`]).
+    ///
+    /// Note: the sorted stick list is cached, and rebuilt when the sticks
+    /// change (See [`Self::invalidate_cache`])
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of sticks constraint iterations
+    pub fn update_sticks_alternating(&mut self, depth: u8) {
+        self.update_sorted_sticks_cache();
+        self.solve_stick_passes(depth, true, 0.0);
+    }
+
+    /// Rebuilds the cached sticks sorted by point ids of
+    /// [`Self::update_sticks_alternating`] if the sticks changed
+    fn update_sorted_sticks_cache(&mut self) {
+        if self.cache.sorted_sticks_count == Some(self.stick_lengths.len()) {
+            return;
+        }
+        let sticks = &mut self.cache.sorted_sticks;
+        sticks.clear();
+        sticks.extend(self.stick_lengths.iter().map(|(id, len)| (*id, *len)));
+        sticks.sort_unstable_by_key(|([a, b], _)| [*a.min(b), *a.max(b)]);
+        self.cache.sorted_sticks_count = Some(self.stick_lengths.len());
+    }
+
+    /// Runs `depth` stick constraint passes, over the cached sorted sticks in
+    /// alternating directions if `alternate` is set
+    fn solve_stick_passes(&mut self, depth: u8, alternate: bool, anchor_softness: f32) {
+        self.cache.point_grid_stale = true;
+        // Corrections of sticks with an anchored end are scaled down
+        let anchored_ratio = 1.0 - anchor_softness.clamp(0.0, 1.0);
+        // The stick lists are moved out of the cloth while its points are solved
+        let lengths = std::mem::take(&mut self.stick_lengths);
+        let sorted = std::mem::take(&mut self.cache.sorted_sticks);
+        for pass in 0..depth {
+            if !alternate {
+                for (id, target_len) in &lengths {
+                    self.solve_stick(*id, *target_len, anchored_ratio);
                 }
-                let target_len = match self.stick_modes[&[*id_a, *id_b]] {
-                    StickMode::Fixed => *target_len,
-                    StickMode::Spring {
-                        min_percent,
-                        max_percent,
-                    } => {
-                        let dist = position_a.distance(position_b) / *target_len;
-                        if dist < min_percent {
-                            *target_len * min_percent
-                        } else if dist > max_percent {
-                            *target_len * max_percent
-                        } else {
-                            continue;
-                        }
-                    }
-                };
-                let center = (position_b + position_a) / 2.0;
-                let direction = match (position_b - position_a).try_normalize() {
-                    None => {
-                        log::warn!(
-                            "Failed handle stick between points {} and {} which are too close to \
-                             each other",
-                            *id_a,
-                            *id_b
-                        );
-                        continue;
-                    }
-                    Some(dir) => dir * target_len / 2.0,
-                };
-                if !fixed_a {
-                    self.current_point_positions[*id_a] = if fixed_b {
-                        position_b - direction * 2.0
-                    } else {
-                        center - direction
-                    };
+            } else if pass % 2 == 0 {
+                for (id, target_len) in &sorted {
+                    self.solve_stick(*id, *target_len, anchored_ratio);
                 }
-                if !fixed_b {
-                    self.current_point_positions[*id_b] = if fixed_a {
-                        position_a + direction * 2.0
-                    } else {
-                        center + direction
-                    };
+            } else {
+                for (id, target_len) in sorted.iter().rev() {
+                    self.solve_stick(*id, *target_len, anchored_ratio);
                 }
             }
         }
+        self.stick_lengths = lengths;
+        self.cache.sorted_sticks = sorted;
+    }
+
+    /// Applies the constraint of the stick `[id_a, id_b]` of length
+    /// `target_len`, scaling the corrections of anchored sticks by
+    /// `anchored_ratio`
+    fn solve_stick(&mut self, [id_a, id_b]: StickId, target_len: f32, anchored_ratio: f32) {
+        let (position_a, fixed_a) =
+            get_point!(id_a, self.current_point_positions, self.anchored_mask);
+        let (position_b, fixed_b) =
+            get_point!(id_b, self.current_point_positions, self.anchored_mask);
+        if fixed_a && fixed_b {
+            return;
+        }
+        let (target_len, correction) = match self.stick_modes[&[id_a, id_b]] {
+            StickMode::Fixed => (target_len, 1.0),
+            StickMode::Spring {
+                min_percent,
+                max_percent,
+                damping,
+            } => {
+                let dist = position_a.distance(position_b) / target_len;
+                let len = if dist < min_percent {
+                    target_len * min_percent
+                } else if dist > max_percent {
+                    target_len * max_percent
+                } else {
+                    return;
+                };
+                (len, 1.0 - damping.clamp(0.0, 1.0))
+            }
+        };
+        // The free points share the correction by inverse mass
+        let shares = self.stick_mass_shares(id_a, id_b);
+        let center = shares.map_or((position_b + position_a) / 2.0, |(share_a, _)| {
+            position_a.lerp(position_b, share_a)
+        });
+        let (share_a, share_b) = shares.unwrap_or((0.5, 0.5));
+        let direction = match (position_b - position_a).try_normalize() {
+            None => {
+                log::warn!(
+                    "Failed handle stick between points {} and {} which are too close to \
+                     each other",
+                    id_a,
+                    id_b
+                );
+                return;
+            }
+            Some(dir) => dir * target_len / 2.0,
+        };
+        let stiffness = self
+            .stick_stiffness
+            .get(&[id_a, id_b])
+            .map_or(1.0, |s| s.clamp(0.0, 1.0))
+            * correction;
+        if !fixed_a {
+            let (target, ratio) = if fixed_b {
+                (position_b - direction * 2.0, stiffness * anchored_ratio)
+            } else {
+                (center - direction * (2.0 * share_a), stiffness)
+            };
+            self.current_point_positions[id_a] = position_a.lerp(target, ratio);
+        }
+        if !fixed_b {
+            let (target, ratio) = if fixed_a {
+                (position_a + direction * 2.0, stiffness * anchored_ratio)
+            } else {
+                (center + direction * (2.0 * share_b), stiffness)
+            };
+            self.current_point_positions[id_b] = position_b.lerp(target, ratio);
+        }
     }
 }
 
@@ -319,7 +2327,1310 @@ impl Cloth {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
-    use crate::mesh::rectangle_mesh;
+    use crate::mesh::{rectangle_mesh, rectangle_quads};
+
+    #[test]
+    fn local_positions() {
+        let cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y],
+            ..Default::default()
+        };
+        let transform = GlobalTransform::from_translation(Vec3::Z);
+        let expected: Vec<_> = cloth.compute_vertex_positions(&transform).collect();
+        assert_eq!(cloth.local_position(1, &transform), Some(expected[1]));
+        assert_eq!(cloth.local_position(3, &transform), None);
+        let positions: Vec<_> = cloth.local_positions([2, 0, 5], &transform).collect();
+        assert_eq!(positions, [Some(expected[2]), Some(expected[0]), None]);
+    }
+
+    #[test]
+    fn from_quads() {
+        let positions: Vec<Vec3> = (0..9_u8)
+            .map(|i| Vec3::new(f32::from(i % 3), f32::from(i / 3), 0.0))
+            .collect();
+        let cloth = Cloth::from_quads(
+            &positions,
+            &rectangle_quads((3, 3)),
+            HashMap::default(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        // 12 shared edges and 2 diagonals per quad
+        assert_eq!(cloth.stick_lengths.len(), 12 + 8);
+        assert_eq!(cloth.triangles.len(), 8);
+        assert!((cloth.stick_lengths[&[1, 3]] - 2.0_f32.sqrt()).abs() < 1e-6);
+        assert!((cloth.stick_lengths[&[0, 4]] - 2.0_f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn from_sticks() {
+        let cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X, Vec3::X * 3.0],
+            &[[0, 1], [1, 2]],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::from_translation(Vec3::Y),
+        );
+        assert_eq!(cloth.current_point_positions[2], Vec3::new(3.0, 1.0, 0.0));
+        assert_eq!(cloth.anchored_points[&0].1, Vec3::ZERO);
+        assert_eq!(cloth.stick_lengths.len(), 2);
+        assert!((cloth.stick_lengths[&[1, 2]] - 2.0).abs() < f32::EPSILON);
+        assert_eq!(cloth.stick_modes.len(), 2);
+    }
+
+    #[test]
+    fn invalid_point_ids_are_skipped() {
+        let positions = [Vec3::ZERO, Vec3::X, Vec3::X * 3.0];
+        let anchors: HashMap<usize, VertexAnchor> =
+            [(0, VertexAnchor::default()), (7, VertexAnchor::default())]
+                .into_iter()
+                .collect();
+        let cloth = Cloth::from_sticks(
+            &positions,
+            &[[0, 1], [1, 5], [9, 2]],
+            anchors.clone(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert_eq!(cloth.anchored_points.len(), 1);
+        assert_eq!(cloth.stick_lengths.len(), 1);
+        assert!(cloth.stick_lengths.contains_key(&[0, 1]));
+        let cloth = Cloth::from_quads(
+            &positions,
+            &[[0, 1, 2, 4]],
+            anchors,
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert!(cloth.stick_lengths.is_empty());
+        assert!(cloth.triangles.is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X],
+            &[[0, 1]],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let mut other = Cloth::from_sticks(
+            &[Vec3::Y, Vec3::new(1.0, 1.0, 0.0), Vec3::new(2.0, 1.0, 0.0)],
+            &[[0, 1], [1, 2]],
+            std::iter::once((2, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        other.triangles.push([0, 1, 2]);
+        other.point_masses = vec![2.0, 2.0, 2.0];
+        // Out of bounds seams on either side are ignored
+        cloth
+            .merge(other, &[(0, 0), (1, 1), (2, 0), (0, 3)])
+            .unwrap();
+        assert_eq!(cloth.current_point_positions.len(), 5);
+        assert_eq!(cloth.previous_point_positions.len(), 5);
+        assert_eq!(cloth.rest_positions[4], Vec3::new(2.0, 1.0, 0.0));
+        assert_eq!(cloth.point_masses, [1.0, 1.0, 2.0, 2.0, 2.0]);
+        let mut anchored: Vec<usize> = cloth.anchored_points.keys().copied().collect();
+        anchored.sort_unstable();
+        assert_eq!(anchored, [0, 4]);
+        assert_eq!(cloth.triangles, [[2, 3, 4]]);
+        // Original, remapped and seam sticks
+        let mut sticks: Vec<StickId> = cloth.stick_lengths.keys().copied().collect();
+        sticks.sort_unstable();
+        assert_eq!(sticks, [[0, 1], [0, 2], [1, 3], [2, 3], [3, 4]]);
+        assert_eq!(cloth.stick_modes.len(), 5);
+        assert!((cloth.stick_lengths[&[3, 4]] - 1.0).abs() < f32::EPSILON);
+        assert!((cloth.stick_lengths[&[1, 3]] - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cloth.components().len(), 1);
+    }
+
+    #[test]
+    fn merge_rest_scale() {
+        let panel = |scale: Vec3| {
+            Cloth::from_sticks(
+                &[Vec3::ZERO, Vec3::X],
+                &[[0, 1]],
+                HashMap::default(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::from_scale(scale),
+            )
+        };
+        let mut cloth = panel(Vec3::ONE);
+        // The incoming sticks are scaled to the cloth rest scale
+        cloth.merge(panel(Vec3::splat(2.0)), &[]).unwrap();
+        assert!((cloth.stick_lengths[&[2, 3]] - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cloth.rest_scale, Vec3::ONE);
+        // Non uniformly different scales are rejected
+        let result = cloth.merge(panel(Vec3::new(1.0, 2.0, 1.0)), &[]);
+        assert!(matches!(
+            result,
+            Err(Error::MismatchedRestScale { other, .. }) if other == Vec3::new(1.0, 2.0, 1.0)
+        ));
+        assert_eq!(cloth.current_point_positions.len(), 4);
+        assert_eq!(cloth.stick_lengths.len(), 2);
+    }
+
+    #[test]
+    fn stick_stiffness() {
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X],
+            &[[0, 1]],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        cloth.current_point_positions[1] = Vec3::X * 3.0;
+        cloth.stick_stiffness.insert([0, 1], 0.5);
+        cloth.update_sticks(1);
+        assert_eq!(cloth.current_point_positions[1], Vec3::X * 2.0);
+        cloth.stick_stiffness.insert([0, 1], 0.0);
+        cloth.update_sticks(1);
+        assert_eq!(cloth.current_point_positions[1], Vec3::X * 2.0);
+    }
+
+    #[test]
+    fn spring_anchors() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::X * 2.0, Vec3::X * 4.0, Vec3::X * 6.0],
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        let anchors = [
+            (0, (VertexAnchor::default(), 0.5)),
+            (1, (VertexAnchor::default(), 0.5)),
+            (5, (VertexAnchor::default(), 0.5)),
+        ];
+        cloth.insert_spring_anchors(&[Vec3::ZERO; 3], anchors.into_iter().collect());
+        assert_eq!(cloth.spring_anchored_points.len(), 1);
+        cloth.update_anchored_points(&GlobalTransform::IDENTITY, |_| None, |_| None);
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::ZERO, Vec3::X * 2.0, Vec3::X * 6.0]
+        );
+    }
+
+    #[test]
+    fn solve_orders_are_stable() {
+        for solve_order in [SolveOrder::PointsThenSticks, SolveOrder::SticksThenPoints] {
+            let config = ClothConfig {
+                solve_order,
+                ..Default::default()
+            };
+            let positions: Vec<Vec3> = (0..10_u8).map(|i| Vec3::X * f32::from(i)).collect();
+            let sticks: Vec<StickId> = (0..9).map(|i| [i, i + 1]).collect();
+            let mut cloth = Cloth::from_sticks(
+                &positions,
+                &sticks,
+                std::iter::once((0, VertexAnchor::default())).collect(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::IDENTITY,
+            );
+            for _ in 0..1000 {
+                cloth.step(
+                    &config,
+                    |_| Vec3::ZERO,
+                    1.0 / 60.0,
+                    &GlobalTransform::IDENTITY,
+                    |_| None,
+                    |_| None,
+                );
+            }
+            assert!(cloth.current_point_positions.iter().all(|p| p.is_finite()));
+            // The rope hangs from its anchor
+            let end = cloth.current_point_positions[9];
+            assert!(end.y < -8.0, "{solve_order:?}: {end}");
+            assert!(end.length() < 9.5, "{solve_order:?}: {end}");
+        }
+    }
+
+    #[test]
+    fn components() {
+        let positions = [
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::Y,
+            Vec3::Z,
+            Vec3::Z + Vec3::X,
+            Vec3::Z + Vec3::Y,
+            Vec3::ONE,
+        ];
+        let mut cloth = Cloth::new(
+            &positions,
+            &[0, 1, 2, 5, 4, 3],
+            HashMap::default(),
+            StickGeneration::Triangles,
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert_eq!(
+            cloth.connected_components,
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+        );
+        cloth.add_point(
+            Vec3::ONE * 2.0,
+            StickMode::Fixed,
+            None,
+            &Mat4::IDENTITY,
+            |i, _| i == 2 || i == 3,
+        );
+        assert_eq!(
+            cloth.connected_components,
+            vec![vec![0, 1, 2, 3, 4, 5, 7], vec![6]]
+        );
+    }
+
+    #[test]
+    fn solve_collisions_detailed() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::NEG_Y, Vec3::Y, Vec3::NEG_Y * 0.5],
+            anchored_points: std::iter::once((0, (VertexAnchor::default(), Vec3::ZERO))).collect(),
+            ..Default::default()
+        };
+        cloth.update_anchored_mask();
+        // Ground plane at y = 0
+        let contacts = cloth.solve_collisions_detailed(|point| {
+            (point.y < 0.0).then(|| Contact {
+                position: Vec3::new(point.x, 0.0, point.z),
+                normal: Vec3::Y,
+                depth: -point.y,
+            })
+        });
+        assert_eq!(
+            contacts,
+            vec![(
+                2,
+                Contact {
+                    position: Vec3::ZERO,
+                    normal: Vec3::Y,
+                    depth: 0.5
+                }
+            )]
+        );
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::NEG_Y, Vec3::Y, Vec3::ZERO]
+        );
+    }
+
+    #[test]
+    fn solve_swept_collisions() {
+        let mut cloth = Cloth {
+            // The second point tunneled through a thin wall at x = 0
+            current_point_positions: vec![Vec3::X, Vec3::X],
+            previous_point_positions: vec![Vec3::X * 0.5, Vec3::NEG_X],
+            ..Default::default()
+        };
+        cloth.solve_swept_collisions_on_free_axes(BVec3::FALSE, |previous, point| {
+            (previous.x < 0.0 && point.x >= 0.0).then_some(Vec3::new(-0.1, point.y, point.z))
+        });
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::X, Vec3::new(-0.1, 0.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn initial_velocity() {
+        let config = ClothConfig {
+            velocity_damping: 0.0,
+            ..ClothConfig::no_gravity()
+        };
+        let positions = [Vec3::ZERO, Vec3::X];
+        let mut cloth = Cloth::from_sticks(
+            &positions,
+            &[],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let delta_time = 1.0 / 60.0;
+        cloth.set_initial_velocity(
+            |i, p| if i == 0 { Vec3::Z } else { Vec3::Y * 3.0 + p },
+            delta_time,
+        );
+        cloth.step(
+            &config,
+            |_| Vec3::ZERO,
+            delta_time,
+            &GlobalTransform::IDENTITY,
+            |_| None,
+            |_| None,
+        );
+        let velocity = |i: usize| {
+            (cloth.current_point_positions[i] - cloth.previous_point_positions[i]) / delta_time
+        };
+        // Anchored points are unaffected
+        assert_eq!(velocity(0), Vec3::ZERO);
+        assert!(velocity(1).abs_diff_eq(Vec3::new(1.0, 3.0, 0.0), 1e-3));
+    }
+
+    #[test]
+    fn step_floor_height() {
+        let config = ClothConfig {
+            floor_height: Some(-1.0),
+            ..Default::default()
+        };
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::NEG_Y * 0.9],
+            &[],
+            HashMap::default(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        for _ in 0..20 {
+            cloth.step(
+                &config,
+                |_| Vec3::ZERO,
+                1.0 / 30.0,
+                &GlobalTransform::IDENTITY,
+                |_| None,
+                |_| None,
+            );
+        }
+        for point in &cloth.current_point_positions {
+            assert!((point.y - -0.99).abs() < 1e-4, "{point}");
+        }
+    }
+
+    #[test]
+    fn mass_collision_response() {
+        let solve = |masses: Vec<f32>| {
+            let mut cloth = Cloth {
+                current_point_positions: vec![Vec3::NEG_Y, Vec3::NEG_Y],
+                previous_point_positions: vec![Vec3::NEG_Y, Vec3::NEG_Y],
+                point_masses: masses,
+                ..Default::default()
+            };
+            // Flat ground at y = 0
+            cloth.solve_collisions(|p| (p.y < 0.0).then_some(Vec3::new(p.x, 0.0, p.z)));
+            // The push velocity matches the push
+            for (current, previous) in cloth
+                .current_point_positions
+                .iter()
+                .zip(&cloth.previous_point_positions)
+            {
+                assert_eq!(*previous, Vec3::NEG_Y);
+                assert!(current.y > previous.y, "{current}");
+            }
+            cloth.current_point_positions
+        };
+        // Uniform masses are fully pushed out of the ground
+        assert_eq!(solve(Vec::new()), [Vec3::ZERO; 2]);
+        assert_eq!(solve(vec![3.0, 3.0]), [Vec3::ZERO; 2]);
+        // The heavy point moves less than the light point
+        let [light, heavy] = solve(vec![1.0, 4.0]).try_into().unwrap();
+        assert_eq!(light, Vec3::ZERO);
+        assert!(heavy.abs_diff_eq(Vec3::NEG_Y * 0.75, 1e-6), "{heavy}");
+    }
+
+    #[test]
+    fn points_within() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::X * 5.0],
+            ..Default::default()
+        };
+        let ids = |cloth: &mut Cloth| {
+            let mut ids: Vec<usize> = cloth
+                .points_within(Vec3::ZERO, 1.5)
+                .map(|(i, _)| i)
+                .collect();
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(ids(&mut cloth), [0, 1]);
+        // Sliding anchors doesn't move any point
+        cloth.slide_anchors(&[0], Vec3::X);
+        assert!(!cloth.cache.point_grid_stale);
+        // The grid is rebuilt after a step
+        cloth.current_point_positions[2] = Vec3::Y;
+        cloth.step(
+            &ClothConfig::no_gravity(),
+            |_| Vec3::ZERO,
+            0.0,
+            &GlobalTransform::IDENTITY,
+            |_| None,
+            |_| None,
+        );
+        assert_eq!(ids(&mut cloth), [0, 1, 2]);
+        assert_eq!(cloth.nearest_points(Vec3::Y, 1), [(2, 0.0)]);
+        // Overwritten positions are queried right away
+        cloth.set_positions(&[Vec3::X * 5.0, Vec3::X, Vec3::ZERO], false);
+        assert_eq!(ids(&mut cloth), [1, 2]);
+        assert_eq!(cloth.nearest_points(Vec3::ZERO, 1), [(2, 0.0)]);
+    }
+
+    #[test]
+    fn mass_stick_correction() {
+        let solve = |masses: Vec<f32>| {
+            let mut cloth = Cloth::from_sticks(
+                &[Vec3::ZERO, Vec3::X],
+                &[[0, 1]],
+                HashMap::default(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::IDENTITY,
+            );
+            cloth.point_masses = masses;
+            cloth.current_point_positions[1] = Vec3::X * 2.0;
+            cloth.update_sticks(1);
+            cloth.current_point_positions
+        };
+        // Uniform masses share the correction evenly
+        assert_eq!(solve(Vec::new()), [Vec3::X * 0.5, Vec3::X * 1.5]);
+        // The light point takes most of the correction
+        let [heavy, light] = solve(vec![3.0]).try_into().unwrap();
+        assert!(heavy.abs_diff_eq(Vec3::X * 0.25, 1e-6), "{heavy}");
+        assert!(light.abs_diff_eq(Vec3::X * 1.25, 1e-6), "{light}");
+    }
+
+    #[test]
+    fn shape_memory() {
+        let run = |shape_memory| {
+            let config = ClothConfig {
+                shape_memory,
+                velocity_damping: 0.1,
+                ..ClothConfig::no_gravity()
+            };
+            let mut cloth = Cloth::from_sticks(
+                &[Vec3::ZERO, Vec3::X],
+                &[[0, 1]],
+                std::iter::once((0, VertexAnchor::default())).collect(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::IDENTITY,
+            );
+            // The free point is pushed out of its rest pose
+            cloth.current_point_positions[1] = Vec3::Y;
+            cloth.previous_point_positions[1] = Vec3::Y;
+            // The rest pose follows the moving transform
+            let transform = GlobalTransform::from_xyz(0.0, 0.0, 2.0);
+            for _ in 0..300 {
+                cloth.step(
+                    &config,
+                    |_| Vec3::ZERO,
+                    1.0 / 60.0,
+                    &transform,
+                    |_| None,
+                    |_| None,
+                );
+            }
+            cloth.current_point_positions[1]
+        };
+        let rest = Vec3::new(1.0, 0.0, 2.0);
+        let point = run(50.0);
+        assert!(point.abs_diff_eq(rest, 1e-2), "{point}");
+        let point = run(0.0);
+        assert!(!point.abs_diff_eq(rest, 0.1), "{point}");
+    }
+
+    #[test]
+    fn external_forces_only() {
+        let config = ClothConfig {
+            external_forces_only: true,
+            ..Default::default()
+        };
+        let positions: Vec<Vec3> = (0..5_u8).map(|i| Vec3::X * f32::from(i)).collect();
+        let sticks: Vec<StickId> = (0..4).map(|i| [i, i + 1]).collect();
+        let mut cloth = Cloth::from_sticks(
+            &positions,
+            &sticks,
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        for _ in 0..100 {
+            cloth.step(
+                &config,
+                |_| Vec3::X * 10.0,
+                1.0 / 60.0,
+                &GlobalTransform::IDENTITY,
+                |_| None,
+                |_| None,
+            );
+        }
+        assert_eq!(cloth.current_point_positions, positions);
+    }
+
+    #[test]
+    fn anchor_substeps() {
+        let total_stretch = |anchor_substeps| {
+            let config = ClothConfig {
+                anchor_substeps,
+                ..ClothConfig::no_gravity()
+            };
+            let positions: Vec<Vec3> = (0..20_u8).map(|i| Vec3::X * f32::from(i) * 0.5).collect();
+            let sticks: Vec<StickId> = (0..19).map(|i| [i, i + 1]).collect();
+            let mut cloth = Cloth::from_sticks(
+                &positions,
+                &sticks,
+                std::iter::once((0, VertexAnchor::default())).collect(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::IDENTITY,
+            );
+            let mut stretch = 0.0;
+            // The anchor is swung up and down
+            for frame in 0..120_u8 {
+                let transform = GlobalTransform::from_translation(
+                    Vec3::Y * (f32::from(frame) * 0.5).sin() * 3.0,
+                );
+                cloth.step(
+                    &config,
+                    |_| Vec3::ZERO,
+                    1.0 / 60.0,
+                    &transform,
+                    |_| None,
+                    |_| None,
+                );
+                stretch += cloth
+                    .stick_lengths
+                    .iter()
+                    .map(|([a, b], len)| {
+                        let [a, b] = [*a, *b].map(|i| cloth.current_point_positions[i]);
+                        (a.distance(b) - len).max(0.0)
+                    })
+                    .sum::<f32>();
+            }
+            stretch
+        };
+        let (single, substeps) = (total_stretch(1), total_stretch(5));
+        assert!(substeps < single, "{substeps} >= {single}");
+    }
+
+    #[test]
+    fn symmetric_solve() {
+        // Taut rope pinned by its two ends, with sticks shorter than the
+        // points spacing
+        let positions: Vec<Vec3> = (0..21_u8).map(|i| Vec3::X * f32::from(i) * 0.5).collect();
+        let sticks: Vec<StickId> = (0..20).map(|i| [i, i + 1]).collect();
+        let cloth = Cloth::from_sticks(
+            &positions,
+            &sticks,
+            [(0, VertexAnchor::default()), (20, VertexAnchor::default())]
+                .into_iter()
+                .collect(),
+            StickLen::Fixed(0.4),
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let lateral_drift = |cloth: &Cloth| {
+            cloth
+                .current_point_positions
+                .iter()
+                .map(|p| p.x - 5.0)
+                .sum::<f32>()
+                .abs()
+        };
+        // Passes in the same direction
+        let mut biased = cloth.clone();
+        for _ in 0..4 {
+            biased.update_sticks_alternating(1);
+        }
+        // Passes in alternating directions
+        let mut symmetric = cloth;
+        symmetric.update_sticks_alternating(4);
+        let (biased, symmetric) = (lateral_drift(&biased), lateral_drift(&symmetric));
+        assert!(symmetric < biased * 0.5, "{symmetric} >= {biased}");
+    }
+
+    #[test]
+    fn anchor_stick_softness() {
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X * 3.0],
+            &[[0, 1]],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Fixed(1.0),
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let config = ClothConfig {
+            sticks_computation_depth: 1,
+            anchor_stick_softness: 0.5,
+            ..Default::default()
+        };
+        cloth.update_sticks_with_config(&config);
+        assert!(cloth.current_point_positions[1].abs_diff_eq(Vec3::X * 2.0, 1e-5));
+    }
+
+    #[test]
+    fn unpin_vertex() {
+        let unpinned_velocity = |inherit_velocity| {
+            let mut cloth = Cloth::from_sticks(
+                &[Vec3::ZERO],
+                &[],
+                std::iter::once((0, VertexAnchor::default())).collect(),
+                StickLen::Auto,
+                StickMode::Fixed,
+                &Mat4::IDENTITY,
+            );
+            // The anchor moves along X
+            for frame in 0..10_u8 {
+                cloth.update_points(1.0, Vec3::ZERO);
+                cloth.update_anchored_points(
+                    &GlobalTransform::from_translation(Vec3::X * f32::from(frame) * 0.1),
+                    |_| None,
+                    |_| None,
+                );
+            }
+            assert!(cloth.unpin_vertex(0, inherit_velocity).is_some());
+            assert!(cloth.unpin_vertex(0, inherit_velocity).is_none());
+            cloth.update_points(1.0, Vec3::ZERO);
+            cloth.current_point_positions[0] - cloth.previous_point_positions[0]
+        };
+        assert!(unpinned_velocity(true).abs_diff_eq(Vec3::X * 0.1, 1e-5));
+        assert_eq!(unpinned_velocity(false), Vec3::ZERO);
+    }
+
+    #[test]
+    fn partial_anchor_slides_on_free_axis() {
+        let anchor = VertexAnchor {
+            constrain_axes: BVec3::new(false, true, true),
+            ..Default::default()
+        };
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X],
+            &[[0, 1]],
+            std::iter::once((0, anchor)).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        // The point is anchored, but dynamic on its free axis
+        assert!(cloth.is_anchored(0));
+        assert!(!cloth.anchored_mask.is_pinned(0));
+        assert!(cloth.spring_anchored_points.is_empty());
+        // The point slides along X
+        cloth.previous_point_positions[0] = -Vec3::X * 0.1;
+        for _ in 0..10 {
+            cloth.step(
+                &ClothConfig::default(),
+                |_| Vec3::ZERO,
+                0.1,
+                &GlobalTransform::IDENTITY,
+                |_| None,
+                |_| None,
+            );
+        }
+        let point = cloth.current_point_positions[0];
+        assert!(point.x > 0.5, "{point}");
+        assert!(point.y.abs() < 1e-5 && point.z.abs() < 1e-5, "{point}");
+        // The free point hangs below the rod
+        assert!(cloth.current_point_positions[1].y < -0.1);
+        // The ring can be released
+        assert!(cloth.unpin_vertex(0, false).is_some());
+        assert!(!cloth.is_anchored(0));
+        assert_eq!(cloth.anchors().count(), 0);
+    }
+
+    #[test]
+    fn is_anchored() {
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X, Vec3::Y],
+            &[[0, 1], [1, 2]],
+            std::iter::once((1, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert_eq!(
+            cloth.anchored_mask.0,
+            [BVec3::FALSE, BVec3::TRUE, BVec3::FALSE]
+        );
+        assert!(cloth.is_anchored(1));
+        assert!(!cloth.is_anchored(10));
+        cloth.unpin_vertex(1, false);
+        assert!(!cloth.is_anchored(1));
+        let (id, _) = cloth.add_point(
+            Vec3::Z,
+            StickMode::Fixed,
+            Some(VertexAnchor::default()),
+            &Mat4::IDENTITY,
+            |_, _| false,
+        );
+        assert!(cloth.is_anchored(id));
+        assert!(cloth.pin_vertex(0, VertexAnchor::default()).is_none());
+        assert!(cloth.pin_vertex(0, VertexAnchor::default()).is_some());
+        assert!(cloth.pin_vertex(10, VertexAnchor::default()).is_none());
+        assert_eq!(
+            cloth.anchored_mask.0,
+            [BVec3::TRUE, BVec3::FALSE, BVec3::FALSE, BVec3::TRUE]
+        );
+    }
+
+    #[test]
+    fn anchor_follow_speed() {
+        let anchor = VertexAnchor {
+            custom_offset: Some(Vec3::X),
+            follow_speed: Some(10.0),
+            ..Default::default()
+        };
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO],
+            &[],
+            std::iter::once((0, anchor)).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        cloth.current_point_positions[0] = Vec3::ZERO;
+        let mut distances = Vec::new();
+        for _ in 0..20 {
+            cloth.follow_anchored_points(0.1, &GlobalTransform::IDENTITY, |_| None, |_| None);
+            distances.push(cloth.current_point_positions[0].distance(Vec3::X));
+        }
+        // The point moves part of the way every frame and converges
+        assert!((distances[0] - (-1.0_f32).exp()).abs() < 1e-5);
+        assert!(distances.windows(2).all(|d| d[1] <= d[0]));
+        assert!(distances[19] < 1e-5);
+    }
+
+    #[test]
+    fn anchor_max_force() {
+        let anchor = VertexAnchor {
+            max_force: Some(0.5),
+            ..Default::default()
+        };
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X, Vec3::NEG_X],
+            &[[0, 1], [0, 2]],
+            [(0, anchor), (2, VertexAnchor::default())]
+                .into_iter()
+                .collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        // The stick is stretched by 0.3: the anchor holds
+        cloth.update_anchored_points(
+            &GlobalTransform::from_translation(Vec3::NEG_X * 0.3),
+            |_| None,
+            |_| None,
+        );
+        assert!(cloth.anchored_points.contains_key(&0));
+        assert!(cloth.released_anchors.is_empty());
+        // The stick is stretched by 0.8: the anchor is released
+        cloth.update_anchored_points(
+            &GlobalTransform::from_translation(Vec3::NEG_X * 0.8),
+            |_| None,
+            |_| None,
+        );
+        assert!(!cloth.anchored_points.contains_key(&0));
+        assert_eq!(cloth.released_anchors, [0]);
+        // Anchors without a maximum force are never released
+        assert!(cloth.anchored_points.contains_key(&2));
+    }
+
+    #[test]
+    fn settle_transitions() {
+        let config = ClothConfig {
+            velocity_damping: 0.1,
+            ..Default::default()
+        };
+        let positions: Vec<Vec3> = (0..5_u8).map(|i| Vec3::NEG_Y * f32::from(i)).collect();
+        let sticks: Vec<StickId> = (0..4).map(|i| [i, i + 1]).collect();
+        let mut cloth = Cloth::from_sticks(
+            &positions,
+            &sticks,
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let mut transitions = Vec::new();
+        // Rest, then a short gust, then rest again
+        for (wind, frames) in [(Vec3::ZERO, 500), (Vec3::X * 20.0, 30), (Vec3::ZERO, 500)] {
+            for _ in 0..frames {
+                cloth.step(
+                    &config,
+                    |_| wind,
+                    1.0 / 60.0,
+                    &GlobalTransform::IDENTITY,
+                    |_| None,
+                    |_| None,
+                );
+                transitions.extend(cloth.update_settled_state(config.settle_threshold));
+            }
+        }
+        assert_eq!(transitions, [true, false, true]);
+    }
+
+    #[test]
+    fn locked_axes() {
+        let config = ClothConfig {
+            gravity: Vec3::new(0.0, -9.81, -5.0),
+            locked_axes: BVec3::new(false, false, true),
+            ..Default::default()
+        };
+        let positions: Vec<Vec3> = (0..5_u8).map(|i| Vec3::X * f32::from(i)).collect();
+        let sticks: Vec<StickId> = (0..4).map(|i| [i, i + 1]).collect();
+        let mut cloth = Cloth::from_sticks(
+            &positions,
+            &sticks,
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let transform = GlobalTransform::from_translation(Vec3::Z);
+        for _ in 0..100 {
+            cloth.step(
+                &config,
+                |_| Vec3::ZERO,
+                1.0 / 60.0,
+                &transform,
+                |_| None,
+                |_| None,
+            );
+            cloth.solve_collisions_on_free_axes(config.locked_axes, |p| Some(*p + Vec3::Z));
+        }
+        // The anchored point follows the transform
+        assert_eq!(cloth.current_point_positions[0], Vec3::Z);
+        for point in &cloth.current_point_positions[1..] {
+            assert!(point.z.abs() < f32::EPSILON, "{point}");
+        }
+        assert!(cloth.current_point_positions[4].y < 0.0);
+    }
+
+    #[test]
+    fn sail_wind() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::X * 5.0],
+            triangles: vec![[0, 1, 2]],
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        let normals = cloth.compute_point_normals();
+        assert_eq!(normals, [Vec3::Z, Vec3::Z, Vec3::Z, Vec3::ZERO]);
+        // Wind hitting the back face pushes toward the front
+        cloth.apply_sail_wind(Vec3::new(1.0, 0.0, 2.0));
+        assert_eq!(
+            cloth.current_point_positions,
+            [
+                Vec3::ZERO,
+                Vec3::new(1.0, 0.0, 2.0),
+                Vec3::new(0.0, 1.0, 2.0),
+                Vec3::X * 5.0
+            ]
+        );
+        // Wind hitting the front face pushes toward the back
+        cloth.current_point_positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::X * 5.0];
+        cloth.apply_sail_wind(-Vec3::Z);
+        assert_eq!(cloth.current_point_positions[1], Vec3::new(1.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn slide_anchors() {
+        let mut cloth = Cloth::default();
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.slide_anchors(&[0, 1], Vec3::X);
+        cloth.slide_anchors(&[0], Vec3::X);
+        assert_eq!(
+            cloth.anchored_points[&0].0.custom_offset,
+            Some(Vec3::X * 2.0)
+        );
+        assert!(!cloth.anchored_points.contains_key(&1));
+    }
+
+    #[test]
+    fn compute_vertex_positions_into() {
+        let cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y],
+            ..Default::default()
+        };
+        let transform = GlobalTransform::from_translation(Vec3::Z);
+        let mut buf = Vec::with_capacity(3);
+        let ptr = buf.as_ptr();
+        for _ in 0..3 {
+            cloth.compute_vertex_positions_into(&transform, &mut buf);
+            assert_eq!(buf.as_ptr(), ptr);
+            assert_eq!(
+                buf,
+                cloth
+                    .compute_vertex_positions(&transform)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn track_inertia() {
+        let mut cloth = Cloth::default();
+        assert_eq!(cloth.track_inertia(Vec3::ZERO), Vec3::ZERO);
+        assert_eq!(cloth.track_inertia(Vec3::X), Vec3::ZERO);
+        assert_eq!(cloth.track_inertia(Vec3::X * 2.0), Vec3::ZERO);
+        assert_eq!(cloth.track_inertia(Vec3::X * 4.0), Vec3::X);
+        assert_eq!(cloth.track_inertia(Vec3::X * 4.0), -Vec3::X * 2.0);
+    }
+
+    #[test]
+    fn update_points() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y * 2.0],
+            previous_point_positions: vec![Vec3::ZERO, Vec3::ZERO],
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        cloth.update_points(0.5, -Vec3::Y);
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::ZERO, Vec3::new(1.5, -1.0, 0.0), Vec3::Y]
+        );
+        assert_eq!(
+            cloth.previous_point_positions,
+            [Vec3::ZERO, Vec3::X, Vec3::Y * 2.0]
+        );
+        cloth.update_points(0.5, -Vec3::Y);
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::ZERO, Vec3::new(1.75, -2.5, 0.0), Vec3::Y * -0.5]
+        );
+    }
+
+    #[test]
+    fn friction_doesnt_affect_acceleration() {
+        let displacements: Vec<Vec3> = [0.0, 0.5, 1.0]
+            .into_iter()
+            .map(|friction| {
+                let mut cloth = Cloth {
+                    current_point_positions: vec![Vec3::ZERO],
+                    ..Default::default()
+                };
+                cloth.update_points(friction, Vec3::Y * -0.1);
+                cloth.current_point_positions[0]
+            })
+            .collect();
+        // Points at rest fall by the same amount whatever the friction
+        assert_eq!(displacements, [Vec3::Y * -0.1; 3]);
+    }
+
+    #[test]
+    fn anchors() {
+        let mut cloth = Cloth::default();
+        let anchor = VertexAnchor {
+            custom_offset: Some(Vec3::Y),
+            ..Default::default()
+        };
+        cloth.anchored_points.insert(3, (anchor, Vec3::X));
+        cloth
+            .anchored_points
+            .insert(1, (VertexAnchor::default(), Vec3::Z));
+        let mut anchors: Vec<_> = cloth
+            .anchors()
+            .map(|(id, anchor, pos)| (id, anchor.custom_offset, pos))
+            .collect();
+        anchors.sort_unstable_by_key(|(id, _, _)| *id);
+        assert_eq!(anchors, [(1, None, Vec3::Z), (3, Some(Vec3::Y), Vec3::X)]);
+    }
+
+    #[test]
+    #[should_panic = "Cloth positions count mismatch"]
+    fn set_positions_count_mismatch() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO; 3],
+            ..Default::default()
+        };
+        cloth.set_positions(&[Vec3::ZERO], false);
+    }
+
+    #[test]
+    fn set_positions() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::X, Vec3::Y],
+            previous_point_positions: vec![Vec3::ZERO, Vec3::Y],
+            ..Default::default()
+        };
+        cloth.set_positions(&[Vec3::Z, Vec3::Y * 2.0], true);
+        assert_eq!(cloth.current_point_positions, [Vec3::Z, Vec3::Y * 2.0]);
+        assert_eq!(
+            cloth.previous_point_positions,
+            [Vec3::new(-1.0, 0.0, 1.0), Vec3::Y * 2.0]
+        );
+        cloth.set_positions(&[Vec3::X, Vec3::Y], false);
+        assert_eq!(cloth.previous_point_positions, [Vec3::X, Vec3::Y]);
+    }
+
+    #[test]
+    fn spring_damping() {
+        let positions = [Vec3::ZERO, Vec3::X * 2.0];
+        let solve = |damping| {
+            let mut cloth = Cloth::from_sticks(
+                &positions,
+                &[[0, 1]],
+                HashMap::from_iter([(0, VertexAnchor::default())]),
+                StickLen::Fixed(1.0),
+                StickMode::Spring {
+                    min_percent: 0.5,
+                    max_percent: 1.5,
+                    damping,
+                },
+                &Mat4::IDENTITY,
+            );
+            cloth.update_sticks(1);
+            cloth.current_point_positions[1]
+        };
+        // Without damping the stick snaps to its max length
+        assert!(solve(0.0).abs_diff_eq(Vec3::X * 1.5, 1e-5));
+        // With damping it eases toward it
+        assert!(solve(0.5).abs_diff_eq(Vec3::X * 1.75, 1e-5));
+        assert!(solve(1.0).abs_diff_eq(Vec3::X * 2.0, 1e-5));
+    }
+
+    #[test]
+    fn surface_area() {
+        let mesh = rectangle_mesh((11, 6), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let rendering =
+            crate::components::cloth_rendering::ClothRendering::init(&mesh, Default::default())
+                .unwrap();
+        let mut cloth = Cloth::new(
+            &rendering.vertex_positions,
+            &rendering.indices,
+            Default::default(),
+            StickGeneration::Quads,
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::from_scale(Vec3::splat(2.0)),
+        );
+        // 10 by 5 units rectangle, scaled by 2
+        assert!((cloth.surface_area() - 200.0).abs() < 1e-3);
+        // Furled along the X axis
+        for p in &mut cloth.current_point_positions {
+            p.x *= 0.25;
+        }
+        assert!((cloth.surface_area() - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn custom_solver() {
+        let positions = [Vec3::ZERO, Vec3::X, Vec3::X * 2.0];
+        let mut cloth = Cloth::from_sticks(
+            &positions,
+            &[[0, 1], [1, 2]],
+            HashMap::from_iter([(0, VertexAnchor::default())]),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        let mut sticks: Vec<_> = cloth.iter_sticks().collect();
+        sticks.sort_unstable_by_key(|(id, _, _)| *id);
+        assert_eq!(sticks.len(), 2);
+        assert_eq!(sticks[0].0, [0, 1]);
+        assert!((sticks[1].1 - 1.0).abs() < f32::EPSILON);
+        assert!(matches!(sticks[1].2, StickMode::Fixed));
+        // The anchored point is left untouched
+        cloth.apply_solved_positions(&[Vec3::Y, Vec3::Y * 2.0, Vec3::Y * 3.0, Vec3::Y * 4.0]);
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::ZERO, Vec3::Y * 2.0, Vec3::Y * 3.0]
+        );
+        assert_eq!(cloth.previous_point_positions, positions);
+    }
+
+    #[test]
+    fn resolve_bone_anchors() {
+        let mut cloth = Cloth::default();
+        let anchor = |name: &str| VertexAnchor {
+            bone_name: Some(name.to_owned()),
+            ..Default::default()
+        };
+        for (i, name) in ["Hand", "Hand", "Foot"].into_iter().enumerate() {
+            cloth.anchored_points.insert(i, (anchor(name), Vec3::ZERO));
+        }
+        cloth
+            .spring_anchored_points
+            .insert(3, (anchor("Hand"), Vec3::ZERO, 0.5));
+        let mut lookups = 0;
+        let missing = cloth.resolve_bone_anchors(|name, root| {
+            lookups += 1;
+            assert_eq!(root, None);
+            (name == "Hand").then_some(Entity::PLACEHOLDER)
+        });
+        // A single lookup per bone name
+        assert_eq!(lookups, 2);
+        assert_eq!(missing, ["Foot"]);
+        assert_eq!(
+            cloth.anchored_points[&1].0.custom_target,
+            Some(Entity::PLACEHOLDER)
+        );
+        assert_eq!(
+            cloth.spring_anchored_points[&3].0.custom_target,
+            Some(Entity::PLACEHOLDER)
+        );
+        assert_eq!(cloth.anchored_points[&2].0.custom_target, None);
+    }
+
+    #[test]
+    fn apply_wind_gust_at() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::X * 3.0, Vec3::NEG_X],
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(3, (VertexAnchor::default(), Vec3::NEG_X));
+        cloth.update_anchored_mask();
+        cloth.apply_wind_gust_at(Vec3::ZERO, Vec3::Y * 5.0, 2.0, 2.0);
+        assert_eq!(
+            cloth.current_point_positions,
+            [
+                Vec3::Y * 2.0,
+                Vec3::new(1.0, 1.0, 0.0),
+                Vec3::X * 3.0,
+                Vec3::NEG_X
+            ]
+        );
+    }
+
+    #[test]
+    fn stick_hops() {
+        let cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO; 5],
+            stick_lengths: [([0, 1], 1.0), ([1, 2], 1.0), ([2, 3], 1.0), ([4, 3], 1.0)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let hops = cloth.stick_hops(&[0, 10], 2);
+        assert_eq!(hops.len(), 3);
+        assert_eq!((hops[&0], hops[&1], hops[&2]), (0, 1, 2));
+        let hops = cloth.stick_hops(&[0, 4], 5);
+        assert_eq!(hops[&2], 2);
+        assert_eq!(hops[&3], 1);
+    }
+
+    #[test]
+    fn limit_stretch() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X * 5.0, Vec3::X * 15.0],
+            stick_lengths: [([0, 1], 1.0), ([1, 2], 1.0)].into_iter().collect(),
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        cloth.limit_stretch(1.5);
+        assert_eq!(
+            cloth.current_point_positions,
+            [Vec3::ZERO, Vec3::X * 1.5, Vec3::X * 3.0]
+        );
+        // Tearing a stick invalidates the cached walk
+        cloth.stick_lengths.remove(&[1, 2]);
+        cloth.current_point_positions[2] = Vec3::X * 15.0;
+        cloth.limit_stretch(1.5);
+        assert_eq!(cloth.current_point_positions[2], Vec3::X * 15.0);
+        // So does a new anchor
+        cloth
+            .anchored_points
+            .insert(2, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.stick_lengths.insert([1, 2], 1.0);
+        cloth.current_point_positions[1] = Vec3::X * 5.0;
+        cloth.limit_stretch(1.5);
+        assert_eq!(cloth.current_point_positions[1], Vec3::X * 1.5);
+        // Editing a stick length requires a manual invalidation
+        cloth.unpin_vertex(2, false);
+        cloth.limit_stretch(1.5);
+        *cloth.stick_lengths.get_mut(&[1, 2]).unwrap() = 3.0;
+        cloth.invalidate_cache();
+        cloth.current_point_positions[2] = Vec3::X * 15.0;
+        cloth.limit_stretch(1.5);
+        assert_eq!(cloth.current_point_positions[2], Vec3::X * 6.0);
+    }
+
+    #[test]
+    fn shrinkwrap() {
+        let sticks = [[0, 1], [1, 2]];
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0],
+            previous_point_positions: vec![Vec3::ZERO; 3],
+            stick_lengths: sticks.iter().map(|id| (*id, 1.0)).collect(),
+            stick_modes: sticks.iter().map(|id| (*id, StickMode::Fixed)).collect(),
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        // The surface is out of reach of the first point
+        cloth.shrinkwrap(20, 5, |p| Vec3::new(p.x, -1.5, p.z));
+        let points = &cloth.current_point_positions;
+        assert_eq!(points[0], Vec3::ZERO);
+        assert!((points[0].distance(points[1]) - 1.0).abs() < 1e-3);
+        assert!((points[1].distance(points[2]) - 1.0).abs() < 1e-3);
+        assert!((points[2].y + 1.5).abs() < 0.1, "{}", points[2]);
+        assert_eq!(
+            cloth.previous_point_positions,
+            cloth.current_point_positions
+        );
+    }
+
+    #[test]
+    fn follow_scale() {
+        let mut cloth = Cloth::from_sticks(
+            &[Vec3::ZERO, Vec3::X],
+            &[[0, 1]],
+            HashMap::default(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::from_scale(Vec3::splat(2.0)),
+        );
+        let stick_len = |cloth: &Cloth| cloth.stick_lengths[&[0, 1]];
+        assert_eq!(cloth.rest_scale, Vec3::splat(2.0));
+        assert!((stick_len(&cloth) - 2.0).abs() < 1e-5);
+        cloth.follow_scale(Vec3::splat(3.0));
+        assert!((stick_len(&cloth) - 3.0).abs() < 1e-5);
+        // Non uniform changes keep the lengths
+        cloth.follow_scale(Vec3::new(1.0, 3.0, 3.0));
+        assert!((stick_len(&cloth) - 3.0).abs() < 1e-5);
+        assert_eq!(cloth.rest_scale, Vec3::new(1.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn scale_rest_lengths() {
+        let mut cloth = Cloth {
+            current_point_positions: vec![Vec3::ZERO, Vec3::X, Vec3::X * 3.0],
+            stick_lengths: [([0, 1], 1.0), ([1, 2], 2.0)].into_iter().collect(),
+            stick_modes: [([0, 1], StickMode::Fixed), ([1, 2], StickMode::Fixed)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        cloth
+            .anchored_points
+            .insert(0, (VertexAnchor::default(), Vec3::ZERO));
+        cloth.update_anchored_mask();
+        cloth.scale_rest_lengths(0.9);
+        assert!((cloth.stick_lengths[&[0, 1]] - 0.9).abs() < 1e-6);
+        assert!((cloth.stick_lengths[&[1, 2]] - 1.8).abs() < 1e-6);
+        cloth.update_sticks(50);
+        let end = cloth.current_point_positions[2];
+        assert!((end.x - 2.7).abs() < 1e-3);
+    }
 
     mod init_from_mesh {
         use super::*;
@@ -341,6 +3652,73 @@ mod tests {
                         (size_x - 1) * size_y + (size_y - 1) * size_x + (size_x - 1) * (size_y - 1)
                     );
                 }
+                StickGeneration::Full { .. } => {
+                    assert_eq!(
+                        len,
+                        (size_x - 1) * size_y
+                            + (size_y - 1) * size_x
+                            + 2 * (size_x - 1) * (size_y - 1)
+                            + (size_x - 2) * size_y
+                            + (size_y - 2) * size_x
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn works_with_full() {
+            let mesh = rectangle_mesh((66, 42), (Vec3::X, -Vec3::Y), Vec3::Z);
+            let matrix = Transform::default().compute_matrix();
+            let cloth_rendering = ClothRendering::init(&mesh, Default::default()).unwrap();
+            let generation = StickGeneration::Full {
+                shear_stiffness: 0.5,
+                bend_stiffness: 0.1,
+            };
+            let cloth = Cloth::new(
+                &cloth_rendering.vertex_positions,
+                &cloth_rendering.indices,
+                Default::default(),
+                generation,
+                StickLen::Auto,
+                StickMode::Fixed,
+                &matrix,
+            );
+            expected_stick_len(cloth.stick_lengths.len(), generation, (66, 42));
+            assert_eq!(cloth.stick_stiffness.len(), 2 * 65 * 41 + 64 * 42 + 40 * 66);
+            // Bend stick spanning two cells
+            let bend = cloth
+                .stick_stiffness
+                .get(&[0, 2])
+                .or_else(|| cloth.stick_stiffness.get(&[2, 0]));
+            assert_eq!(bend.copied(), Some(0.1));
+        }
+
+        #[test]
+        fn skips_zero_length_sticks() {
+            // Vertex 3 duplicates vertex 1
+            let vertex_positions = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::X];
+            let indices = [0, 1, 2, 3, 1, 2];
+            for stick_generation in [
+                StickGeneration::Quads,
+                StickGeneration::Triangles,
+                StickGeneration::Full {
+                    shear_stiffness: 0.5,
+                    bend_stiffness: 0.5,
+                },
+            ] {
+                let cloth = Cloth::new(
+                    &vertex_positions,
+                    &indices,
+                    HashMap::default(),
+                    stick_generation,
+                    StickLen::Auto,
+                    StickMode::Fixed,
+                    &Mat4::IDENTITY,
+                );
+                assert!(!cloth.stick_lengths.is_empty());
+                assert!(cloth.stick_lengths.values().all(|len| *len > f32::EPSILON));
+                assert!(!cloth.stick_lengths.contains_key(&[3, 1]));
+                assert_eq!(cloth.stick_lengths.len(), cloth.stick_modes.len());
             }
         }
 
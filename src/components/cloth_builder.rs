@@ -1,9 +1,17 @@
-use crate::prelude::*;
+use crate::{
+    components::{
+        cloth::{Cloth, StickId},
+        cloth_rendering::ClothRendering,
+    },
+    mesh::{boundary_vertices, corner_vertices},
+    prelude::*,
+};
 use bevy::{
     color::{Color, ColorToComponents, ColorToPacked, Srgba},
-    ecs::prelude::Component,
+    ecs::prelude::{Component, Entity, ReflectComponent},
     log,
-    math::Vec3,
+    math::{Vec2, Vec3},
+    prelude::ReflectDefault,
     reflect::Reflect,
     render::{mesh::VertexAttributeValues, prelude::Mesh},
     utils::HashMap,
@@ -11,21 +19,73 @@ use bevy::{
 use std::sync::Arc;
 
 type PinnedPosCondition = dyn Fn(Vec3) -> bool + Send + Sync;
+type InitialVelocity = dyn Fn(usize, Vec3) -> Vec3 + Send + Sync;
+
+/// Vertex color channel selection
+#[derive(Debug, Copy, Clone, Default, Reflect, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum ColorChannel {
+    /// Red channel
+    #[default]
+    Red,
+    /// Green channel
+    Green,
+    /// Blue channel
+    Blue,
+    /// Alpha channel
+    Alpha,
+}
+
+impl ColorChannel {
+    /// Retrieves the channel value of `color`, between 0.0 and 1.0
+    #[inline]
+    #[must_use]
+    pub fn value(self, color: [u8; 4]) -> f32 {
+        let value = match self {
+            Self::Red => color[0],
+            Self::Green => color[1],
+            Self::Blue => color[2],
+            Self::Alpha => color[3],
+        };
+        f32::from(value) / 255.0
+    }
+}
 
 /// Builder component for cloth behaviour, defines every available option for
 /// cloth generation and rendering.
 ///
 /// Add this component to an entity with at least a `GlobalTransform` and a
-/// `Handle<Mesh>`
+/// `Handle<Mesh>`, or use [`Self::from_points`] to simulate explicit point and
+/// stick data without any mesh.
 ///
 /// ## Note
 ///
 /// The associated `Mesh` must have its [`RenderAssetUsages`] set to
 /// `MAIN_WORLD` for the cloth engine to access it
 ///
+/// ## Scenes
+///
+/// The builder is reflected and can be defined in scene files. Position
+/// conditions closures (See [`Self::with_anchored_vertex_positions`]) can't be
+/// reflected and are code only, use [`Self::anchored_regions`] instead for
+/// data driven anchoring. Both are evaluated and merged on initialization.
+///
+/// ## Live editing
+///
+/// The builder is kept on the entity after initialization, and modifying it
+/// (for example from an inspector) rebuilds the cloth. Rebuilding resets the
+/// simulation: the mesh is restored to its original shape and the points
+/// lose their velocity. Only modifications of reflected fields trigger a
+/// rebuild, changing only the position conditions or the initial velocity
+/// doesn't.
+///
+/// Note: the original mesh is kept in memory to allow rebuilding.
+///
 /// [`RenderAssetUsages`]: bevy::render::render_asset::RenderAssetUsages
 #[derive(Clone, Reflect, Default, Component)]
+#[reflect(Component, Default)]
 #[must_use]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClothBuilder {
     /// cloth vertex ids unaffected by physics and following the attached
     /// `GlobalTransform`.
@@ -38,24 +98,181 @@ pub struct ClothBuilder {
     /// by physics and following the attached `GlobalTransform`
     #[reflect(ignore)]
     pub anchored_position_conditions: Vec<(Arc<PinnedPosCondition>, VertexAnchor)>,
+    /// Declarative anchored regions, every vertex inside a region will be
+    /// anchored. Contrary to [`Self::anchored_position_conditions`], the
+    /// regions can be reflected and defined in scene files.
+    pub anchored_regions: Vec<AnchorRegion>,
+    /// Declarative anchored UV regions, every vertex whose
+    /// [`Self::uv_channel`] coordinates are inside a region will be anchored
+    /// (See [`Self::with_anchored_uv_region`])
+    pub anchored_uv_regions: Vec<AnchorUvRegion>,
+    /// Optional anchor for every mesh boundary vertex (See
+    /// [`Self::with_anchored_boundary`])
+    pub anchored_boundary: Option<VertexAnchor>,
+    /// Optional target entities of the four cloth corner vertices (See
+    /// [`Self::with_corner_anchors`])
+    pub corner_anchors: Option<[Entity; 4]>,
     /// How cloth sticks get generated
     pub stick_generation: StickGeneration,
     /// Define cloth sticks target length
     pub stick_length: StickLen,
     /// Defines the cloth computation mode of vertex normals
     pub normals_computing: NormalComputing,
+    /// Mesh UV channel read by the UV region anchors and the cloth rendering
+    /// (See [`Self::with_uv_channel`])
+    pub uv_channel: UvChannel,
+    /// If set to true, every face contributes equally to the smooth normals,
+    /// instead of proportionally to its area (See
+    /// [`Self::with_equal_weight_normals`])
+    pub equal_weight_normals: bool,
     /// Default behaviour for cloth sticks
     pub default_stick_mode: StickMode,
+    /// If set to true, the mesh triangles winding will be reversed, flipping
+    /// the mesh faces and computed normals. Stick generation is unaffected.
+    pub flip_winding: bool,
+    /// If set to true, a crude fold ambient occlusion is baked into the mesh
+    /// vertex colors (See [`Self::with_fold_ao`])
+    pub fold_ao: bool,
+    /// Optional normal used for degenerate triangles, `Vec3::Y` by default
+    /// (See [`Self::with_fallback_normal`])
+    pub fallback_normal: Option<Vec3>,
+    /// Optional vertex color channel defining the sticks stiffness. Each
+    /// stick stiffness is the average of its two vertices channel value (See
+    /// [`Self::with_stiffness_from_vertex_color`])
+    pub stiffness_color_channel: Option<ColorChannel>,
+    /// If set to true, the vertex colors alpha channel is used as a pin
+    /// weight (See [`Self::with_weighted_pin_from_color_alpha`])
+    pub weighted_pin_from_color_alpha: bool,
+    /// Optional vertex attribute name and weight threshold above which the
+    /// vertices are pinned (See [`Self::with_pins_from_attribute`])
+    pub pin_attribute: Option<(String, f32)>,
+    /// If set to true, the per vertex velocity magnitude is written to the
+    /// mesh (See [`Self::with_velocity_attribute`])
+    pub velocity_attribute: bool,
+    /// Explicit local space cloth point positions. If not empty, the cloth
+    /// is built from these points and [`Self::sticks`] instead of the entity
+    /// mesh (See [`Self::from_points`])
+    pub points: Vec<Vec3>,
+    /// Explicit cloth sticks, as point index pairs. Only used with
+    /// [`Self::points`]
+    pub sticks: Vec<StickId>,
+    /// Optional mesh quads, as vertex ids in winding order. If not empty, the
+    /// sticks are generated from these quads instead of the mesh triangles
+    /// (See [`Self::with_quads`])
+    pub quads: Vec<[usize; 4]>,
+    /// Number of simulation steps run on initialization (See
+    /// [`Self::with_warmup`])
+    pub warmup_steps: usize,
+    /// Optional pin gradient (See [`Self::with_pin_gradient`])
+    pub pin_gradient: Option<PinGradient>,
+    /// Initial wind phase of the cloth, in seconds (See
+    /// [`Self::with_wind_phase`])
+    pub wind_phase: f32,
+    /// Optional cloth point masses, by point index (See
+    /// [`Self::with_point_masses`])
+    pub point_masses: Vec<f32>,
+    /// Optional entity the cloth is conformed to on initialization (See
+    /// [`Self::with_shrinkwrap`])
+    pub shrinkwrap_target: Option<Entity>,
+    /// Optional initial point velocity (See [`Self::with_initial_velocity`])
+    #[reflect(ignore)]
+    pub initial_velocity: Option<Arc<InitialVelocity>>,
 }
 
 #[allow(clippy::missing_const_for_fn)]
 impl ClothBuilder {
+    /// Fixed delta time of the warmup simulation steps, in seconds (See
+    /// [`Self::with_warmup`])
+    pub const WARMUP_DELTA_TIME: f32 = 1.0 / 60.0;
+
+    /// Number of shrink-wrap iterations run on initialization (See
+    /// [`Self::with_shrinkwrap`])
+    pub const SHRINKWRAP_ITERATIONS: usize = 20;
+
     /// Instantiates a new `ClothBuilder`
     #[inline]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Instantiates a `ClothBuilder` simulating explicit point and stick data
+    /// instead of a mesh.
+    ///
+    /// The resulting cloth doesn't require a mesh handle and no mesh is
+    /// updated, the simulated points are available in
+    /// [`Cloth::current_point_positions`] for custom rendering.
+    ///
+    /// Anchoring still works through vertex ids, position conditions and
+    /// regions, evaluated on `positions`. Vertex colors and UV regions anchors
+    /// are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - the local space point positions
+    /// * `sticks` - the point index pairs to connect with a stick
+    ///
+    /// [`Cloth::current_point_positions`]: crate::components::cloth::Cloth::current_point_positions
+    #[inline]
+    pub fn from_points(positions: Vec<Vec3>, sticks: Vec<StickId>) -> Self {
+        Self {
+            points: positions,
+            sticks,
+            ..Self::default()
+        }
+    }
+
+    /// Preset for flags and banners:
+    ///
+    /// * [`StickGeneration::Quads`], the cheapest generation which lets the
+    ///   fabric shear and flutter in the wind
+    /// * [`StickLen::Auto`]
+    /// * [`StickMode::Fixed`]
+    /// * [`NormalComputing::SmoothNormals`]
+    #[inline]
+    pub fn flag() -> Self {
+        Self::new()
+            .with_stick_generation(StickGeneration::Quads)
+            .with_stick_length(StickLen::Auto)
+            .with_stick_mode(StickMode::Fixed)
+            .with_smooth_normals()
+    }
+
+    /// Preset for capes and skirts, attached to moving characters:
+    ///
+    /// * [`StickGeneration::Triangles`], the diagonal sticks prevent the cloth
+    ///   from shearing under the character movement
+    /// * [`StickLen::Auto`]
+    /// * [`StickMode::Fixed`]
+    /// * [`NormalComputing::SmoothNormals`]
+    #[inline]
+    pub fn cape() -> Self {
+        Self::new()
+            .with_stick_generation(StickGeneration::Triangles)
+            .with_stick_length(StickLen::Auto)
+            .with_stick_mode(StickMode::Fixed)
+            .with_smooth_normals()
+    }
+
+    /// Preset for ropes and cords:
+    ///
+    /// * [`StickGeneration::Triangles`]
+    /// * [`StickLen::Auto`]
+    /// * [`StickMode::Spring`] between 0% and 100% of the stick length, the
+    ///   rope can't stretch but bends and compresses freely
+    /// * [`NormalComputing::SmoothNormals`]
+    #[inline]
+    pub fn rope() -> Self {
+        Self::new()
+            .with_stick_generation(StickGeneration::Triangles)
+            .with_stick_length(StickLen::Auto)
+            .with_stick_mode(StickMode::Spring {
+                min_percent: 0.0,
+                max_percent: 1.0,
+                damping: 0.0,
+            })
+            .with_smooth_normals()
+    }
+
     /// Adds pinned points for the cloth
     ///
     /// # Arguments
@@ -99,6 +316,63 @@ impl ClothBuilder {
         self
     }
 
+    /// Pins every mesh boundary vertex to the associated `GlobalTransform`,
+    /// like the frame of a trampoline or a stretched tarp (See
+    /// [`Self::with_anchored_boundary`])
+    #[inline]
+    pub fn with_pinned_boundary(self) -> Self {
+        self.with_anchored_boundary(VertexAnchor::default())
+    }
+
+    /// Anchors every mesh boundary vertex: the vertices of every edge
+    /// belonging to a single triangle (See [`boundary_vertices`]).
+    ///
+    /// Explicit anchors (vertex ids, colors, positions, regions) take
+    /// precedence over the boundary anchor.
+    ///
+    /// Note: ignored for cloths built from explicit points (See
+    /// [`Self::from_points`])
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_anchor` - Vertex anchor definition
+    ///
+    /// [`boundary_vertices`]: crate::mesh::boundary_vertices
+    #[inline]
+    pub fn with_anchored_boundary(mut self, vertex_anchor: VertexAnchor) -> Self {
+        self.anchored_boundary = Some(vertex_anchor);
+        self
+    }
+
+    /// Anchors each of the four corner vertices of the cloth to a different
+    /// entity, like a banner between two poles or a carried stretcher.
+    ///
+    /// The corners are identified on initialization from the bounds of the
+    /// local space vertex positions (See [`corner_vertices`]): `u` and `v`
+    /// are the two axes with the largest extent, in X, Y, Z order, and the
+    /// `targets` are ordered as:
+    ///
+    /// 0. min `u`, min `v`
+    /// 1. max `u`, min `v`
+    /// 2. min `u`, max `v`
+    /// 3. max `u`, max `v`
+    ///
+    /// Each corner vertex is placed at the position of its target entity
+    /// (See [`VertexAnchor::ignore_vertex_position`]). Explicit anchors
+    /// (vertex ids, colors, positions, regions) take precedence over the
+    /// corner anchors.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - the corner target entities
+    ///
+    /// [`corner_vertices`]: crate::mesh::corner_vertices
+    #[inline]
+    pub fn with_corner_anchors(mut self, targets: [Entity; 4]) -> Self {
+        self.corner_anchors = Some(targets);
+        self
+    }
+
     /// Adds custom anchored vertex ids for the cloth
     ///
     /// # Arguments
@@ -112,7 +386,7 @@ impl ClothBuilder {
         vertex_anchor: VertexAnchor,
     ) -> Self {
         self.anchored_vertex_ids
-            .extend(vertex_ids.map(|id| (id, vertex_anchor)));
+            .extend(vertex_ids.zip(std::iter::repeat(vertex_anchor)));
         self
     }
 
@@ -173,8 +447,11 @@ impl ClothBuilder {
         vertex_colors: impl Iterator<Item = Color>,
         vertex_anchor: VertexAnchor,
     ) -> Self {
-        self.anchored_vertex_colors
-            .extend(vertex_colors.map(|c| (c.to_srgba().to_u8_array(), vertex_anchor)));
+        self.anchored_vertex_colors.extend(
+            vertex_colors
+                .map(|c| c.to_srgba().to_u8_array())
+                .zip(std::iter::repeat(vertex_anchor)),
+        );
         self
     }
 
@@ -241,6 +518,83 @@ impl ClothBuilder {
         self
     }
 
+    /// Adds a pinned region for the cloth. Every vertex inside the region will
+    /// be pinned to the associated `GlobalTransform`
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - minimum corner of the region, in local space
+    /// * `max` - maximum corner of the region, in local space
+    #[inline]
+    pub fn with_pinned_region(self, min: Vec3, max: Vec3) -> Self {
+        self.with_anchored_region(min, max, Default::default())
+    }
+
+    /// Adds an anchored region for the cloth. Every vertex inside the region
+    /// will be anchored
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - minimum corner of the region, in local space
+    /// * `max` - maximum corner of the region, in local space
+    /// * `vertex_anchor` - Vertex anchor definition
+    #[inline]
+    pub fn with_anchored_region(
+        mut self,
+        min: Vec3,
+        max: Vec3,
+        vertex_anchor: VertexAnchor,
+    ) -> Self {
+        self.anchored_regions.push(AnchorRegion {
+            min,
+            max,
+            anchor: vertex_anchor,
+        });
+        self
+    }
+
+    /// Adds a pinned UV region for the cloth. Every vertex whose UV
+    /// coordinates are inside the region will be pinned to the associated
+    /// `GlobalTransform` (See [`Self::with_anchored_uv_region`])
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - minimum corner of the region, in UV space
+    /// * `max` - maximum corner of the region, in UV space
+    #[inline]
+    pub fn with_pinned_uv_region(self, min: Vec2, max: Vec2) -> Self {
+        self.with_anchored_uv_region(min, max, Default::default())
+    }
+
+    /// Adds an anchored UV region for the cloth. Every vertex whose UV
+    /// coordinates are inside the region will be anchored.
+    ///
+    /// The UVs are read from the [`Self::uv_channel`], `UV_0` by default.
+    ///
+    /// Note: a warning is logged if the mesh doesn't have the UV channel, and
+    /// the region is ignored for cloths built from explicit points (See
+    /// [`Self::from_points`])
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - minimum corner of the region, in UV space
+    /// * `max` - maximum corner of the region, in UV space
+    /// * `vertex_anchor` - Vertex anchor definition
+    #[inline]
+    pub fn with_anchored_uv_region(
+        mut self,
+        min: Vec2,
+        max: Vec2,
+        vertex_anchor: VertexAnchor,
+    ) -> Self {
+        self.anchored_uv_regions.push(AnchorUvRegion {
+            min,
+            max,
+            anchor: vertex_anchor,
+        });
+        self
+    }
+
     /// Sets the stick generation option for the cloth
     ///
     /// # Arguments
@@ -252,6 +606,21 @@ impl ClothBuilder {
         self
     }
 
+    /// Sets the stick generation to [`StickGeneration::Full`], generating
+    /// structural, shear and bend sticks
+    ///
+    /// # Arguments
+    ///
+    /// * `shear_stiffness` - Stiffness of the shear sticks, between 0.0 and 1.0
+    /// * `bend_stiffness` - Stiffness of the bend sticks, between 0.0 and 1.0
+    #[inline]
+    pub fn with_full_stick_generation(self, shear_stiffness: f32, bend_stiffness: f32) -> Self {
+        self.with_stick_generation(StickGeneration::Full {
+            shear_stiffness,
+            bend_stiffness,
+        })
+    }
+
     /// Sets the default stick mode option for the cloth
     ///
     /// # Arguments
@@ -298,6 +667,59 @@ impl ClothBuilder {
         self
     }
 
+    /// The cloth smooth normals will average the face normals equally,
+    /// instead of weighting them by their triangle area.
+    ///
+    /// Area weighting gives a better shading on meshes mixing large and small
+    /// triangles, the equal weights match the smooth normals of previous
+    /// versions (See [`ClothRendering::area_weighted_normals`])
+    ///
+    /// [`ClothRendering::area_weighted_normals`]: crate::components::cloth_rendering::ClothRendering::area_weighted_normals
+    #[inline]
+    pub fn with_equal_weight_normals(mut self) -> Self {
+        self.equal_weight_normals = true;
+        self
+    }
+
+    /// The cloth will compute smooth vertex normals a single time, when it
+    /// first settles (See [`NormalComputing::Once`])
+    #[inline]
+    pub fn with_normals_once(mut self) -> Self {
+        self.normals_computing = NormalComputing::Once;
+        self
+    }
+
+    /// The cloth will compute smooth vertex normals blended with the previous
+    /// frame ones (See [`NormalComputing::SmoothTemporal`])
+    ///
+    /// # Arguments
+    ///
+    /// * `blend` - weight of the current frame normals for a 60 FPS frame,
+    ///   between 0 and 1
+    #[inline]
+    pub fn with_temporal_smooth_normals(mut self, blend: f32) -> Self {
+        self.normals_computing = NormalComputing::SmoothTemporal { blend };
+        self
+    }
+
+    /// Sets the mesh UV channel read by the cloth, `UV_0` by default. The
+    /// UV region anchors (See [`Self::with_anchored_uv_region`]) are
+    /// evaluated on this channel, and it is stored in
+    /// [`ClothRendering::uv_channel`].
+    ///
+    /// Use it for assets whose simulation or pin UVs differ from the texture
+    /// UVs. If the mesh doesn't have the requested channel, the UV regions
+    /// are ignored with a warning and the cloth is built without UVs. With
+    /// [`NormalComputing::FlatNormals`], both UV channels are duplicated
+    /// along with the vertices.
+    ///
+    /// [`ClothRendering::uv_channel`]: crate::components::cloth_rendering::ClothRendering::uv_channel
+    #[inline]
+    pub fn with_uv_channel(mut self, uv_channel: UvChannel) -> Self {
+        self.uv_channel = uv_channel;
+        self
+    }
+
     /// The cloth will compute flat vertex normals and duplicate shared vertices
     #[deprecated(note = "Use `with_flat_normals` instead")]
     #[doc(hidden)]
@@ -314,69 +736,547 @@ impl ClothBuilder {
         self
     }
 
+    /// Sets the mesh quad topology, generating the sticks from the quad
+    /// corners instead of the mesh triangles: the four edges of every quad
+    /// and its two diagonals (See [`Cloth::from_quads`]).
+    ///
+    /// Bevy meshes only store triangles, so quad authored meshes lose their
+    /// quads on import and generate asymmetric sticks from the triangulation.
+    /// The quads are provided as a side channel instead, as mesh vertex ids
+    /// in winding order, for example exported along with the mesh from the
+    /// authoring tool, or from [`rectangle_quads`] for generated grids. The
+    /// mesh triangles are still used for rendering.
+    ///
+    /// Note: the [`Self::stick_generation`] is ignored when quads are set
+    ///
+    /// # Arguments
+    ///
+    /// * `quads` - the quads vertex ids
+    ///
+    /// [`Cloth::from_quads`]: crate::components::cloth::Cloth::from_quads
+    /// [`rectangle_quads`]: crate::mesh::rectangle_quads
+    #[inline]
+    pub fn with_quads(mut self, quads: impl IntoIterator<Item = [usize; 4]>) -> Self {
+        self.quads = quads.into_iter().collect();
+        self
+    }
+
+    /// The mesh triangles winding will be reversed on initialization, fixing
+    /// inverted lighting on imported meshes with a reversed winding order.
+    #[inline]
+    pub fn with_flipped_winding(mut self) -> Self {
+        self.flip_winding = true;
+        self
+    }
+
+    /// Runs `steps` simulation steps on initialization, so the cloth appears
+    /// already settled on its first rendered frame instead of visibly falling
+    /// into place, like a flag already hanging from its pole.
+    ///
+    /// The steps use a fixed delta time ([`Self::WARMUP_DELTA_TIME`]) and the
+    /// cloth configuration, gravity included, without any wind. The anchors
+    /// are resolved from their current `GlobalTransform`.
+    ///
+    /// Note: the steps all run during the initialization frame, which can
+    /// cause a noticeable hitch for large meshes or step counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - the number of simulation steps to run
+    #[inline]
+    pub fn with_warmup(mut self, steps: usize) -> Self {
+        self.warmup_steps = steps;
+        self
+    }
+
+    /// Sets the initial wind phase of the cloth, in seconds, desynchronizing
+    /// the winds of identical cloths spawned together, like a forest of flags.
+    ///
+    /// The phase is stored in the cloth [`Cloth::phase_offset`] and adds up
+    /// with the [`ClothConfig::phase_offset`] when sampling the winds, the
+    /// cloth simulation time is left untouched. The default 0.0 keeps
+    /// identical cloths in phase.
+    ///
+    /// Give every spawned cloth its own random phase:
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// # fn random() -> f32 { 0.42 }
+    /// for _ in 0..10 {
+    ///     // `random` returns a value between 0 and 1, from a per spawn RNG
+    ///     let builder = ClothBuilder::flag().with_wind_phase(random() * 10.0);
+    ///     # let _ = builder;
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - the initial wind phase, in seconds
+    ///
+    /// [`Cloth::phase_offset`]: crate::components::cloth::Cloth::phase_offset
+    #[inline]
+    pub fn with_wind_phase(mut self, phase: f32) -> Self {
+        self.wind_phase = phase;
+        self
+    }
+
+    /// Sets the cloth point masses, by point index, making heavy areas like a
+    /// weighted hem resist collisions and stick corrections while light
+    /// areas yield (See [`Cloth::point_masses`]).
+    ///
+    /// Points missing from the list have a mass of 1.0. If left empty, the
+    /// masses are uniform, or kept from the previous cloth when it is rebuilt
+    /// after a builder change.
+    ///
+    /// # Arguments
+    ///
+    /// * `masses` - the point masses, by point index
+    ///
+    /// [`Cloth::point_masses`]: crate::components::cloth::Cloth::point_masses
+    #[inline]
+    pub fn with_point_masses(mut self, masses: impl IntoIterator<Item = f32>) -> Self {
+        self.point_masses = masses.into_iter().collect();
+        self
+    }
+
+    /// Conforms the cloth to the `target` entity on initialization, making it
+    /// appear already draped, like a cover over a piece of furniture.
+    ///
+    /// With the `rapier_collisions` or `avian_collisions` feature, the cloth
+    /// is conformed to the target collider shape. Otherwise, or if the target
+    /// has no collider, the target is approximated by its `Aabb` and
+    /// `GlobalTransform`, which bevy computes for every mesh entity. During
+    /// initialization, after the warmup steps (See [`Self::with_warmup`]),
+    /// the cloth runs [`Self::SHRINKWRAP_ITERATIONS`] iterations, each moving
+    /// every non anchored point to the closest point of the target surface,
+    /// then applying the stick constraints. The cloth then starts at rest. No
+    /// gravity is applied, so points beyond the target footprint are pulled
+    /// onto its closest edges and will fall down the sides once simulated.
+    ///
+    /// Points which can't reach the surface, held back by anchors or by the
+    /// stick lengths, are left where the final stick constraints put them,
+    /// hanging as close to the surface as the cloth allows. If the target has
+    /// neither a collider nor an `Aabb`, the cloth is left untouched.
+    ///
+    /// Note: the shrink-wrap only shapes the initial points, add a
+    /// `ClothCollider` to keep the cloth on the target afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - the entity to conform the cloth to
+    #[inline]
+    pub fn with_shrinkwrap(mut self, target: Entity) -> Self {
+        self.shrinkwrap_target = Some(target);
+        self
+    }
+
+    /// Sets the initial velocity of the cloth points, making the cloth move
+    /// from its first frame instead of starting at rest.
+    ///
+    /// On initialization, the previous position of every non anchored point
+    /// is set to `current - velocity * delta_time` (See
+    /// [`Cloth::set_initial_velocity`]). `delta_time` is the fixed timestep
+    /// if [`ClothConfig::fixed_timestep`] is enabled, otherwise the current
+    /// frame delta time, or [`Self::WARMUP_DELTA_TIME`] on the first frame
+    /// where the delta time is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `velocity` - a function returning the world space velocity, in units
+    ///   per second, from a point id and its world space position
+    ///
+    /// [`Cloth::set_initial_velocity`]: crate::components::cloth::Cloth::set_initial_velocity
+    #[inline]
+    pub fn with_initial_velocity(
+        mut self,
+        velocity: impl Fn(usize, Vec3) -> Vec3 + Send + Sync + 'static,
+    ) -> Self {
+        self.initial_velocity = Some(Arc::new(velocity));
+        self
+    }
+
+    /// A crude ambient occlusion will be baked every frame into the mesh
+    /// vertex colors, darkening the cloth folds for a better perceived depth
+    /// without a normal map. The existing vertex colors are multiplied by the
+    /// occlusion factor (See [`ClothRendering::compute_fold_occlusion`]).
+    ///
+    /// Note: This overrides the mesh vertex colors and has an extra cost:
+    /// the occlusion is computed on every frame from the smooth normals,
+    /// which are also computed if the cloth uses flat or no normal computation
+    ///
+    /// [`ClothRendering::compute_fold_occlusion`]: crate::components::cloth_rendering::ClothRendering::compute_fold_occlusion
+    #[inline]
+    pub fn with_fold_ao(mut self) -> Self {
+        self.fold_ao = true;
+        self
+    }
+
+    /// Sets the normal used for degenerate triangles, whose vertices are
+    /// collapsed or aligned during heavy deformation and don't have a defined
+    /// face normal.
+    ///
+    /// Note: `Vec3::Y` is used by default
+    #[inline]
+    pub fn with_fallback_normal(mut self, normal: Vec3) -> Self {
+        self.fallback_normal = Some(normal);
+        self
+    }
+
+    /// The sticks stiffness will be read from the `channel` of the mesh
+    /// vertex colors, allowing to paint stiffness maps: each stick stiffness
+    /// is the average of its two vertices channel value, from 0.0 (no
+    /// correction) to 1.0 (fully stiff).
+    ///
+    /// Note: ignored if the mesh doesn't have vertex colors
+    #[inline]
+    pub fn with_stiffness_from_vertex_color(mut self, channel: ColorChannel) -> Self {
+        self.stiffness_color_channel = Some(channel);
+        self
+    }
+
+    /// The per vertex velocity magnitude will be written every frame to the
+    /// custom [`ClothRendering::ATTRIBUTE_VELOCITY`] mesh attribute, allowing
+    /// custom shaders to react to the cloth movement (e.g flapping in the
+    /// wind)
+    ///
+    /// [`ClothRendering::ATTRIBUTE_VELOCITY`]: crate::components::cloth_rendering::ClothRendering::ATTRIBUTE_VELOCITY
+    #[inline]
+    pub fn with_velocity_attribute(mut self) -> Self {
+        self.velocity_attribute = true;
+        self
+    }
+
+    /// The vertex colors alpha channel will be used as a pin weight, allowing
+    /// to paint smooth attachment masks:
+    ///
+    /// * Vertices with a `1.0` alpha are fully pinned, like any anchored vertex
+    /// * Vertices with a `0.0` alpha are left untouched, free
+    /// * Vertices in between become spring anchors, pulled toward their
+    ///   original position (relative to the cloth entity `GlobalTransform`)
+    ///   with a stiffness equal to the alpha value (See
+    ///   [`Cloth::spring_anchored_points`])
+    ///
+    /// Explicit anchors (vertex ids, colors, positions, regions) take
+    /// precedence over the alpha pin weight.
+    ///
+    /// Note: ignored if the mesh doesn't have vertex colors
+    ///
+    /// [`Cloth::spring_anchored_points`]: crate::components::cloth::Cloth::spring_anchored_points
+    #[inline]
+    pub fn with_weighted_pin_from_color_alpha(mut self) -> Self {
+        self.weighted_pin_from_color_alpha = true;
+        self
+    }
+
+    /// Pins every vertex whose weight in the `name` mesh vertex attribute
+    /// exceeds `threshold`, allowing to author pin groups in a DCC tool
+    /// instead of listing vertex ids in code.
+    ///
+    /// The attribute is fetched on initialization from the Bevy `Mesh`
+    /// attributes by its [`MeshVertexAttribute::name`], and must be a single
+    /// `Float32` per vertex. To import a Blender vertex group exported as a
+    /// custom glTF attribute (e.g. `_PIN`), map the glTF name to a custom
+    /// `MeshVertexAttribute` with `GltfPlugin::add_custom_vertex_attribute`,
+    /// and use that attribute name:
+    ///
+    /// ```rust
+    /// # use bevy::render::{mesh::MeshVertexAttribute, render_resource::VertexFormat};
+    /// # use bevy_silk::prelude::*;
+    /// // Registered with `GltfPlugin::default().add_custom_vertex_attribute("_PIN", ATTRIBUTE_PIN)`
+    /// const ATTRIBUTE_PIN: MeshVertexAttribute =
+    ///     MeshVertexAttribute::new("Pin", 988_540_918, VertexFormat::Float32);
+    ///
+    /// let builder = ClothBuilder::new().with_pins_from_attribute(ATTRIBUTE_PIN.name, 0.5);
+    /// ```
+    ///
+    /// Explicit anchors (vertex ids, colors, positions, regions) take
+    /// precedence over the attribute pins.
+    ///
+    /// Note: a warning is logged if the mesh doesn't have a valid `name`
+    /// attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the vertex attribute name
+    /// * `threshold` - the weight above which a vertex is pinned
+    ///
+    /// [`MeshVertexAttribute::name`]: bevy::render::mesh::MeshVertexAttribute::name
+    #[inline]
+    pub fn with_pins_from_attribute(mut self, name: &str, threshold: f32) -> Self {
+        self.pin_attribute = Some((name.to_string(), threshold));
+        self
+    }
+
+    /// Pins the `seed_ids` vertices and progressively softens the attachment
+    /// over the next `rows` of the cloth, avoiding a hard transition that
+    /// stretches the first free row, like a banner hanging from a rod.
+    ///
+    /// On initialization, the stick network is walked breadth first from the
+    /// seeds: every vertex reached in `n` sticks, with `n` at most `rows`,
+    /// becomes a spring anchor (See [`Cloth::spring_anchored_points`]) with a
+    /// stiffness of `falloff` to the power of `n`. A vertex reachable from
+    /// several seeds uses its shortest path.
+    ///
+    /// Explicit anchors take precedence over the gradient spring anchors.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed_ids` - the vertex ids to pin to the associated `GlobalTransform`
+    /// * `rows` - the number of stick steps from the seeds to soften
+    /// * `falloff` - the stiffness ratio between two consecutive rows, between
+    ///   0.0 and 1.0. For example `0.5` gives stiffnesses of 0.5, 0.25, 0.125..
+    ///
+    /// [`Cloth::spring_anchored_points`]: crate::components::cloth::Cloth::spring_anchored_points
+    #[inline]
+    pub fn with_pin_gradient(mut self, seed_ids: Vec<usize>, rows: usize, falloff: f32) -> Self {
+        self.pin_gradient = Some(PinGradient {
+            seed_ids,
+            rows,
+            falloff,
+        });
+        self
+    }
+
+    /// Retrieves the spring anchors of the [`Self::pin_gradient`] for `cloth`,
+    /// if any
+    #[must_use]
+    pub fn pin_gradient_point_ids(&self, cloth: &Cloth) -> HashMap<usize, (VertexAnchor, f32)> {
+        let Some(gradient) = &self.pin_gradient else {
+            return HashMap::default();
+        };
+        cloth
+            .stick_hops(&gradient.seed_ids, gradient.rows)
+            .into_iter()
+            .filter_map(|(i, hops)| {
+                gradient
+                    .stiffness(hops)
+                    .map(|stiffness| (i, (VertexAnchor::default(), stiffness)))
+            })
+            .collect()
+    }
+
+    /// Retrieves the vertex ids pin weights from the `mesh` vertex colors alpha
+    /// channel, if [`Self::weighted_pin_from_color_alpha`] is set.
+    ///
+    /// Vertices with a `0.0` alpha are ignored and vertices with a `1.0` alpha
+    /// are fully pinned, retrieved by [`Self::anchored_vertex_ids`] instead.
+    #[must_use]
+    pub fn weighted_pin_vertex_ids(&self, mesh: &Mesh) -> HashMap<usize, (VertexAnchor, f32)> {
+        if !self.weighted_pin_from_color_alpha {
+            return HashMap::default();
+        }
+        let Some(colors) = Self::vertex_colors(mesh) else {
+            return HashMap::default();
+        };
+        colors
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, color)| {
+                let weight = ColorChannel::Alpha.value(color);
+                (weight > 0.0 && weight < 1.0).then(|| (i, (VertexAnchor::default(), weight)))
+            })
+            .collect()
+    }
+
+    /// Retrieves the vertex ids of `mesh` pinned by the
+    /// [`Self::pin_attribute`], if any
+    fn attribute_pin_ids(&self, mesh: &Mesh) -> Vec<usize> {
+        let Some((name, threshold)) = &self.pin_attribute else {
+            return Vec::new();
+        };
+        let Some(weights) = Self::pin_weights(mesh, name) else {
+            return Vec::new();
+        };
+        weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| *weight > threshold)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Reads the `name` pin weights attribute of `mesh`
+    fn pin_weights<'a>(mesh: &'a Mesh, name: &str) -> Option<&'a Vec<f32>> {
+        mesh.attributes()
+            .find(|(attribute, _)| attribute.name == name)
+            .and_then(|(_, values)| match values {
+                VertexAttributeValues::Float32(v) => Some(v),
+                _ => None,
+            })
+    }
+
+    /// Retrieves the names of the mesh attributes read to pin the vertices
+    /// (vertex colors, UV regions and pin attribute) which `mesh` lacks. The
+    /// matching pins are ignored, and the cloth initialization reports them.
+    pub(crate) fn missing_pin_attributes(&self, mesh: &Mesh) -> Vec<String> {
+        let mut missing = Vec::new();
+        let color_pins =
+            !self.anchored_vertex_colors.is_empty() || self.weighted_pin_from_color_alpha;
+        if color_pins && Self::vertex_colors(mesh).is_none() {
+            missing.push(Mesh::ATTRIBUTE_COLOR.name.to_string());
+        }
+        if !self.anchored_uv_regions.is_empty()
+            && ClothRendering::read_uvs(mesh, self.uv_channel).is_none()
+        {
+            missing.push(self.uv_channel.attribute().name.to_string());
+        }
+        if let Some((name, _)) = &self.pin_attribute {
+            if Self::pin_weights(mesh, name).is_none() {
+                missing.push(name.clone());
+            }
+        }
+        missing
+    }
+
+    /// Reads the `mesh` vertex positions
+    fn vertex_positions(mesh: &Mesh) -> Option<Vec<Vec3>> {
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|attr| match attr {
+                VertexAttributeValues::Float32x3(v) => {
+                    Some(v.iter().copied().map(Vec3::from).collect())
+                }
+                _ => None,
+            })
+    }
+
+    /// Reads the `mesh` vertex colors as `u8` arrays
+    fn vertex_colors(mesh: &Mesh) -> Option<Vec<[u8; 4]>> {
+        mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+            .and_then(|attr| match attr {
+                VertexAttributeValues::Float32x3(v) => Some(
+                    v.iter()
+                        .copied()
+                        .map(|c| Srgba::from_f32_array_no_alpha(c).to_u8_array())
+                        .collect(),
+                ),
+                VertexAttributeValues::Float32x4(v) => Some(
+                    v.iter()
+                        .copied()
+                        .map(|c| Srgba::from_f32_array(c).to_u8_array())
+                        .collect(),
+                ),
+                VertexAttributeValues::Uint8x4(v) => Some(v.clone()),
+                _ => None,
+            })
+    }
+
+    /// Computes the stiffness of the given `sticks` from the
+    /// [`Self::stiffness_color_channel`] of the `mesh` vertex colors.
+    ///
+    /// Returns an empty map if no channel is set or if the `mesh` doesn't
+    /// have vertex colors.
+    ///
+    /// Note: the painted stiffness overrides the [`StickGeneration::Full`]
+    /// shear and bend stiffness
+    #[must_use]
+    pub fn stick_stiffness<'a>(
+        &self,
+        mesh: &Mesh,
+        sticks: impl Iterator<Item = &'a StickId>,
+    ) -> HashMap<StickId, f32> {
+        let Some(channel) = self.stiffness_color_channel else {
+            return HashMap::default();
+        };
+        let Some(colors) = Self::vertex_colors(mesh) else {
+            log::warn!(
+                "ClothBuilder has a stiffness color channel but the associated mesh doesn't have \
+                 a valid Vertex_Color attribute"
+            );
+            return HashMap::default();
+        };
+        sticks
+            .filter_map(|[a, b]| {
+                let (color_a, color_b) = (colors.get(*a)?, colors.get(*b)?);
+                let stiffness = (channel.value(*color_a) + channel.value(*color_b)) * 0.5;
+                Some(([*a, *b], stiffness))
+            })
+            .collect()
+    }
+
     /// Retrieves all anchored vertex ids using:
     /// - [`Self::anchored_vertex_ids`] explicit ids
     /// - [`Self::anchored_vertex_colors`] to find every vertex id in `mesh`
     ///   matching a pinned color
+    /// - [`Self::anchored_position_conditions`] and [`Self::anchored_regions`]
+    ///   to find every vertex id in `mesh` matching a position condition or
+    ///   region
+    /// - [`Self::anchored_uv_regions`] to find every vertex id in `mesh` with
+    ///   [`Self::uv_channel`] coordinates inside a UV region
+    /// - [`Self::weighted_pin_from_color_alpha`] to find every vertex id in
+    ///   `mesh` with a fully opaque color, unless explicitly anchored
+    /// - [`Self::anchored_boundary`] to find every boundary vertex id in
+    ///   `mesh`, unless explicitly anchored
+    /// - [`Self::corner_anchors`] to find the corner vertex ids in `mesh`,
+    ///   unless explicitly anchored
+    /// - [`Self::pin_attribute`] to find every vertex id in `mesh` with a
+    ///   weight above the threshold, unless explicitly anchored
     ///
-    /// Note: anchored vertex colors are ignored if the given `mesh` doesn't
-    /// have vertex colors
+    /// Note: anchored vertex colors and UV regions are ignored if the given
+    /// `mesh` doesn't have vertex colors or the UV channel
     #[must_use]
     pub fn anchored_vertex_ids(&self, mesh: &Mesh) -> HashMap<usize, VertexAnchor> {
-        let mut res = self.anchored_vertex_ids.clone();
+        let mut res = HashMap::default();
+        if let Some(anchor) = &self.anchored_boundary {
+            if let Some(indices) = mesh.indices() {
+                let indices: Vec<usize> = indices.iter().collect();
+                res.extend(
+                    boundary_vertices(&indices)
+                        .into_iter()
+                        .map(|i| (i, anchor.clone())),
+                );
+            } else {
+                log::warn!(
+                    "ClothBuilder has an anchored boundary but the associated mesh doesn't have \
+                     indices"
+                );
+            }
+        }
+        if self.corner_anchors.is_some() {
+            if let Some(positions) = Self::vertex_positions(mesh) {
+                res.extend(self.corner_anchor_ids(&positions));
+            } else {
+                log::warn!(
+                    "ClothBuilder has corner anchors but the associated mesh doesn't have a valid \
+                     Vertex_Position attribute"
+                );
+            }
+        }
+        res.extend(
+            self.attribute_pin_ids(mesh)
+                .into_iter()
+                .map(|i| (i, VertexAnchor::default())),
+        );
+        if self.weighted_pin_from_color_alpha {
+            if let Some(colors) = Self::vertex_colors(mesh) {
+                res.extend(
+                    colors
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(_, color)| color[3] == u8::MAX)
+                        .map(|(i, _)| (i, VertexAnchor::default())),
+                );
+            }
+        }
+        res.extend(self.pin_gradient_seeds());
+        res.extend(
+            self.anchored_vertex_ids
+                .iter()
+                .map(|(i, a)| (*i, a.clone())),
+        );
         if !self.anchored_vertex_colors.is_empty() {
-            let vertex_colors: Option<Vec<[u8; 4]>> = mesh
-                .attribute(Mesh::ATTRIBUTE_COLOR)
-                .and_then(|attr| match attr {
-                    VertexAttributeValues::Float32x3(v) => Some(
-                        v.iter()
-                            .copied()
-                            .map(|c| Srgba::from_f32_array_no_alpha(c).to_u8_array())
-                            .collect(),
-                    ),
-                    VertexAttributeValues::Float32x4(v) => Some(
-                        v.iter()
-                            .copied()
-                            .map(|c| Srgba::from_f32_array(c).to_u8_array())
-                            .collect(),
-                    ),
-                    VertexAttributeValues::Uint8x4(v) => Some(v.clone()),
-                    _ => None,
-                });
-            #[allow(clippy::option_if_let_else)]
-            match vertex_colors {
-                Some(colors) => {
-                    res.extend(colors.into_iter().enumerate().filter_map(|(i, color)| {
-                        self.anchored_vertex_colors
-                            .get(&color)
-                            .map(|anchor| (i, *anchor))
-                    }));
-                }
-                None => {
-                    log::warn!(
-                        "ClothBuilder has anchored vertex colors but the associated mesh doesn't \
-                         have a valid Vertex_Color attribute"
-                    );
-                }
-            };
+            if let Some(colors) = Self::vertex_colors(mesh) {
+                res.extend(colors.into_iter().enumerate().filter_map(|(i, color)| {
+                    self.anchored_vertex_colors
+                        .get(&color)
+                        .map(|anchor| (i, anchor.clone()))
+                }));
+            }
         }
-        if !self.anchored_position_conditions.is_empty() {
-            let vertex_positions: Option<Vec<Vec3>> = mesh
-                .attribute(Mesh::ATTRIBUTE_POSITION)
-                .and_then(|attr| match attr {
-                    VertexAttributeValues::Float32x3(v) => {
-                        Some(v.iter().copied().map(Vec3::from).collect())
-                    }
-                    _ => None,
-                });
+        if !self.anchored_position_conditions.is_empty() || !self.anchored_regions.is_empty() {
             #[allow(clippy::option_if_let_else)]
-            match vertex_positions {
+            match Self::vertex_positions(mesh) {
                 Some(positions) => {
-                    res.extend(positions.into_iter().enumerate().flat_map(|(i, pos)| {
-                        self.anchored_position_conditions
-                            .iter()
-                            .filter_map(move |(c, anchor)| c(pos).then_some((i, *anchor)))
-                    }));
+                    self.extend_position_anchors(&mut res, &positions);
                 }
                 None => {
                     log::warn!(
@@ -386,6 +1286,139 @@ impl ClothBuilder {
                 }
             };
         }
+        if !self.anchored_uv_regions.is_empty() {
+            if let Some(uvs) = ClothRendering::read_uvs(mesh, self.uv_channel) {
+                res.extend(uvs.into_iter().enumerate().flat_map(|(i, uv)| {
+                    self.anchored_uv_regions
+                        .iter()
+                        .filter(move |region| region.contains(Vec2::from(uv)))
+                        .map(move |region| (i, region.anchor.clone()))
+                }));
+            }
+        }
         res
     }
+
+    /// Retrieves all anchored point ids for [`Self::points`] using:
+    /// - [`Self::corner_anchors`] to find the corner point ids, unless
+    ///   explicitly anchored
+    /// - [`Self::anchored_vertex_ids`] explicit ids
+    /// - [`Self::anchored_position_conditions`] and [`Self::anchored_regions`]
+    ///   to find every point id matching a position condition or region
+    ///
+    /// Note: anchored vertex colors and UV regions are ignored
+    #[must_use]
+    pub fn anchored_point_ids(&self) -> HashMap<usize, VertexAnchor> {
+        let mut res: HashMap<usize, VertexAnchor> = self.corner_anchor_ids(&self.points).collect();
+        res.extend(self.pin_gradient_seeds());
+        res.extend(
+            self.anchored_vertex_ids
+                .iter()
+                .map(|(i, a)| (*i, a.clone())),
+        );
+        self.extend_position_anchors(&mut res, &self.points);
+        res
+    }
+
+    fn corner_anchor_ids(&self, positions: &[Vec3]) -> impl Iterator<Item = (usize, VertexAnchor)> {
+        let corners = self.corner_anchors.and_then(|targets| {
+            corner_vertices(positions).map(|corners| corners.into_iter().zip(targets))
+        });
+        corners.into_iter().flatten().map(|(i, target)| {
+            (
+                i,
+                VertexAnchor {
+                    custom_target: Some(target),
+                    ignore_vertex_position: true,
+                    ..Default::default()
+                },
+            )
+        })
+    }
+
+    fn pin_gradient_seeds(&self) -> impl Iterator<Item = (usize, VertexAnchor)> + '_ {
+        self.pin_gradient
+            .iter()
+            .flat_map(|gradient| &gradient.seed_ids)
+            .map(|id| (*id, VertexAnchor::default()))
+    }
+
+    fn extend_position_anchors(&self, res: &mut HashMap<usize, VertexAnchor>, positions: &[Vec3]) {
+        res.extend(positions.iter().enumerate().flat_map(|(i, pos)| {
+            self.anchored_position_conditions
+                .iter()
+                .filter(move |(c, _)| c(*pos))
+                .map(move |(_, anchor)| (i, anchor.clone()))
+        }));
+        res.extend(positions.iter().enumerate().flat_map(|(i, pos)| {
+            self.anchored_regions
+                .iter()
+                .filter(move |region| region.contains(*pos))
+                .map(move |region| (i, region.anchor.clone()))
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::rectangle_mesh;
+    use bevy::render::{mesh::MeshVertexAttribute, render_resource::VertexFormat};
+
+    #[test]
+    fn pins_from_attribute() {
+        const ATTRIBUTE_PIN: MeshVertexAttribute =
+            MeshVertexAttribute::new("Pin", 42, VertexFormat::Float32);
+        let mut mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let builder = ClothBuilder::new().with_pins_from_attribute(ATTRIBUTE_PIN.name, 0.5);
+        // Missing attribute
+        assert!(builder.anchored_vertex_ids(&mesh).is_empty());
+        mesh.insert_attribute(
+            ATTRIBUTE_PIN,
+            vec![1.0, 0.8, 0.5, 0.0, 0.2, 0.6, 0.0, 0.0, 0.0],
+        );
+        let mut anchored: Vec<usize> = builder.anchored_vertex_ids(&mesh).into_keys().collect();
+        anchored.sort_unstable();
+        assert_eq!(anchored, [0, 1, 5]);
+    }
+
+    #[test]
+    fn uv_region_anchors() {
+        let mut mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let builder = ClothBuilder::new().with_pinned_uv_region(Vec2::ZERO, Vec2::new(0.1, 1.0));
+        let anchored = |builder: &ClothBuilder, mesh: &Mesh| {
+            let mut ids: Vec<usize> = builder.anchored_vertex_ids(mesh).into_keys().collect();
+            ids.sort_unstable();
+            ids
+        };
+        // The `UV_0` U axis follows the columns
+        assert_eq!(anchored(&builder, &mesh), [0, 3, 6]);
+        // Missing `UV_1` channel
+        let builder = builder.with_uv_channel(UvChannel::Uv1);
+        assert!(anchored(&builder, &mesh).is_empty());
+        // The `UV_1` U axis follows the rows
+        let uvs_1: Vec<[f32; 2]> = (0_u8..9)
+            .map(|i| [f32::from(i / 3) / 2.0, f32::from(i % 3) / 2.0])
+            .collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs_1);
+        assert_eq!(anchored(&builder, &mesh), [0, 1, 2]);
+    }
+
+    #[test]
+    fn corner_anchors() {
+        let (size_x, size_y) = (6, 4);
+        let mesh = rectangle_mesh((size_x, size_y), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let entities = [0, 1, 2, 3].map(Entity::from_raw);
+        let anchored = ClothBuilder::new()
+            .with_corner_anchors(entities)
+            .anchored_vertex_ids(&mesh);
+        assert_eq!(anchored.len(), 4);
+        // The mesh goes down the Y axis
+        let corners = [size_x * (size_y - 1), size_x * size_y - 1, 0, size_x - 1];
+        for (corner, entity) in corners.into_iter().zip(entities) {
+            let anchor = &anchored[&corner];
+            assert_eq!(anchor.custom_target, Some(entity));
+            assert!(anchor.ignore_vertex_position);
+        }
+    }
 }
@@ -0,0 +1,113 @@
+use bevy::{
+    ecs::prelude::{Component, ReflectComponent},
+    reflect::Reflect,
+};
+
+/// Cloth cull distance component, fully disabling the simulation of a cloth
+/// far away from the cameras.
+///
+/// Add this component to a cloth entity to freeze it in its last pose when
+/// its AABB center is farther than [`Self::distance`] from the nearest active
+/// camera: its simulation, events, mesh update and collisions are skipped.
+/// The cloth wakes up and resumes from its frozen pose when a camera gets
+/// closer than `distance - hysteresis`, the [`Self::hysteresis`] margin
+/// preventing the cloth from toggling every frame at the boundary.
+///
+/// With multiple active cameras, the distance to the nearest one is used.
+/// Cloths are never culled without any active camera.
+///
+/// This is coarser than [`ClothConfig::pause_when_invisible`]: culled cloths
+/// are frozen even when in view.
+///
+/// [`ClothConfig::pause_when_invisible`]: crate::config::ClothConfig::pause_when_invisible
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct ClothCullDistance {
+    /// Camera distance beyond which the cloth is culled
+    pub distance: f32,
+    /// Distance margin the camera needs to come back within to wake up a
+    /// culled cloth
+    pub hysteresis: f32,
+    /// Whether the cloth is currently culled
+    pub culled: bool,
+}
+
+impl ClothCullDistance {
+    /// Instantiates a new cull distance, with a hysteresis margin of 10% of
+    /// the `distance`
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// let cull = ClothCullDistance::new(50.0);
+    /// assert_eq!(cull.hysteresis, 5.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(distance: f32) -> Self {
+        Self {
+            distance,
+            hysteresis: distance * 0.1,
+            culled: false,
+        }
+    }
+
+    /// Sets the wake up distance margin (See [`Self::hysteresis`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// let cull = ClothCullDistance::new(50.0).with_hysteresis(2.0);
+    /// assert_eq!(cull.hysteresis, 2.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Updates the [`Self::culled`] state from the distance to the nearest
+    /// active camera.
+    ///
+    /// # Arguments
+    ///
+    /// * `camera_distance` - distance between the cloth and the nearest camera,
+    ///   `None` if there are no active cameras
+    ///
+    /// # Returns
+    ///
+    /// `true` if the culled state changed
+    pub fn update_culled(&mut self, camera_distance: Option<f32>) -> bool {
+        let culled = camera_distance.is_some_and(|d| {
+            if self.culled {
+                d > self.distance - self.hysteresis
+            } else {
+                d > self.distance
+            }
+        });
+        let changed = culled != self.culled;
+        self.culled = culled;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hysteresis() {
+        let mut cull = ClothCullDistance::new(10.0).with_hysteresis(2.0);
+        assert!(!cull.update_culled(Some(9.0)));
+        assert!(cull.update_culled(Some(10.5)));
+        assert!(cull.culled);
+        // Within the margin, the cloth stays culled
+        assert!(!cull.update_culled(Some(9.0)));
+        assert!(cull.culled);
+        assert!(cull.update_culled(Some(7.5)));
+        assert!(!cull.culled);
+        // Without cameras, the cloth wakes up
+        cull.update_culled(Some(20.0));
+        assert!(cull.update_culled(None));
+        assert!(!cull.culled);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::point_grid::PointGrid;
+use bevy::{
+    ecs::prelude::{Component, Entity, ReflectComponent},
+    math::Vec3,
+    reflect::Reflect,
+};
+
+/// Cloth follower component, deforming the mesh of its entity from the
+/// simulated points of a `primary` cloth entity.
+///
+/// Allows to build a single cloth from several mesh entities, like the layers
+/// of a skirt: only the primary entity holds a [`Cloth`] and is simulated,
+/// the follower meshes reuse its points instead of being simulated
+/// independently, which would make the overlapping layers interpenetrate.
+///
+/// Every frame, after the primary cloth update, each mapped follower vertex is
+/// moved to its primary cloth point world space position, expressed in the
+/// follower entity local space. If the follower mesh is a `TriangleList` with
+/// a normal attribute, its normals are then recomputed.
+///
+/// [`Cloth`]: crate::components::cloth::Cloth
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct ClothFollower {
+    /// Entity holding the simulated [`Cloth`]
+    ///
+    /// [`Cloth`]: crate::components::cloth::Cloth
+    pub primary: Entity,
+    /// Primary cloth point id for every follower mesh vertex, by vertex index
+    pub vertex_map: Vec<usize>,
+}
+
+impl ClothFollower {
+    /// Generates a follower mapping every vertex to its nearest primary cloth
+    /// point.
+    ///
+    /// The cloth points are binned in a spatial grid to find the nearest
+    /// point of every vertex, the mapping should still be built once when
+    /// spawning the follower.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary` - the entity holding the simulated cloth
+    /// * `vertex_positions` - the follower mesh vertex positions
+    /// * `cloth_positions` - the primary cloth point positions, in the same
+    ///   space as `vertex_positions`
+    #[must_use]
+    pub fn map_nearest(
+        primary: Entity,
+        vertex_positions: &[Vec3],
+        cloth_positions: &[Vec3],
+    ) -> Self {
+        let grid = PointGrid::from_surface(cloth_positions);
+        let vertex_map = vertex_positions
+            .iter()
+            .map(|pos| grid.nearest_weights(*pos, 1).first().map_or(0, |(i, _)| *i))
+            .collect();
+        Self {
+            primary,
+            vertex_map,
+        }
+    }
+
+    /// Retrieves the mapped follower vertex world space positions from the
+    /// primary cloth `point_positions`.
+    ///
+    /// Vertices mapped to out of bounds cloth points are skipped.
+    pub fn compute_positions<'a>(
+        &'a self,
+        point_positions: &'a [Vec3],
+    ) -> impl Iterator<Item = (usize, Vec3)> + 'a {
+        self.vertex_map
+            .iter()
+            .enumerate()
+            .filter_map(|(vertex, point)| point_positions.get(*point).map(|p| (vertex, *p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_nearest() {
+        let cloth_positions = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        let vertex_positions = [Vec3::X * 0.9, Vec3::Y * 1.1, Vec3::splat(0.1)];
+        let follower =
+            ClothFollower::map_nearest(Entity::PLACEHOLDER, &vertex_positions, &cloth_positions);
+        assert_eq!(follower.vertex_map, [1, 2, 0]);
+        let positions: Vec<_> = follower.compute_positions(&[Vec3::ZERO, Vec3::Z]).collect();
+        assert_eq!(positions, [(0, Vec3::Z), (2, Vec3::ZERO)]);
+    }
+}
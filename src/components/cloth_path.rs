@@ -0,0 +1,72 @@
+use bevy::{
+    ecs::prelude::{Component, ReflectComponent},
+    math::Vec3,
+    reflect::Reflect,
+};
+
+/// Polyline path which cloth vertices can be anchored to (See
+/// [`VertexAnchor::path`]).
+///
+/// The path points are expressed in the local space of the entity holding the
+/// component, so animating either the points or the entity's `Transform` will
+/// move every vertex anchored to it.
+///
+/// [`VertexAnchor::path`]: crate::vertex_anchor::VertexAnchor::path
+#[derive(Debug, Clone, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct ClothPath(pub Vec<Vec3>);
+
+impl ClothPath {
+    /// Retrieves the local space position at `t` along the path.
+    ///
+    /// The path is parameterized by arc length: `0.0` is the first point,
+    /// `1.0` is the last point and `0.5` is halfway along the total length of
+    /// the path, regardless of how the points are distributed. Positions
+    /// between two points are linearly interpolated.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - normalized position along the path, clamped between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// `None` if the path has no points
+    #[must_use]
+    pub fn sample(&self, t: f32) -> Option<Vec3> {
+        let first = *self.0.first()?;
+        let total_len: f32 = self.0.windows(2).map(|w| w[0].distance(w[1])).sum();
+        if total_len <= f32::EPSILON {
+            return Some(first);
+        }
+        let mut remaining = t.clamp(0.0, 1.0) * total_len;
+        for segment in self.0.windows(2) {
+            let len = segment[0].distance(segment[1]);
+            if remaining <= len {
+                return Some(segment[0].lerp(segment[1], remaining / len));
+            }
+            remaining -= len;
+        }
+        self.0.last().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_by_arc_length() {
+        let path = ClothPath(vec![Vec3::ZERO, Vec3::X, Vec3::X * 3.0]);
+        assert_eq!(path.sample(0.0), Some(Vec3::ZERO));
+        assert_eq!(path.sample(0.5), Some(Vec3::X * 1.5));
+        assert_eq!(path.sample(1.0), Some(Vec3::X * 3.0));
+    }
+
+    #[test]
+    fn clamps_parameter() {
+        let path = ClothPath(vec![Vec3::ZERO, Vec3::Y]);
+        assert_eq!(path.sample(-1.0), Some(Vec3::ZERO));
+        assert_eq!(path.sample(2.0), Some(Vec3::Y));
+        assert_eq!(ClothPath::default().sample(0.5), None);
+    }
+}
@@ -1,15 +1,17 @@
-use crate::Error;
+use crate::{components::cloth::Cloth, Error};
 use bevy::{
     color::{ColorToComponents, Srgba},
     ecs::prelude::Component,
     math::Vec3,
     reflect::Reflect,
     render::{
-        mesh::{Indices, Mesh, VertexAttributeValues},
+        mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues},
         primitives::Aabb,
+        render_resource::VertexFormat,
     },
-    utils::HashMap,
+    transform::components::GlobalTransform,
 };
+use std::borrow::Cow;
 
 /// Defines the cloth computation mode of vertex normals
 #[derive(Debug, Copy, Clone, Default, Reflect)]
@@ -22,31 +24,166 @@ pub enum NormalComputing {
     /// The cloth will duplicate the vertex positions, avoiding shared vertices,
     /// and compute flat vertex normals
     FlatNormals,
+    /// The cloth will compute smooth vertex normals a single time, once the
+    /// cloth first settles, and then keep them. Suited for cloths ending up
+    /// mostly static, like a draped tablecloth.
+    ///
+    /// Note: the normals won't be updated if the cloth moves significantly
+    /// afterwards, or computed at all if it never settles. Use
+    /// [`Self::SmoothNormals`] for such cloths.
+    Once,
+    /// The cloth will compute smooth vertex normals, blended with the
+    /// previous frame normals to reduce the lighting shimmer of rapidly
+    /// vibrating cloths.
+    ///
+    /// The lower the `blend`, the smoother the lighting over time but the more
+    /// the normals lag behind the actual cloth shape. A `blend` of `1.0`
+    /// matches [`Self::SmoothNormals`].
+    ///
+    /// The blend is scaled by the frame duration, so the normals converge at
+    /// the same speed regardless of the frame rate (See
+    /// [`ClothRendering::update_normals`]).
+    SmoothTemporal {
+        /// Weight of the current frame normals for a 60 FPS frame, between 0
+        /// and 1
+        blend: f32,
+    },
+}
+
+/// Mesh UV channel selection, driving the UV region anchors (See
+/// [`ClothBuilder::with_uv_channel`])
+///
+/// [`ClothBuilder::with_uv_channel`]: crate::components::cloth_builder::ClothBuilder::with_uv_channel
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+pub enum UvChannel {
+    /// The `Mesh::ATTRIBUTE_UV_0` channel
+    #[default]
+    Uv0,
+    /// The `Mesh::ATTRIBUTE_UV_1` channel
+    Uv1,
+}
+
+impl UvChannel {
+    /// Retrieves the associated mesh attribute
+    #[inline]
+    #[must_use]
+    pub const fn attribute(self) -> MeshVertexAttribute {
+        match self {
+            Self::Uv0 => Mesh::ATTRIBUTE_UV_0,
+            Self::Uv1 => Mesh::ATTRIBUTE_UV_1,
+        }
+    }
+
+    /// Retrieves the other UV channel
+    #[inline]
+    #[must_use]
+    const fn other(self) -> Self {
+        match self {
+            Self::Uv0 => Self::Uv1,
+            Self::Uv1 => Self::Uv0,
+        }
+    }
 }
 
 /// Cloth rendering component. It allows mesh data extraction, vertex
 /// duplication and normal computation
-#[derive(Debug, Clone, Component, Default)]
+#[derive(Debug, Clone, Component)]
 pub struct ClothRendering {
     /// Mesh vertex positions
     pub vertex_positions: Vec<Vec3>,
-    /// Mesh vertex UV positions
+    /// Mesh vertex UV positions, read from the [`Self::uv_channel`]
     pub vertex_uvs: Option<Vec<[f32; 2]>>,
+    /// Mesh UV channel read into [`Self::vertex_uvs`], `UV_0` by default
+    /// (See [`ClothBuilder::uv_channel`]).
+    ///
+    /// If the mesh doesn't have the channel, the cloth has no UVs, as for a
+    /// mesh without any UVs.
+    ///
+    /// [`ClothBuilder::uv_channel`]: crate::components::cloth_builder::ClothBuilder::uv_channel
+    pub uv_channel: UvChannel,
     /// Mesh vertex colors
     pub vertex_colors: Option<Vec<[f32; 4]>>,
+    /// Optional vertex velocity magnitudes, written to the
+    /// [`Self::ATTRIBUTE_VELOCITY`] mesh attribute if set
+    pub vertex_velocities: Option<Vec<f32>>,
     /// Mesh vertex indices
     pub indices: Vec<u32>,
     /// If set to true, the vertices will be duplicated and normals computed
     /// before updating the mesh
     pub normal_computing: NormalComputing,
+    /// Normal used for degenerate triangles, with collapsed or aligned
+    /// vertices, which don't have a defined face normal. Defaults to
+    /// `Vec3::Y`
+    pub fallback_normal: Vec3,
+    /// If set to true, a crude fold ambient occlusion is baked into the mesh
+    /// vertex colors every frame (See [`Self::compute_fold_occlusion`])
+    pub fold_ao: bool,
+    /// If set to true, the smooth normals weight the face normals by their
+    /// triangle area, otherwise every face contributes equally. Defaults to
+    /// `true` (See [`Self::update_normals`])
+    pub area_weighted_normals: bool,
+    /// Whether the normals were computed, with [`NormalComputing::Once`]
+    /// (See [`Self::apply_once_normals`])
+    pub once_normals_computed: bool,
+    /// Temporally blended vertex normals, with
+    /// [`NormalComputing::SmoothTemporal`] (See
+    /// [`Self::update_normals`]). Reset if it doesn't match the vertex count.
+    pub previous_normals: Vec<Vec3>,
+    /// Smooth vertex normals computed by [`Self::update_normals`], reusing
+    /// their allocation every frame. Cleared when the vertex positions are
+    /// updated.
+    smooth_normals: Vec<Vec3>,
+    /// Per vertex accumulated face weights of the smooth normals computation,
+    /// kept to reuse their allocation
+    normal_weights: Vec<f32>,
+}
+
+impl Default for ClothRendering {
+    fn default() -> Self {
+        Self {
+            vertex_positions: Vec::new(),
+            vertex_uvs: None,
+            uv_channel: UvChannel::Uv0,
+            vertex_colors: None,
+            vertex_velocities: None,
+            indices: Vec::new(),
+            normal_computing: NormalComputing::default(),
+            fallback_normal: Vec3::Y,
+            fold_ao: false,
+            area_weighted_normals: true,
+            once_normals_computed: false,
+            previous_normals: Vec::new(),
+            smooth_normals: Vec::new(),
+            normal_weights: Vec::new(),
+        }
+    }
 }
 
 impl ClothRendering {
-    fn face_normal(a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
-        (b - a).cross(c - a).normalize() // TODO: enable default value
+    /// Custom mesh vertex attribute containing the per vertex velocity
+    /// magnitude of the cloth, in world space units per frame. Enabled by
+    /// [`ClothBuilder::with_velocity_attribute`].
+    ///
+    /// The attribute is a single `f32` (`Float32` format) named
+    /// `Vertex_ClothVelocity`. It isn't bound to any shader location by
+    /// default: a custom material must declare it in its vertex buffer layout
+    /// in `Material::specialize`, for example with
+    /// `ClothRendering::ATTRIBUTE_VELOCITY.at_shader_location(8)`, and read it
+    /// in its vertex shader input as `@location(8) velocity: f32`.
+    ///
+    /// [`ClothBuilder::with_velocity_attribute`]: crate::prelude::ClothBuilder::with_velocity_attribute
+    pub const ATTRIBUTE_VELOCITY: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_ClothVelocity", 988_540_917, VertexFormat::Float32);
+
+    fn face_normal(&self, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+        (b - a)
+            .cross(c - a)
+            .try_normalize()
+            .unwrap_or(self.fallback_normal)
     }
 
-    /// Initializes from mesh data.
+    /// Initializes from mesh data, reading the UVs from the
+    /// [`UvChannel::Uv0`] channel.
     ///
     /// # Arguments
     ///
@@ -58,6 +195,26 @@ impl ClothRendering {
     /// attribute is missing or invalid. It may also fail if the mesh
     /// doesn't have indices.
     pub fn init(mesh: &Mesh, normal_computing: NormalComputing) -> Result<Self, Error> {
+        Self::init_with_uv_channel(mesh, normal_computing, UvChannel::default())
+    }
+
+    /// Initializes from mesh data, reading the UVs from `uv_channel` (See
+    /// [`Self::uv_channel`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the mesh containing the desired data
+    ///
+    /// # Errors
+    ///
+    /// The function fails in the event of the mesh `ATTRIBUTE_POSITION`
+    /// attribute is missing or invalid. It may also fail if the mesh
+    /// doesn't have indices.
+    pub fn init_with_uv_channel(
+        mesh: &Mesh,
+        normal_computing: NormalComputing,
+        uv_channel: UvChannel,
+    ) -> Result<Self, Error> {
         let vertex_positions = mesh
             .attribute(Mesh::ATTRIBUTE_POSITION)
             .ok_or_else(|| Error::MissingMeshAttribute("Vertex_Position".to_string()))?;
@@ -68,17 +225,12 @@ impl ClothRendering {
         };
         let vertex_count = vertex_positions.len();
         // UVs
-        let vertex_uvs = mesh
-            .attribute(Mesh::ATTRIBUTE_UV_0)
-            .and_then(|attr| match attr {
-                VertexAttributeValues::Float32x2(v) => Some(v.clone()),
-                _ => None,
-            });
+        let vertex_uvs = Self::read_uvs(mesh, uv_channel);
         // Assertion
         let attr_count = vertex_uvs.as_ref().map_or(vertex_count, Vec::len);
         if attr_count != vertex_count {
             return Err(Error::InvalidMeshAttribute {
-                attribute: "Vertex_Uv".to_string(),
+                attribute: uv_channel.attribute().name.to_string(),
                 message: format!("Expected {vertex_count} values, got {attr_count}"),
             });
         }
@@ -117,12 +269,24 @@ impl ClothRendering {
         Ok(Self {
             vertex_positions,
             vertex_uvs,
+            uv_channel,
             vertex_colors,
+            vertex_velocities: None,
             indices,
             normal_computing,
+            ..Self::default()
         })
     }
 
+    /// Reverses the winding order of every triangle, flipping the mesh faces.
+    ///
+    /// The triangle edges are preserved, only their orientation changes.
+    pub fn flip_winding(&mut self) {
+        for triangle in self.indices.chunks_exact_mut(3) {
+            triangle.swap(0, 2);
+        }
+    }
+
     ///  Compute the Axis-Aligned Bounding Box of the mesh vertices in model
     /// space
     ///
@@ -147,13 +311,49 @@ impl ClothRendering {
 
     /// Updates the vertex positions from the cloth point values
     ///
+    /// Extra values are ignored.
+    ///
     /// # Panics
     ///
-    /// Panics if the new `vertex_positions` doesn't have the same length as the
-    /// previous vertices
+    /// In debug builds, panics if `vertex_positions` has less values than the
+    /// current vertices. In release builds, the vertex positions are
+    /// truncated to the new values instead.
     pub fn update_positions(&mut self, vertex_positions: impl ExactSizeIterator<Item = Vec3>) {
         debug_assert!(vertex_positions.len() >= self.vertex_positions.len());
         self.vertex_positions = vertex_positions.take(self.vertex_positions.len()).collect();
+        self.smooth_normals.clear();
+    }
+
+    /// Updates the vertex positions from the cloth point values, reusing the
+    /// existing vertex positions allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `cloth` - the cloth to retrieve the points from
+    /// * `transform` - the `GlobalTransform` associated to the cloth entity
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the `cloth` has less points than the current
+    /// vertices. In release builds, the vertex positions are truncated to the
+    /// cloth points instead.
+    pub fn update_positions_from(&mut self, cloth: &Cloth, transform: &GlobalTransform) {
+        let vertex_count = self.vertex_positions.len();
+        cloth.compute_vertex_positions_into(transform, &mut self.vertex_positions);
+        debug_assert!(self.vertex_positions.len() >= vertex_count);
+        self.vertex_positions.truncate(vertex_count);
+        self.smooth_normals.clear();
+        if let Some(velocities) = &mut self.vertex_velocities {
+            velocities.clear();
+            velocities.extend(
+                cloth
+                    .current_point_positions
+                    .iter()
+                    .zip(&cloth.previous_point_positions)
+                    .take(vertex_count)
+                    .map(|(current, previous)| current.distance(*previous)),
+            );
+        }
     }
 
     /// Duplicates `self` by computing one vertex position per indice.
@@ -179,94 +379,664 @@ impl ClothRendering {
             vertex_positions,
             indices,
             normal_computing: self.normal_computing,
+            fallback_normal: self.fallback_normal,
+            fold_ao: self.fold_ao,
+            area_weighted_normals: self.area_weighted_normals,
+            once_normals_computed: self.once_normals_computed,
+            previous_normals: Vec::new(),
+            smooth_normals: Vec::new(),
+            normal_weights: Vec::new(),
             vertex_uvs: vertex_uvs.into_iter().collect(),
+            uv_channel: self.uv_channel,
             vertex_colors: vertex_colors.into_iter().collect(),
+            vertex_velocities: self.vertex_velocities.as_ref().map(|v| {
+                self.indices
+                    .iter()
+                    .map(|indice| v.get(*indice as usize).copied().unwrap_or_default())
+                    .collect()
+            }),
         }
     }
 
-    /// Computes vertex normals from indices, should be called on
-    /// [`Self::duplicated_self`] as it requires no shared vertices
-    pub(crate) fn compute_flat_normals(&self) -> Vec<Vec3> {
+    /// Iterates over the vertex positions of [`Self::duplicated_self`],
+    /// without duplicating the other data
+    fn flat_positions(&self) -> impl Iterator<Item = Vec3> + '_ {
         self.indices
-            .chunks_exact(3)
-            .flat_map(|chunk| {
-                let [a, b, c] =
-                    [chunk[0], chunk[1], chunk[2]].map(|i| self.vertex_positions[i as usize]);
-                let normal = Self::face_normal(a, b, c);
-                [normal; 3]
-            })
-            .collect()
+            .iter()
+            .map(|indice| self.vertex_positions[*indice as usize])
+    }
+
+    /// Iterates over the flat vertex normals of [`Self::duplicated_self`],
+    /// one per index, without duplicating the other data
+    fn flat_normals(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.indices.chunks_exact(3).flat_map(|chunk| {
+            let [a, b, c] =
+                [chunk[0], chunk[1], chunk[2]].map(|i| self.vertex_positions[i as usize]);
+            let normal = self.face_normal(a, b, c);
+            [normal; 3]
+        })
     }
 
     /// Computes averaged vertex normals from indices, should be called without
-    /// duplication as it requires shared vertices
-    #[allow(clippy::cast_precision_loss)]
+    /// duplication as it requires shared vertices.
+    ///
+    /// If [`Self::area_weighted_normals`] is set, each face normal is weighted
+    /// by its triangle area, so large triangles dominate the shading of the
+    /// vertices they share with small ones. Otherwise every face contributes
+    /// equally.
+    ///
+    /// Vertices not used by any triangle, or only by degenerate triangles with
+    /// area weighting, get the [`Self::fallback_normal`]
     pub(crate) fn compute_smooth_normals(&self) -> Vec<Vec3> {
-        let mut map: HashMap<_, Vec<_>> = HashMap::with_capacity(self.vertex_positions.len());
+        let mut normals = Vec::new();
+        self.compute_smooth_normals_into(&mut normals, &mut Vec::new());
+        normals
+    }
+
+    /// Computes the smooth normals (See [`Self::compute_smooth_normals`])
+    /// into `normals`, reusing its allocation and the `weights` one.
+    ///
+    /// With area weighting, the non normalized cross products, whose length
+    /// is twice the triangle area, are accumulated and divided by the
+    /// accumulated lengths. Otherwise the face normals are accumulated and
+    /// divided by the number of triangles sharing the vertex.
+    fn compute_smooth_normals_into(&self, normals: &mut Vec<Vec3>, weights: &mut Vec<f32>) {
+        let vertex_count = self.vertex_positions.len();
+        normals.clear();
+        normals.resize(vertex_count, Vec3::ZERO);
+        weights.clear();
+        weights.resize(vertex_count, 0.0);
         for chunk in self.indices.chunks_exact(3) {
             let [a, b, c] = [chunk[0] as usize, chunk[1] as usize, chunk[2] as usize];
-            let flat_normal = Self::face_normal(
-                self.vertex_positions[a],
-                self.vertex_positions[b],
-                self.vertex_positions[c],
-            );
-            map.entry(a).or_default().push(flat_normal);
-            map.entry(b).or_default().push(flat_normal);
-            map.entry(c).or_default().push(flat_normal);
+            let [p_a, p_b, p_c] = [a, b, c].map(|i| self.vertex_positions[i]);
+            let (normal, weight) = if self.area_weighted_normals {
+                let cross = (p_b - p_a).cross(p_c - p_a);
+                (cross, cross.length())
+            } else {
+                (self.face_normal(p_a, p_b, p_c), 1.0)
+            };
+            for i in [a, b, c] {
+                normals[i] += normal;
+                weights[i] += weight;
+            }
+        }
+        for (normal, weight) in normals.iter_mut().zip(weights.iter()) {
+            *normal = if *weight > f32::EPSILON {
+                *normal / *weight
+            } else {
+                self.fallback_normal
+            };
+        }
+    }
+
+    /// Computes a per vertex fold occlusion factor from the vertex positions
+    /// and their smooth `normals`, should be called without duplication as
+    /// it requires shared vertices.
+    ///
+    /// Each vertex concavity is the average dot product between its normal
+    /// and the directions to its neighbors: a vertex in a fold has neighbors
+    /// above its surface. The factor goes from `1.0` for flat or convex
+    /// vertices to `0.0` for the deepest folds.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn compute_fold_occlusion(&self, normals: &[Vec3]) -> Vec<f32> {
+        let mut concavity = vec![(0.0, 0_u32); self.vertex_positions.len()];
+        for chunk in self.indices.chunks_exact(3) {
+            let [a, b, c] = [chunk[0] as usize, chunk[1] as usize, chunk[2] as usize];
+            for (from, to) in [(a, b), (b, c), (c, a), (b, a), (c, b), (a, c)] {
+                let direction =
+                    (self.vertex_positions[to] - self.vertex_positions[from]).normalize_or_zero();
+                let (sum, count) = &mut concavity[from];
+                *sum += normals[from].dot(direction);
+                *count += 1;
+            }
         }
-        (0..self.vertex_positions.len())
-            .map(|i| {
-                let sum = map[&i].iter().fold(Vec3::ZERO, |res, v| res + *v);
-                sum / map[&i].len() as f32
+        concavity
+            .into_iter()
+            .map(|(sum, count)| {
+                let average = if count == 0 { 0.0 } else { sum / count as f32 };
+                1.0 - average.clamp(0.0, 1.0)
             })
             .collect()
     }
 
-    fn vec3_vertex_attr(attr: &[Vec3]) -> Vec<[f32; 3]> {
-        attr.iter().map(Vec3::to_array).collect()
+    /// Multiplies the vertex colors, or white if there are none, by the fold
+    /// occlusion factors
+    fn fold_ao_colors(&self, normals: &[Vec3]) -> Vec<[f32; 4]> {
+        self.compute_fold_occlusion(normals)
+            .into_iter()
+            .enumerate()
+            .map(|(i, factor)| {
+                let [r, g, b, a] = self
+                    .vertex_colors
+                    .as_ref()
+                    .and_then(|colors| colors.get(i).copied())
+                    .unwrap_or([1.0; 4]);
+                [r * factor, g * factor, b * factor, a]
+            })
+            .collect()
+    }
+
+    /// Writes `values` in the color mesh buffer, reusing the existing buffer
+    /// in place if it has the expected size and format.
+    fn write_color_attribute(mesh: &mut Mesh, values: Vec<[f32; 4]>) {
+        if let Some(VertexAttributeValues::Float32x4(buffer)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
+        {
+            if buffer.len() == values.len() {
+                buffer.copy_from_slice(&values);
+                return;
+            }
+        }
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, values);
+    }
+
+    /// Writes `values` in the `attribute` mesh buffer, reusing the existing
+    /// buffer in place if it has the expected size and format.
+    fn write_vec3_attribute(mesh: &mut Mesh, attribute: MeshVertexAttribute, values: &[Vec3]) {
+        Self::write_vec3_attribute_iter(mesh, attribute, values.len(), values.iter().copied());
+    }
+
+    /// Writes the `len` `values` in the `attribute` mesh buffer, reusing the
+    /// existing buffer in place if it has the expected size and format.
+    fn write_vec3_attribute_iter(
+        mesh: &mut Mesh,
+        attribute: MeshVertexAttribute,
+        len: usize,
+        values: impl Iterator<Item = Vec3>,
+    ) {
+        if let Some(VertexAttributeValues::Float32x3(buffer)) = mesh.attribute_mut(attribute.id) {
+            if buffer.len() == len {
+                for (dst, src) in buffer.iter_mut().zip(values) {
+                    *dst = src.to_array();
+                }
+                return;
+            }
+        }
+        mesh.insert_attribute(attribute, values.map(|v| v.to_array()).collect::<Vec<_>>());
+    }
+
+    /// Writes the optional vertex velocities in the
+    /// [`Self::ATTRIBUTE_VELOCITY`] mesh buffer, reusing the existing buffer in
+    /// place if it has the expected size.
+    fn write_velocity_attribute(mesh: &mut Mesh, values: Option<&Vec<f32>>) {
+        let Some(values) = values else {
+            return;
+        };
+        if let Some(VertexAttributeValues::Float32(buffer)) =
+            mesh.attribute_mut(Self::ATTRIBUTE_VELOCITY.id)
+        {
+            if buffer.len() == values.len() {
+                buffer.copy_from_slice(values);
+                return;
+            }
+        }
+        mesh.insert_attribute(Self::ATTRIBUTE_VELOCITY, values.clone());
+    }
+
+    /// Checks if `mesh` already has the duplicated vertices topology, meaning
+    /// that every index is equal to its own position in the index buffer
+    #[allow(clippy::cast_possible_truncation)]
+    fn has_duplicated_topology(&self, mesh: &Mesh) -> bool {
+        mesh.count_vertices() == self.indices.len()
+            && match mesh.indices() {
+                Some(Indices::U32(v)) => {
+                    v.len() == self.indices.len()
+                        && v.iter().enumerate().all(|(i, indice)| *indice == i as u32)
+                }
+                _ => false,
+            }
+    }
+
+    /// Reads the `uv_channel` vertex UVs of `mesh`
+    pub(crate) fn read_uvs(mesh: &Mesh, uv_channel: UvChannel) -> Option<Vec<[f32; 2]>> {
+        mesh.attribute(uv_channel.attribute())
+            .and_then(|attr| match attr {
+                VertexAttributeValues::Float32x2(v) => Some(v.clone()),
+                _ => None,
+            })
     }
 
     /// applies the rendering data to the mesh.
     ///
+    /// Only the vertex positions and normals are rewritten every frame, in
+    /// place whenever the mesh buffers allow it, avoiding one buffer
+    /// allocation per attribute and per frame. The UVs, colors and indices
+    /// never change after initialization and are left untouched.
+    ///
     /// If [`Self::normal_computing`] is set to
-    /// [`NormalComputing::FlatNormals`], the vertices will be first be
-    /// duplicated before the normals are computed
+    /// [`NormalComputing::FlatNormals`], the mesh uses duplicated vertices
+    /// (See [`Self::duplicated_self`]). The duplicated UVs, colors and indices
+    /// are only written once, when the mesh doesn't have the duplicated
+    /// topology yet. Every frame, only the positions and normals are remapped
+    /// through the indices.
+    ///
+    /// The smooth normals computed by [`Self::update_normals`] since the last
+    /// positions update are reused, otherwise they are computed on the fly.
+    /// If [`Self::normal_computing`] is set to
+    /// [`NormalComputing::SmoothTemporal`], the blended
+    /// [`Self::previous_normals`] are applied, or the current smooth normals if
+    /// they don't match the vertex count.
+    ///
+    /// Note: Bevy still uploads the whole modified mesh to the GPU
     pub fn apply(&self, mesh: &mut Mesh) {
         match self.normal_computing {
-            NormalComputing::None => mesh.insert_attribute(
-                Mesh::ATTRIBUTE_POSITION,
-                Self::vec3_vertex_attr(&self.vertex_positions),
-            ),
-            NormalComputing::SmoothNormals => {
-                mesh.insert_attribute(
-                    Mesh::ATTRIBUTE_POSITION,
-                    Self::vec3_vertex_attr(&self.vertex_positions),
-                );
-                let vertex_normals = self.compute_smooth_normals();
-                mesh.insert_attribute(
-                    Mesh::ATTRIBUTE_NORMAL,
-                    Self::vec3_vertex_attr(&vertex_normals),
-                );
+            NormalComputing::None | NormalComputing::Once => {
+                Self::write_vec3_attribute(mesh, Mesh::ATTRIBUTE_POSITION, &self.vertex_positions);
+                Self::write_velocity_attribute(mesh, self.vertex_velocities.as_ref());
+                if self.fold_ao {
+                    let vertex_normals = self.smooth_normals();
+                    Self::write_color_attribute(mesh, self.fold_ao_colors(&vertex_normals));
+                }
+            }
+            NormalComputing::SmoothNormals | NormalComputing::SmoothTemporal { .. } => {
+                Self::write_vec3_attribute(mesh, Mesh::ATTRIBUTE_POSITION, &self.vertex_positions);
+                Self::write_velocity_attribute(mesh, self.vertex_velocities.as_ref());
+                let vertex_normals = self.smooth_normals();
+                Self::write_vec3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL, &vertex_normals);
+                if self.fold_ao {
+                    Self::write_color_attribute(mesh, self.fold_ao_colors(&vertex_normals));
+                }
             }
             NormalComputing::FlatNormals => {
-                let new_self = self.duplicated_self();
-                mesh.insert_attribute(
+                let vertex_count = self.indices.len();
+                if !self.has_duplicated_topology(mesh) {
+                    let new_self = self.duplicated_self();
+                    if let Some(attr) = new_self.vertex_uvs {
+                        mesh.insert_attribute(self.uv_channel.attribute(), attr);
+                    }
+                    // The other UV channel, unused by the cloth, is duplicated as well
+                    let other_channel = self.uv_channel.other();
+                    if let Some(uvs) = Self::read_uvs(mesh, other_channel) {
+                        let attr: Vec<[f32; 2]> = self
+                            .indices
+                            .iter()
+                            .map(|i| uvs.get(*i as usize).copied().unwrap_or_default())
+                            .collect();
+                        mesh.insert_attribute(other_channel.attribute(), attr);
+                    }
+                    if let Some(attr) = new_self.vertex_colors.filter(|_| !self.fold_ao) {
+                        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, attr);
+                    }
+                    mesh.insert_indices(Indices::U32(new_self.indices));
+                }
+                Self::write_vec3_attribute_iter(
+                    mesh,
                     Mesh::ATTRIBUTE_POSITION,
-                    Self::vec3_vertex_attr(&new_self.vertex_positions),
+                    vertex_count,
+                    self.flat_positions(),
                 );
-                if let Some(ref attr) = new_self.vertex_uvs {
-                    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, attr.clone());
-                }
-                if let Some(ref attr) = new_self.vertex_colors {
-                    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, attr.clone());
-                }
-                let vertex_normals = new_self.compute_flat_normals();
-                mesh.insert_attribute(
+                Self::write_vec3_attribute_iter(
+                    mesh,
                     Mesh::ATTRIBUTE_NORMAL,
-                    Self::vec3_vertex_attr(&vertex_normals),
+                    vertex_count,
+                    self.flat_normals(),
                 );
-                mesh.insert_indices(Indices::U32(new_self.indices));
+                let velocities = self.vertex_velocities.as_ref().map(|v| {
+                    self.indices
+                        .iter()
+                        .map(|indice| v.get(*indice as usize).copied().unwrap_or_default())
+                        .collect()
+                });
+                Self::write_velocity_attribute(mesh, velocities.as_ref());
+                if self.fold_ao {
+                    // The occlusion requires the shared vertices
+                    let colors = self.fold_ao_colors(&self.smooth_normals());
+                    let colors = self.indices.iter().map(|i| colors[*i as usize]).collect();
+                    Self::write_color_attribute(mesh, colors);
+                }
             }
         }
     }
+
+    /// Retrieves the smooth normals applied to the mesh: the blended
+    /// [`Self::previous_normals`] with [`NormalComputing::SmoothTemporal`] if
+    /// they match the vertex count, the normals computed by
+    /// [`Self::update_normals`] if they are up to date, or freshly computed
+    /// smooth normals otherwise
+    fn smooth_normals(&self) -> Cow<'_, [Vec3]> {
+        let vertex_count = self.vertex_positions.len();
+        match self.normal_computing {
+            NormalComputing::SmoothTemporal { .. }
+                if self.previous_normals.len() == vertex_count =>
+            {
+                Cow::Borrowed(&self.previous_normals)
+            }
+            _ if self.smooth_normals.len() == vertex_count => Cow::Borrowed(&self.smooth_normals),
+            _ => Cow::Owned(self.compute_smooth_normals()),
+        }
+    }
+
+    /// Computes the smooth vertex normals applied by [`Self::apply`], reusing
+    /// the same buffers every frame, if [`Self::normal_computing`] is set to
+    /// [`NormalComputing::SmoothNormals`] or
+    /// [`NormalComputing::SmoothTemporal`], or if [`Self::fold_ao`] is set.
+    /// The cloth systems call it every frame after updating the positions and
+    /// before [`Self::apply`].
+    ///
+    /// With [`NormalComputing::SmoothTemporal`], the normals are blended into
+    /// the [`Self::previous_normals`]. The `blend` weight is defined for a 60
+    /// FPS frame and scaled to `delta_time`, so the normals converge at the
+    /// same speed regardless of the frame rate. Without matching previous
+    /// normals, the current ones are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_time` - the frame duration, in seconds
+    pub fn update_normals(&mut self, delta_time: f32) {
+        let blend = match self.normal_computing {
+            NormalComputing::SmoothTemporal { blend } => Some(blend),
+            NormalComputing::SmoothNormals => None,
+            _ if self.fold_ao => None,
+            _ => return,
+        };
+        let mut normals = std::mem::take(&mut self.smooth_normals);
+        let mut weights = std::mem::take(&mut self.normal_weights);
+        self.compute_smooth_normals_into(&mut normals, &mut weights);
+        self.smooth_normals = normals;
+        self.normal_weights = weights;
+        let Some(blend) = blend else {
+            return;
+        };
+        if blend >= 1.0 || self.previous_normals.len() != self.smooth_normals.len() {
+            self.previous_normals.clone_from(&self.smooth_normals);
+            return;
+        }
+        let blend = 1.0 - (1.0 - blend.max(0.0)).powf(delta_time * 60.0);
+        for (previous, normal) in self.previous_normals.iter_mut().zip(&self.smooth_normals) {
+            *previous = previous.lerp(*normal, blend);
+        }
+    }
+
+    /// Computes and applies the smooth vertex normals to the mesh, if
+    /// [`Self::normal_computing`] is set to [`NormalComputing::Once`] and
+    /// they weren't computed yet.
+    ///
+    /// The cloth systems call it when the cloth first settles.
+    pub fn apply_once_normals(&mut self, mesh: &mut Mesh) {
+        if !matches!(self.normal_computing, NormalComputing::Once) || self.once_normals_computed {
+            return;
+        }
+        let vertex_normals = self.compute_smooth_normals();
+        Self::write_vec3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL, &vertex_normals);
+        self.once_normals_computed = true;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::mesh::rectangle_mesh;
+
+    #[test]
+    fn flat_normals_topology_is_applied_once() {
+        let mut mesh = rectangle_mesh((10, 10), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let rendering = ClothRendering::init(&mesh, NormalComputing::FlatNormals).unwrap();
+        assert!(!rendering.has_duplicated_topology(&mesh));
+        rendering.apply(&mut mesh);
+        assert!(rendering.has_duplicated_topology(&mesh));
+        assert_eq!(mesh.count_vertices(), rendering.indices.len());
+        rendering.apply(&mut mesh);
+        assert_eq!(mesh.count_vertices(), rendering.indices.len());
+    }
+
+    #[test]
+    fn flat_normals_match_duplicated_self() {
+        let mut mesh = rectangle_mesh((5, 5), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, NormalComputing::FlatNormals).unwrap();
+        let attribute = |mesh: &Mesh, id| match mesh.attribute(id) {
+            Some(VertexAttributeValues::Float32x3(v)) => v.clone(),
+            _ => panic!("Missing attribute"),
+        };
+        for offset in [0.0, 0.5, -1.0] {
+            rendering.vertex_positions[12].z = offset;
+            rendering.vertex_positions[3].x += offset;
+            rendering.apply(&mut mesh);
+            let duplicated = rendering.duplicated_self();
+            let positions: Vec<_> = duplicated
+                .vertex_positions
+                .iter()
+                .map(Vec3::to_array)
+                .collect();
+            let normals: Vec<_> = duplicated.flat_normals().map(|v| v.to_array()).collect();
+            assert_eq!(attribute(&mesh, Mesh::ATTRIBUTE_POSITION), positions);
+            assert_eq!(attribute(&mesh, Mesh::ATTRIBUTE_NORMAL), normals);
+            let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+            else {
+                panic!("Missing uv attribute");
+            };
+            assert_eq!(Some(uvs), duplicated.vertex_uvs.as_ref());
+        }
+    }
+
+    #[test]
+    fn velocity_attribute() {
+        let mut mesh = rectangle_mesh((2, 2), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, NormalComputing::None).unwrap();
+        rendering.vertex_velocities = Some(Vec::new());
+        let mut cloth = Cloth::default();
+        cloth
+            .current_point_positions
+            .clone_from(&rendering.vertex_positions);
+        cloth
+            .previous_point_positions
+            .clone_from(&rendering.vertex_positions);
+        cloth.current_point_positions[1] += Vec3::Z * 2.0;
+        rendering.update_positions_from(&cloth, &GlobalTransform::IDENTITY);
+        rendering.apply(&mut mesh);
+        let Some(VertexAttributeValues::Float32(velocities)) =
+            mesh.attribute(ClothRendering::ATTRIBUTE_VELOCITY)
+        else {
+            panic!("Missing velocity attribute");
+        };
+        assert_eq!(velocities, &[0.0, 2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn once_normals() {
+        let mut mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, NormalComputing::Once).unwrap();
+        rendering.vertex_positions[4] += Vec3::Z;
+        let normals = |mesh: &Mesh| match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(v)) => v.clone(),
+            _ => panic!("Missing normal attribute"),
+        };
+        rendering.apply(&mut mesh);
+        assert_eq!(normals(&mesh), [[0.0, 0.0, 1.0]; 9]);
+        rendering.apply_once_normals(&mut mesh);
+        assert!(rendering.once_normals_computed);
+        let expected: Vec<[f32; 3]> = rendering
+            .compute_smooth_normals()
+            .iter()
+            .map(Vec3::to_array)
+            .collect();
+        assert_eq!(normals(&mesh), expected);
+        // The normals are not computed again
+        rendering.vertex_positions[4] -= Vec3::Z * 2.0;
+        rendering.apply(&mut mesh);
+        rendering.apply_once_normals(&mut mesh);
+        assert_eq!(normals(&mesh), expected);
+    }
+
+    #[test]
+    fn degenerate_triangle_normals() {
+        let rendering = ClothRendering {
+            vertex_positions: vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0],
+            indices: vec![0, 1, 2],
+            fallback_normal: Vec3::Z,
+            ..Default::default()
+        };
+        assert_eq!(rendering.compute_smooth_normals(), [Vec3::Z; 3]);
+        assert_eq!(rendering.flat_normals().collect::<Vec<_>>(), [Vec3::Z; 3]);
+    }
+
+    #[test]
+    fn fold_ao() {
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, NormalComputing::None).unwrap();
+        let normal = rendering.compute_smooth_normals()[4];
+        // Center vertex pushed below the surface, in a fold
+        rendering.vertex_positions[4] -= normal * 0.5;
+        let occlusion = rendering.compute_fold_occlusion(&rendering.compute_smooth_normals());
+        assert!(occlusion[4] < 0.9, "{}", occlusion[4]);
+        // Center vertex pushed above the surface, convex
+        rendering.vertex_positions[4] += normal;
+        let occlusion = rendering.compute_fold_occlusion(&rendering.compute_smooth_normals());
+        assert!((occlusion[4] - 1.0).abs() < f32::EPSILON);
+        // The occlusion is written to the colors
+        let mut mesh = mesh;
+        rendering.vertex_positions[4] -= normal;
+        rendering.fold_ao = true;
+        rendering.apply(&mut mesh);
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("Missing color attribute");
+        };
+        assert!(colors[4][0] < 0.9);
+        assert!((colors[4][3] - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn smooth_normals_match_per_vertex_average() {
+        let mesh = rectangle_mesh((6, 6), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, NormalComputing::SmoothNormals).unwrap();
+        rendering.area_weighted_normals = false;
+        for (i, position) in (0_u8..).zip(&mut rendering.vertex_positions) {
+            position.z = (f32::from(i) * 0.7).sin();
+        }
+        let expected: Vec<Vec3> = (0_u32..)
+            .take(rendering.vertex_positions.len())
+            .map(|vertex| {
+                let normals: Vec<Vec3> = rendering
+                    .indices
+                    .chunks_exact(3)
+                    .filter(|chunk| chunk.contains(&vertex))
+                    .map(|chunk| {
+                        let [a, b, c] = [chunk[0], chunk[1], chunk[2]]
+                            .map(|i| rendering.vertex_positions[i as usize]);
+                        rendering.face_normal(a, b, c)
+                    })
+                    .collect();
+                normals.iter().fold(Vec3::ZERO, |res, v| res + *v) / normals.len() as f32
+            })
+            .collect();
+        assert_eq!(rendering.compute_smooth_normals(), expected);
+        // The reused buffers match the computed normals
+        rendering.update_normals(1.0 / 60.0);
+        rendering.update_normals(1.0 / 60.0);
+        assert_eq!(*rendering.smooth_normals(), expected);
+    }
+
+    #[test]
+    fn flip_winding_reverses_triangles() {
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let mut rendering = ClothRendering::init(&mesh, Default::default()).unwrap();
+        let original = rendering.indices.clone();
+        rendering.flip_winding();
+        for (flipped, original) in rendering
+            .indices
+            .chunks_exact(3)
+            .zip(original.chunks_exact(3))
+        {
+            assert_eq!(flipped, [original[2], original[1], original[0]]);
+        }
+    }
+
+    #[test]
+    fn smooth_temporal_normals_blend() {
+        let mut mesh = rectangle_mesh((5, 5), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let normals = |mesh: &Mesh| match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(v)) => v.clone(),
+            _ => panic!("Missing normals"),
+        };
+        let mut rendering =
+            ClothRendering::init(&mesh, NormalComputing::SmoothTemporal { blend: 1.0 }).unwrap();
+        rendering.update_normals(1.0 / 60.0);
+        rendering.apply(&mut mesh);
+        let flat = rendering.compute_smooth_normals();
+        for (i, position) in (0_u8..).zip(&mut rendering.vertex_positions) {
+            position.z = (f32::from(i) * 0.7).sin();
+        }
+        let bent = rendering.compute_smooth_normals();
+        // A blend of 1 matches the instantaneous smooth normals
+        rendering.update_normals(1.0 / 60.0);
+        rendering.apply(&mut mesh);
+        let expected: Vec<[f32; 3]> = bent.iter().map(Vec3::to_array).collect();
+        assert_eq!(normals(&mesh), expected);
+        // Lower blends lag behind the cloth shape
+        rendering.normal_computing = NormalComputing::SmoothTemporal { blend: 0.5 };
+        rendering.previous_normals.clone_from(&flat);
+        rendering.update_normals(1.0 / 60.0);
+        rendering.apply(&mut mesh);
+        for ((applied, flat), bent) in normals(&mesh).into_iter().zip(&flat).zip(&bent) {
+            assert!(Vec3::from(applied).abs_diff_eq(flat.lerp(*bent, 0.5), 1e-5));
+        }
+        assert_eq!(rendering.previous_normals.len(), mesh.count_vertices());
+        // Two 120 FPS frames blend as much as a single 60 FPS frame
+        rendering.previous_normals.clone_from(&flat);
+        rendering.update_normals(1.0 / 120.0);
+        rendering.update_normals(1.0 / 120.0);
+        for ((blended, flat), bent) in rendering.previous_normals.iter().zip(flat).zip(bent) {
+            assert!(blended.abs_diff_eq(flat.lerp(bent, 0.5), 1e-5));
+        }
+    }
+
+    #[test]
+    fn uv_channel() {
+        let mut mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let uvs_1: Vec<[f32; 2]> = (0_u8..9).map(|i| [f32::from(i), 0.0]).collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs_1.clone());
+        let rendering = ClothRendering::init_with_uv_channel(
+            &mesh,
+            NormalComputing::FlatNormals,
+            UvChannel::Uv1,
+        )
+        .unwrap();
+        assert_eq!(rendering.vertex_uvs.as_ref(), Some(&uvs_1));
+        // Both channels are duplicated
+        rendering.apply(&mut mesh);
+        for channel in [UvChannel::Uv0, UvChannel::Uv1] {
+            let uvs = ClothRendering::read_uvs(&mesh, channel).unwrap();
+            assert_eq!(uvs.len(), rendering.indices.len());
+        }
+        let expected: Vec<[f32; 2]> = rendering
+            .indices
+            .iter()
+            .map(|i| uvs_1[*i as usize])
+            .collect();
+        assert_eq!(
+            ClothRendering::read_uvs(&mesh, UvChannel::Uv1),
+            Some(expected)
+        );
+        // Missing channel
+        mesh.remove_attribute(Mesh::ATTRIBUTE_UV_1);
+        let rendering =
+            ClothRendering::init_with_uv_channel(&mesh, Default::default(), UvChannel::Uv1)
+                .unwrap();
+        assert!(rendering.vertex_uvs.is_none());
+    }
+
+    #[test]
+    fn area_weighted_smooth_normals() {
+        // A large triangle in the XY plane and a small one folded up along
+        // the shared edge 0-1, in the XZ plane
+        let mut rendering = ClothRendering {
+            vertex_positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y * 10.0, -Vec3::Z * 0.1],
+            indices: vec![0, 1, 2, 0, 1, 3],
+            ..Default::default()
+        };
+        let normals = rendering.compute_smooth_normals();
+        // The shared vertices are weighted 100 to 1 toward the large face
+        let expected = (Vec3::Z * 10.0 + Vec3::Y * 0.1) / 10.1;
+        assert!(normals[0].abs_diff_eq(expected, 1e-5), "{}", normals[0]);
+        assert!(normals[1].abs_diff_eq(expected, 1e-5), "{}", normals[1]);
+        assert!(normals[2].abs_diff_eq(Vec3::Z, 1e-5));
+        assert!(normals[3].abs_diff_eq(Vec3::Y, 1e-5));
+        // Equal weights
+        rendering.area_weighted_normals = false;
+        let normals = rendering.compute_smooth_normals();
+        assert!(normals[0].abs_diff_eq((Vec3::Z + Vec3::Y) * 0.5, 1e-5));
+    }
 }
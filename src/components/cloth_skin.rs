@@ -0,0 +1,107 @@
+use crate::point_grid::PointGrid;
+use bevy::{
+    ecs::prelude::{Component, Entity, ReflectComponent},
+    math::Vec3,
+    reflect::Reflect,
+};
+
+/// Cloth skin component, binding a separate "skin" mesh to the cloth
+/// simulation (cage deformation).
+///
+/// Add this component to a cloth entity to drive the mesh of the `target`
+/// entity from the cloth points. This allows to simulate a cheap, low
+/// resolution cloth while rendering a detailed mesh.
+///
+/// Every frame, each bound skin vertex is moved to the weighted average of its
+/// bound cloth points world space positions, expressed in the `target` entity
+/// local space. Skin vertices without bindings are left untouched.
+///
+/// If the skin mesh is a `TriangleList` with a normal attribute, its normals
+/// are then recomputed from the deformed positions: smooth normals for
+/// indexed meshes, flat normals otherwise.
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct ClothSkin {
+    /// Entity holding the skin `Mesh3d`
+    pub target: Entity,
+    /// Skin vertex bindings:
+    ///
+    /// * 0: the skin vertex index
+    /// * 1: the bound cloth point indexes and their weights
+    pub bindings: Vec<(usize, Vec<(usize, f32)>)>,
+}
+
+impl ClothSkin {
+    /// Generates a skin binding every skin vertex to its `count` nearest cloth
+    /// points, weighted by inverse distance.
+    ///
+    /// A skin vertex matching exactly a cloth point is bound to that single
+    /// point.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - the entity holding the skin mesh
+    /// * `skin_positions` - the skin vertex positions
+    /// * `cloth_positions` - the cloth point positions, in the same space as
+    ///   `skin_positions`
+    /// * `count` - the maximum number of cloth points bound to each vertex
+    #[must_use]
+    pub fn bind_nearest(
+        target: Entity,
+        skin_positions: &[Vec3],
+        cloth_positions: &[Vec3],
+        count: usize,
+    ) -> Self {
+        let grid = PointGrid::from_surface(cloth_positions);
+        let bindings = skin_positions
+            .iter()
+            .enumerate()
+            .map(|(i, pos)| (i, grid.nearest_weights(*pos, count)))
+            .collect();
+        Self { target, bindings }
+    }
+
+    /// Computes the bound skin vertex world space positions from the cloth
+    /// `point_positions`.
+    ///
+    /// Out of bounds cloth points are ignored and bindings with a null total
+    /// weight are skipped.
+    pub fn compute_positions<'a>(
+        &'a self,
+        point_positions: &'a [Vec3],
+    ) -> impl Iterator<Item = (usize, Vec3)> + 'a {
+        self.bindings.iter().filter_map(|(vertex, weights)| {
+            let (sum, total) = weights
+                .iter()
+                .filter_map(|(id, w)| point_positions.get(*id).map(|p| (*p * *w, *w)))
+                .fold((Vec3::ZERO, 0.0), |(sum, total), (p, w)| {
+                    (sum + p, total + w)
+                });
+            (total > f32::EPSILON).then(|| (*vertex, sum / total))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_nearest() {
+        let cloth_positions = [Vec3::ZERO, Vec3::X, Vec3::X * 10.0];
+        let skin_positions = [Vec3::X, Vec3::X * 0.25];
+        let skin =
+            ClothSkin::bind_nearest(Entity::PLACEHOLDER, &skin_positions, &cloth_positions, 2);
+        assert_eq!(skin.bindings[0], (0, vec![(1, 1.0)]));
+        let (vertex, weights) = &skin.bindings[1];
+        assert_eq!(*vertex, 1);
+        assert_eq!(weights[0].0, 0);
+        assert!((weights[0].1 - 0.75).abs() < 1e-5);
+        assert_eq!(weights[1].0, 1);
+        assert!((weights[1].1 - 0.25).abs() < 1e-5);
+        let positions: Vec<_> = skin.compute_positions(&cloth_positions).collect();
+        assert_eq!(positions[0], (0, Vec3::X));
+        assert_eq!(positions[1].0, 1);
+        assert!(positions[1].1.abs_diff_eq(Vec3::X * 0.25, 1e-5));
+    }
+}
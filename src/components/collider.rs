@@ -1,10 +1,78 @@
-use bevy::{ecs::component::Component, reflect::Reflect};
+use bevy::{
+    ecs::{component::Component, entity::Entity, reflect::ReflectComponent},
+    prelude::ReflectDefault,
+    reflect::Reflect,
+};
+
+/// Shape of the collider of a cloth entity, detecting the contacts with other
+/// colliders (See [`ClothCollider::live_shape`])
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum LiveShape {
+    /// Cuboid shape using the cloth AABB bounding box, computed from the
+    /// cloth mesh
+    #[default]
+    Aabb,
+    /// Convex hull of the live cloth points, rebuilt every `FixedUpdate`
+    /// step.
+    ///
+    /// The hull follows the draped or folded cloth much more tightly than
+    /// the AABB, so objects no longer collide with the empty space around
+    /// the cloth. Building a hull is expensive: the points are decimated,
+    /// only one every `points.len() / max_points` is used, bounding the cost
+    /// but smoothing out thin cloth features. The AABB is used if the hull
+    /// can't be built, for example from coplanar points.
+    ConvexHull {
+        /// Maximum number of cloth points used to build the hull
+        max_points: usize,
+    },
+}
+
+/// Entities of the colliders in contact with a cloth, refreshed by the
+/// collision handling.
+///
+/// Add this component to a cloth entity with a [`ClothCollider`] to track its
+/// contacts, for gameplay logic like detecting a cloth covering an object. It
+/// isn't populated without the component.
+///
+/// The contacts are detected through the cloth collider shape (See
+/// [`ClothCollider::live_shape`]), and don't include the other cloths (See
+/// [`ClothCollider::collide_with_cloths`]).
+///
+/// The component is updated in the `FixedUpdate` schedule, alongside the
+/// collision handling (See `ClothSystems::Collisions`), on every collision
+/// check step (See [`ClothCollider::check_interval`]). It is cleared when
+/// the collisions are disabled and left untouched while the cloth is culled.
+/// It is only flagged as changed when the contacts differ, so
+/// `Changed<ClothTouching>` can be used to react to new or lost contacts.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct ClothTouching(pub Vec<Entity>);
+
+impl ClothTouching {
+    /// Whether the cloth is in contact with the `entity` collider
+    ///
+    /// ```rust
+    /// # use bevy::prelude::Entity;
+    /// # use bevy_silk::prelude::*;
+    /// let altar = Entity::from_raw(42);
+    /// let touching = ClothTouching(vec![altar]);
+    /// assert!(touching.contains(altar));
+    /// assert!(!touching.contains(Entity::PLACEHOLDER));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
 
 /// Enables collisions on a cloth entity
 ///
-/// The collisions will be detected through a cuboid shape using the cloth AABB
-/// bounding box.
+/// The collisions will be detected through a shape around the cloth, a cuboid
+/// using the cloth AABB bounding box by default (See [`Self::live_shape`]).
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component, Default)]
 pub struct ClothCollider {
     /// offset to apply on collision projected point to prevent clipping
     pub offset: f32,
@@ -16,6 +84,83 @@ pub struct ClothCollider {
     /// Defines the velocity reduction coefficient for dynamic rigibodies
     /// colliding with the cloth, improving the cloth effect.
     pub dampen_others: Option<f32>,
+    /// Ratio of the penetration resolved every frame, between 0.0 and 1.0:
+    /// - 1 meaning penetrating points are moved out of colliders immediately
+    /// - below 1 meaning the penetration is resolved gradually over several
+    ///   frames, for softer and less poppy collisions
+    ///
+    /// Note: low values may let fast moving colliders go through the cloth,
+    /// as the penetration can increase faster than it is resolved. Keep it
+    /// close to 1.0 when colliding with high velocity objects.
+    pub push_strength: f32,
+    /// Number of collision solving iterations per contact, at least 1.
+    ///
+    /// Each extra iteration runs a single stick solving pass followed by a new
+    /// point projection, converging collisions and stick constraints together.
+    /// Increasing it reduces clipping on deep contacts, at the cost of
+    /// performance.
+    pub solve_iterations: u8,
+    /// Number of relaxation passes over all the contacting colliders, at
+    /// least 1.
+    ///
+    /// Each pass re-projects the cloth points against every contacting
+    /// collider in turn. With a single pass, a cloth pinched between two
+    /// colliders is pushed into the second collider when resolving the first
+    /// one, and jitters. Extra passes let opposing contacts reach an
+    /// equilibrium.
+    ///
+    /// The collision cost grows linearly with this value. Keep it to 1 for
+    /// cloths touching a single collider at a time, use 2 to 4 for pinch
+    /// scenarios like a cloth squeezed between two bodies.
+    pub contact_relaxation: u8,
+    /// Enables the collisions, can be toggled at runtime (e.g. for LOD or
+    /// far away cloths).
+    ///
+    /// While enabled, the following components are inserted on the cloth
+    /// entity:
+    /// - With `rapier_collisions`: `RigidBody::KinematicPositionBased`,
+    ///   `Collider` and `SolverGroups`
+    /// - With `avian_collisions`: `RigidBody::Kinematic`, `Sensor` and
+    ///   `Collider`
+    ///
+    /// Disabling the collisions removes these components, and the collisions
+    /// are no longer handled. Enabling them back inserts the components
+    /// again.
+    pub enabled: bool,
+    /// Enables swept collisions, preventing fast moving cloth points from
+    /// tunneling through thin colliders.
+    ///
+    /// The segment travelled by each point since the previous step is cast
+    /// against the colliders, and a point crossing a collider surface is
+    /// pushed back to the entry point, minus the `offset`. If no intersection
+    /// is found along the segment, the regular discrete collision result is
+    /// kept.
+    ///
+    /// Swept collisions run an extra ray cast per point and contact, so keep
+    /// it disabled unless tunneling is noticeable.
+    pub swept: bool,
+    /// Number of fixed steps between two collision checks, at least 1.
+    ///
+    /// Higher values resolve the collisions of the cloth only every N
+    /// `FixedUpdate` steps, trading some collision latency for performance
+    /// in scenes with many cloths. The checks are staggered across cloths
+    /// from their entity index (See [`Self::is_check_step`]), spreading the
+    /// load over the fixed steps.
+    pub check_interval: u8,
+    /// Enables collisions with the other cloths with this option enabled,
+    /// like a pile of towels.
+    ///
+    /// The collisions are approximated at the box level: the cloth points are
+    /// pushed out of the other cloths AABB, extended by the `offset`. This
+    /// prevents gross interpenetration but doesn't let cloths fold onto each
+    /// other. To avoid oscillations, each pair of cloths is resolved in a
+    /// single direction: the cloth with the greater entity is pushed out of
+    /// the other one, so the first spawned cloths act as static boxes for the
+    /// next ones.
+    pub collide_with_cloths: bool,
+    /// Shape of the cloth collider detecting the contacts with other
+    /// colliders, see [`LiveShape`]
+    pub live_shape: LiveShape,
 }
 
 impl Default for ClothCollider {
@@ -24,6 +169,143 @@ impl Default for ClothCollider {
             offset: 0.25,
             velocity_coefficient: 1.0,
             dampen_others: None,
+            push_strength: 1.0,
+            solve_iterations: 1,
+            contact_relaxation: 1,
+            enabled: true,
+            swept: false,
+            check_interval: 1,
+            collide_with_cloths: false,
+            live_shape: LiveShape::Aabb,
         }
     }
 }
+
+#[allow(clippy::missing_const_for_fn)]
+impl ClothCollider {
+    /// Instantiates a new `ClothCollider` with the default options
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// let collider = ClothCollider::new();
+    /// assert!(collider.enabled);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the offset applied on the collision projected points to prevent
+    /// clipping (See [`Self::offset`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// let collider = ClothCollider::new().with_offset(0.1);
+    /// assert_eq!(collider.offset, 0.1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the coefficient of the received velocity to apply to the cloth
+    /// (See [`Self::velocity_coefficient`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// // The cloth ignores the colliders velocity
+    /// let collider = ClothCollider::new().with_velocity_coefficient(0.0);
+    /// assert_eq!(collider.velocity_coefficient, 0.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_velocity_coefficient(mut self, coefficient: f32) -> Self {
+        self.velocity_coefficient = coefficient;
+        self
+    }
+
+    /// Sets the velocity reduction coefficient for dynamic rigid bodies
+    /// colliding with the cloth (See [`Self::dampen_others`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// let collider = ClothCollider::new().with_dampen_others(0.02);
+    /// assert_eq!(collider.dampen_others, Some(0.02));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_dampen_others(mut self, coefficient: f32) -> Self {
+        self.dampen_others = Some(coefficient);
+        self
+    }
+
+    /// Sets the number of relaxation passes over the contacting colliders
+    /// (See [`Self::contact_relaxation`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// // The cloth is pinched between two colliders
+    /// let collider = ClothCollider::new().with_contact_relaxation(3);
+    /// assert_eq!(collider.contact_relaxation, 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_contact_relaxation(mut self, passes: u8) -> Self {
+        self.contact_relaxation = passes;
+        self
+    }
+
+    /// Enables or disables the collisions (See [`Self::enabled`])
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// // The collisions will be enabled later, for example on LOD change
+    /// let collider = ClothCollider::new().with_enabled(false);
+    /// assert!(!collider.enabled);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns `true` if the collisions of the cloth `entity` should be
+    /// resolved on the given fixed `step`, according to
+    /// [`Self::check_interval`].
+    ///
+    /// The entity index is added to the step count, so cloths spawned
+    /// together check their collisions on successive steps instead of all on
+    /// the same one.
+    #[inline]
+    #[must_use]
+    pub fn is_check_step(&self, step: u32, entity: Entity) -> bool {
+        let interval = u32::from(self.check_interval.max(1));
+        step.wrapping_add(entity.index()).is_multiple_of(interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staggered_check_steps() {
+        let collider = ClothCollider {
+            check_interval: 3,
+            ..Default::default()
+        };
+        let [a, b] = [0, 1].map(Entity::from_raw);
+        let steps = |entity| {
+            (0..6)
+                .filter(|step| collider.is_check_step(*step, entity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(steps(a), [0, 3]);
+        assert_eq!(steps(b), [2, 5]);
+        assert!((0..6).all(|step| ClothCollider::default().is_check_step(step, a)));
+    }
+}
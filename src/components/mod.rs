@@ -2,8 +2,16 @@
 pub mod cloth;
 /// cloth builder module
 pub mod cloth_builder;
+/// cloth cull module
+pub mod cloth_cull;
+/// cloth follower module
+pub mod cloth_follower;
+/// cloth path module
+pub mod cloth_path;
 /// cloth rendering module
 pub mod cloth_rendering;
+/// cloth skin module
+pub mod cloth_skin;
 /// collider module
 #[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
 pub mod collider;
@@ -1,8 +1,11 @@
 use bevy::{
     ecs::prelude::{Component, ReflectComponent, Resource},
-    math::Vec3,
+    log,
+    math::{BVec3, Vec3},
     reflect::Reflect,
+    render::view::ViewVisibility,
 };
+use std::borrow::Cow;
 
 /// Defines how verlet physics acceleration components like gravity and winds
 /// are smoothed through every frame.
@@ -22,38 +25,361 @@ pub enum AccelerationSmoothing {
     FixedCoefficient(f32),
 }
 
+/// Defines the order in which the cloth simulation steps are solved every
+/// frame.
+#[derive(Debug, Copy, Clone, Default, Reflect, PartialEq, Eq)]
+pub enum SolveOrder {
+    /// The points are first integrated with their velocity and external
+    /// accelerations, then the sticks constraints are solved. The constraints
+    /// get the final word, producing the least stretch before rendering.
+    ///
+    /// Suits most cloth, especially loose and elastic fabrics.
+    #[default]
+    PointsThenSticks,
+    /// The sticks constraints are first solved, then the points are
+    /// integrated with their velocity and external accelerations. The
+    /// constraints are applied on the previous frame integration, producing
+    /// less stretch accumulation over frames for stiff materials but the
+    /// rendered points are not constrained.
+    ///
+    /// Suits stiff materials like leather or heavy canvas.
+    SticksThenPoints,
+}
+
+/// Overrides the [`ClothConfig::gravity`] of a cloth entity, like an indoor
+/// cloth with a custom gravity, keeping every other configuration field.
+///
+/// Takes precedence over both the `ClothConfig` resource and component.
+#[derive(Debug, Copy, Clone, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct GravityOverride(pub Vec3);
+
+/// Overrides the [`ClothConfig::velocity_damping`] of a cloth entity, keeping
+/// every other configuration field.
+///
+/// Takes precedence over both the `ClothConfig` resource and component.
+#[derive(Debug, Copy, Clone, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct VelocityDampingOverride(pub f32);
+
 /// Cloth physics configuration.
 ///
 /// Used as a resource, it is used as a global configuration for every cloth
 /// entity. Used as a component on a cloth entity, it overrides the global
 /// values for that cloth.
+///
+/// ## Precedence
+///
+/// The configuration of a cloth is resolved in the following order, the
+/// later overriding the former:
+///
+/// 1. the `ClothConfig` resource
+/// 2. the `ClothConfig` component of the cloth entity, overriding every field
+/// 3. the [`GravityOverride`] and [`VelocityDampingOverride`] components of the cloth
+///    entity, overriding only their field
 #[derive(Debug, Clone, Component, Reflect, Resource)]
 #[reflect(Component)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClothConfig {
     /// Custom gravity, classic (0, -9.81, 0) is used by default
     pub gravity: Vec3,
-    /// Custom friction to apply to velocity. Useful to reduce the elasticity of
-    /// a cloth.
+    /// Ratio of the points velocity removed every update, controlling how
+    /// quickly the cloth motion dies out, between 0.0 and 1.0:
+    /// - 0 meaning the points keep their full velocity
+    /// - 1 meaning the points stop every frame
+    ///
+    /// The damping only affects the motion: the cloth shape rigidity is
+    /// controlled by the sticks (See [`Self::sticks_computation_depth`] and
+    /// [`Cloth::stick_stiffness`]).
     ///
-    /// Note: The friction is not applied to external accelerations like gravity
-    /// and winds Note: will be clamped between 0.0 and 1.0
-    pub friction: f32,
+    /// Note: The damping is not applied to external accelerations like
+    /// gravity and winds
+    ///
+    /// [`Cloth::stick_stiffness`]: crate::components::cloth::Cloth::stick_stiffness
+    pub velocity_damping: f32,
+    /// Legacy name of [`Self::velocity_damping`], not set by default.
+    ///
+    /// Still honored for backward compatibility: if set, it is used when
+    /// [`Self::velocity_damping`] is left to its default value (See
+    /// [`Self::DEFAULT_VELOCITY_DAMPING`]). If both fields are changed,
+    /// [`Self::velocity_damping`] wins.
+    #[deprecated(note = "Use `velocity_damping` instead")]
+    pub friction: Option<f32>,
     /// Sets the number of sticks computation iteration.
     /// The higher the value, the more precision and less elasticity for the
     /// sticks but the cost is increased
+    ///
+    /// Note: the effective value is clamped to
+    /// [`Self::max_sticks_computation_depth`] (See
+    /// [`Self::effective_sticks_computation_depth`])
     pub sticks_computation_depth: u8,
+    /// Maximum number of sticks computation iterations actually run, higher
+    /// [`Self::sticks_computation_depth`] values are clamped to prevent
+    /// accidental frame time cliffs. Defaults to 32
+    pub max_sticks_computation_depth: u8,
+    /// [`Self::sticks_computation_depth`] above which a warning is logged
+    /// once. Defaults to 20
+    ///
+    /// Past a few iterations, each one costs a full sticks pass for
+    /// diminishing stiffness returns: bend sticks (See
+    /// [`ClothBuilder::with_full_stick_generation`]) or
+    /// [`Self::max_stretch_ratio`] are usually cheaper ways to stiffen a
+    /// cloth.
+    ///
+    /// [`ClothBuilder::with_full_stick_generation`]: crate::components::cloth_builder::ClothBuilder::with_full_stick_generation
+    pub sticks_depth_warning_threshold: u8,
+    /// If set to true, the sticks are iterated in alternating directions on
+    /// every computation pass, cancelling the sideways drift of a cloth
+    /// under symmetric loads (See [`Cloth::update_sticks_alternating`]).
+    ///
+    /// Note: the sorted sticks are cached, and sorted again when the sticks
+    /// change
+    ///
+    /// [`Cloth::update_sticks_alternating`]: crate::components::cloth::Cloth::update_sticks_alternating
+    pub symmetric_solve: bool,
+    /// Softness of the sticks connecting an anchored point to a dynamic
+    /// point, between 0.0 and 1.0.
+    ///
+    /// Every stick constraint iteration, the dynamic end of such a stick is
+    /// fully moved to its target length from the anchor, which can yank it
+    /// unrealistically when the anchor moves fast or when the stick is very
+    /// short. The correction is scaled by `1.0 - anchor_stick_softness`,
+    /// spreading it over several iterations and frames.
+    ///
+    /// Note: 0.0 (default) applies the full correction, 1.0 disables the
+    /// anchored sticks entirely
+    pub anchor_stick_softness: f32,
+    /// Shape memory stiffness, pulling the cloth back toward its authored
+    /// pose like an elastic fabric.
+    ///
+    /// Every non anchored point is accelerated toward its rest position (See
+    /// [`Cloth::rest_positions`]) by `shape_memory` times its distance to
+    /// it, smoothed like the gravity (See [`Self::acceleration_smoothing`]).
+    /// The rest positions are expressed in the cloth entity space, so they
+    /// follow the moving transform and the cloth springs back to its pose
+    /// relative to the entity, while the sticks keep solving the local
+    /// lengths.
+    ///
+    /// The pull competes with the gravity: at rest, a point sags by about
+    /// `gravity / shape_memory` below its rest position. Combine with some
+    /// [`Self::velocity_damping`] to damp the oscillations.
+    ///
+    /// Note: 0.0 (default) disables the shape memory
+    ///
+    /// [`Cloth::rest_positions`]: crate::components::cloth::Cloth::rest_positions
+    pub shape_memory: f32,
+    /// Optional world space height of a flat ground below which the non
+    /// anchored points can't fall.
+    ///
+    /// The points are lifted back above the floor after the sticks
+    /// computation, leaving a [`Self::floor_offset`] gap. This is the
+    /// cheapest ground collision and requires no physics feature.
+    pub floor_height: Option<f32>,
+    /// Gap kept between the points and the [`Self::floor_height`] to prevent
+    /// clipping, like the collider `offset`. Defaults to 0.01
+    pub floor_offset: f32,
     /// Smoothing behaviour for gravity and winds
     pub acceleration_smoothing: AccelerationSmoothing,
+    /// Optional maximum stick stretch ratio.
+    ///
+    /// If set, a final clamping pass runs after the sticks computation,
+    /// walking the stick network from the anchored points outward and
+    /// pulling back every point so that no stick exceeds its target length
+    /// multiplied by this ratio. This prevents the "rubber band" look of a
+    /// cloth under extreme forces.
+    ///
+    /// Note: Values below 1.0 will shrink the cloth
+    pub max_stretch_ratio: Option<f32>,
+    /// Inertia pseudo-force scale.
+    ///
+    /// The frame to frame acceleration of the cloth entity is computed from
+    /// its successive `GlobalTransform` and its opposite, multiplied by this
+    /// value, is applied to the non anchored points. This makes the cloth
+    /// trail behind an accelerating parent, like the cape of a running
+    /// character.
+    ///
+    /// Note: 0.0 (default) disables inertia
+    pub inertia_scale: f32,
+    /// Order of the simulation steps, see [`SolveOrder`]
+    pub solve_order: SolveOrder,
+    /// If set to true, cloth entities not visible by any camera (with a
+    /// `ViewVisibility` set to hidden) are paused: their simulation and mesh
+    /// update are skipped, along with the update of their skin and follower
+    /// meshes.
+    ///
+    /// Note: A paused cloth resumes from its state when it was hidden, it
+    /// may visibly pop when it re-enters the view, especially if its anchors
+    /// moved in the meantime.
+    pub pause_when_invisible: bool,
+    /// Axes on which the cloth points can't move, useful to keep a cloth
+    /// planar in 2.5D games (e.g. locking the Z axis for side-scroller
+    /// banners).
+    ///
+    /// The gravity, wind and inertia components along the locked axes are
+    /// ignored and the non anchored points keep their world space value on
+    /// these axes. Anchored points are unaffected.
+    ///
+    /// Collisions with 3D colliders are projected on the free axes: a point
+    /// pushed out of a collider only moves along the unlocked axes.
+    pub locked_axes: BVec3,
+    /// If set to true, the wind is applied along the cloth surface normal
+    /// instead of uniformly, like on a sail: the wind pushes whichever face
+    /// it hits, proportionally to the angle between the wind and the surface,
+    /// and a surface parallel to the wind catches nothing.
+    ///
+    /// Note: requires the cloth triangles, cloths built from explicit points
+    /// won't receive any wind
+    pub sail_mode: bool,
+    /// Maximum distance, in world units, a cloth point can move during a
+    /// single update for the cloth to be considered settled.
+    ///
+    /// A [`ClothEvent`] is sent whenever a cloth transitions between moving
+    /// and settled.
+    ///
+    /// [`ClothEvent`]: crate::event::ClothEvent
+    pub settle_threshold: f32,
+    /// Time offset, in seconds, added to the cloth simulation time (See
+    /// [`Cloth::sim_time`]) when evaluating the winds (See
+    /// [`Wind::current_velocity`]).
+    ///
+    /// Identical cloths share the same wind and move in perfect sync, giving a
+    /// different offset to each cloth through a [`ClothConfig`] component
+    /// phase-shifts their `SinWave` winds. The value can be randomized at
+    /// spawn:
+    ///
+    /// ```rust
+    /// # use bevy_silk::prelude::*;
+    /// # fn random() -> f32 { 0.42 }
+    /// let config = ClothConfig {
+    ///     // `random` returns a value between 0 and 1
+    ///     phase_offset: random() * 10.0,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    ///
+    /// [`Cloth::sim_time`]: crate::components::cloth::Cloth::sim_time
+    /// [`Wind::current_velocity`]: crate::wind::Wind::current_velocity
+    pub phase_offset: f32,
+    /// If set to true, the built-in gravity and winds are disabled: the
+    /// [`Self::gravity`] and the `Winds` resource are ignored, and only
+    /// external manipulations of the cloth points (See
+    /// [`Cloth::set_initial_velocity`]) move the cloth.
+    ///
+    /// This is the basis for scripted cloth, more explicit than zeroing the
+    /// gravity and not using any wind. The inertia, anchors and sticks are
+    /// still simulated.
+    ///
+    /// [`Cloth::set_initial_velocity`]: crate::components::cloth::Cloth::set_initial_velocity
+    pub external_forces_only: bool,
+    /// Number of anchor substeps, interleaving the anchors update and the
+    /// sticks computation to let the cloth interior keep up with fast moving
+    /// anchors (e.g. a swung flagpole).
+    ///
+    /// Every substep moves the anchored points a fraction of the way from
+    /// their previous position to their new anchor position, using a linear
+    /// interpolation: substep `i` of `n` places them at `i / n` of the way.
+    /// The sticks are then computed [`Self::sticks_computation_depth`] times,
+    /// pulling the cloth interior along before the next substep.
+    ///
+    /// Note: 0 and 1 (default) disable the substeps. The sticks computation
+    /// cost is multiplied by the substep count
+    pub anchor_substeps: u8,
+    /// If set to true, the cloth is stepped once per elapsed `Time<Fixed>`
+    /// step (See the `FixedUpdate` schedule) with the fixed timestep duration,
+    /// instead of once per frame with the frame delta time.
+    ///
+    /// The cloth [`Cloth::sim_time`], and therefore the winds, then follow the
+    /// number of fixed steps rather than the frame timing: the same fixed step
+    /// count always produces the same motion, whatever the frame rate. This
+    /// is required for reproducible recordings and replays.
+    ///
+    /// Note: frames without any elapsed fixed step don't move the cloth
+    ///
+    /// [`Cloth::sim_time`]: crate::components::cloth::Cloth::sim_time
+    pub fixed_timestep: bool,
 }
 
 impl ClothConfig {
     /// Default Y value for gravity
     pub const DEFAULT_GRAVITY: f32 = -9.81;
+    /// Default value for [`Self::velocity_damping`]
+    pub const DEFAULT_VELOCITY_DAMPING: f32 = 0.01;
 
+    /// Retrieves the effective velocity damping: the legacy
+    /// [`Self::friction`] if it is set and [`Self::velocity_damping`] is left
+    /// to its default value, [`Self::velocity_damping`] otherwise
+    #[must_use]
+    #[inline]
+    #[allow(deprecated)]
+    pub fn effective_velocity_damping(&self) -> f32 {
+        match self.friction {
+            Some(friction)
+                if (self.velocity_damping - Self::DEFAULT_VELOCITY_DAMPING).abs()
+                    <= f32::EPSILON =>
+            {
+                friction
+            }
+            _ => self.velocity_damping,
+        }
+    }
+
+    /// Retrieves the velocity multiplier applied to the points every update,
+    /// from [`Self::effective_velocity_damping`]
     #[must_use]
     #[inline]
     pub(crate) fn friction_coefficient(&self) -> f32 {
-        1.0 - self.friction.clamp(0.0, 1.0)
+        1.0 - self.effective_velocity_damping().clamp(0.0, 1.0)
+    }
+
+    /// Applies the single field override components of a cloth entity (See
+    /// [`GravityOverride`] and [`VelocityDampingOverride`]), cloning the
+    /// configuration only if there is an override.
+    #[must_use]
+    pub(crate) fn with_overrides(
+        &self,
+        gravity: Option<&GravityOverride>,
+        damping: Option<&VelocityDampingOverride>,
+    ) -> Cow<'_, Self> {
+        if gravity.is_none() && damping.is_none() {
+            return Cow::Borrowed(self);
+        }
+        let mut config = self.clone();
+        if let Some(gravity) = gravity {
+            config.gravity = gravity.0;
+        }
+        if let Some(damping) = damping {
+            config.velocity_damping = damping.0;
+            #[allow(deprecated)]
+            {
+                config.friction = None;
+            }
+        }
+        Cow::Owned(config)
+    }
+
+    /// Retrieves the number of sticks computation iterations run by the
+    /// simulation: [`Self::sticks_computation_depth`] clamped to
+    /// [`Self::max_sticks_computation_depth`].
+    ///
+    /// Logs a warning, once, if the depth exceeds
+    /// [`Self::sticks_depth_warning_threshold`]
+    #[must_use]
+    pub fn effective_sticks_computation_depth(&self) -> u8 {
+        if self.sticks_computation_depth > self.sticks_depth_warning_threshold {
+            log::warn_once!(
+                "A cloth `sticks_computation_depth` of {} has a high cost for diminishing \
+                 stiffness returns, consider using bend sticks or a `max_stretch_ratio` instead",
+                self.sticks_computation_depth
+            );
+        }
+        self.sticks_computation_depth
+            .min(self.max_sticks_computation_depth)
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn is_paused(&self, visibility: Option<&ViewVisibility>) -> bool {
+        self.pause_when_invisible && visibility.is_some_and(|v| !v.get())
     }
 
     /// Applies smoothing to a given `acceleration` value.
@@ -97,9 +423,77 @@ impl Default for ClothConfig {
     fn default() -> Self {
         Self {
             gravity: Vec3::Y * Self::DEFAULT_GRAVITY,
-            friction: 0.01,
+            velocity_damping: Self::DEFAULT_VELOCITY_DAMPING,
+            #[allow(deprecated)]
+            friction: None,
             sticks_computation_depth: 5,
+            max_sticks_computation_depth: 32,
+            sticks_depth_warning_threshold: 20,
+            symmetric_solve: false,
+            anchor_stick_softness: 0.0,
+            shape_memory: 0.0,
+            floor_height: None,
+            floor_offset: 0.01,
             acceleration_smoothing: Default::default(),
+            max_stretch_ratio: None,
+            inertia_scale: 0.0,
+            solve_order: SolveOrder::default(),
+            pause_when_invisible: false,
+            locked_axes: BVec3::FALSE,
+            sail_mode: false,
+            settle_threshold: 0.001,
+            phase_offset: 0.0,
+            external_forces_only: false,
+            anchor_substeps: 1,
+            fixed_timestep: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effective_sticks_computation_depth() {
+        let mut config = ClothConfig::default();
+        assert_eq!(config.effective_sticks_computation_depth(), 5);
+        config.sticks_computation_depth = 100;
+        assert_eq!(config.effective_sticks_computation_depth(), 32);
+        config.max_sticks_computation_depth = u8::MAX;
+        assert_eq!(config.effective_sticks_computation_depth(), 100);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_friction() {
+        let mut config = ClothConfig {
+            velocity_damping: 0.2,
+            ..Default::default()
+        };
+        assert!((config.friction_coefficient() - 0.8).abs() < f32::EPSILON);
+        // The new field wins when both are set
+        config.friction = Some(0.1);
+        assert!((config.friction_coefficient() - 0.8).abs() < f32::EPSILON);
+        // The legacy field is applied if the new one is left to its default
+        config.velocity_damping = ClothConfig::DEFAULT_VELOCITY_DAMPING;
+        assert!((config.friction_coefficient() - 0.9).abs() < f32::EPSILON);
+        // An explicit default damping is kept when the legacy field isn't set
+        let config = ClothConfig {
+            velocity_damping: ClothConfig::DEFAULT_VELOCITY_DAMPING,
+            ..ClothConfig::no_gravity()
+        };
+        assert!(
+            (config.effective_velocity_damping() - ClothConfig::DEFAULT_VELOCITY_DAMPING).abs()
+                < f32::EPSILON
+        );
+        let config = ClothConfig {
+            friction: Some(0.02),
+            ..Default::default()
+        };
+        assert!((config.effective_velocity_damping() - 0.02).abs() < f32::EPSILON);
+        // The override replaces both fields
+        let config = config.with_overrides(None, Some(&VelocityDampingOverride(0.3)));
+        assert!((config.friction_coefficient() - 0.7).abs() < f32::EPSILON);
+    }
+}
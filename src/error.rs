@@ -1,3 +1,4 @@
+use bevy::math::Vec3;
 use thiserror::Error;
 
 /// Error enum for [`ClothPlugin`]
@@ -22,4 +23,12 @@ pub enum Error {
     /// The mesh associated to a cloth has no indices
     #[error("Cloth requires meshes with indexed geometry")]
     MissingIndices,
+    /// The merged cloths have non uniformly different rest scales
+    #[error("Cannot merge a cloth with a rest scale of {other} into a cloth with a rest scale of {rest_scale}")]
+    MismatchedRestScale {
+        /// Rest scale of the cloth merged into
+        rest_scale: Vec3,
+        /// Rest scale of the merged cloth
+        other: Vec3,
+    },
 }
@@ -0,0 +1,35 @@
+use bevy::ecs::{entity::Entity, event::Event};
+
+/// Cloth simulation events, sent once per state transition.
+///
+/// Allows gameplay code to react to a cloth state without polling it every
+/// frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Event)]
+pub enum ClothEvent {
+    /// The cloth came to rest: none of its points moved by more than
+    /// [`ClothConfig::settle_threshold`] during the last update.
+    ///
+    /// Note: a newly spawned cloth is considered moving, so a cloth at rest
+    /// from the start sends this event after its first update.
+    ///
+    /// [`ClothConfig::settle_threshold`]: crate::config::ClothConfig::settle_threshold
+    Settled {
+        /// The cloth entity
+        entity: Entity,
+    },
+    /// The cloth started moving again after being [`Self::Settled`]
+    Reactivated {
+        /// The cloth entity
+        entity: Entity,
+    },
+    /// An anchored point was pulled harder than its
+    /// [`VertexAnchor::max_force`] and released.
+    ///
+    /// [`VertexAnchor::max_force`]: crate::vertex_anchor::VertexAnchor::max_force
+    AnchorReleased {
+        /// The cloth entity
+        entity: Entity,
+        /// The released point id
+        point: usize,
+    },
+}
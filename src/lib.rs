@@ -133,16 +133,16 @@
 //!
 //!     let cloth = ClothBuilder::new()
 //!         // Adds pinned vertices ids using an Iterator
-//!         .with_anchored_vertex_ids(0..9, anchor_to_a)
+//!         .with_anchored_vertex_ids(0..9, anchor_to_a.clone())
 //!         // Adds a single pinned vertex id
-//!         .with_anchored_vertex_id(10, anchor_to_self)
+//!         .with_anchored_vertex_id(10, anchor_to_self.clone())
 //!         // Adds pinned vertex colors using an Iterator
 //!         .with_anchored_vertex_colors(
 //!             [Color::from(WHITE), Color::from(BLACK)].into_iter(),
 //!             anchor_to_a,
 //!         )
 //!         // Adds a single pinned vertex color
-//!         .with_anchored_vertex_color(Color::from(YELLOW), anchor_to_self)
+//!         .with_anchored_vertex_color(Color::from(YELLOW), anchor_to_self.clone())
 //!         // Adds pinned vertex positions
 //!         .with_anchored_vertex_positions(|pos| pos.x > 0.0 && pos.z <= 5.0, anchor_to_self);
 //! }
@@ -150,11 +150,16 @@
 //!
 //! Custom anchoring allows to :
 //!
-//! * pin vertices to various entities, like skeletal mesh joints
+//! * pin vertices to various entities, like skeletal mesh joints, or to a bone
+//!   found by name in a skeleton hierarchy with `VertexAnchor::bone_name`
 //! * define custom offsets to customize the distance between the anchored
 //!   vertices an the target
 //! * use world space pinning and ignore the target's rotation for example
 //! * override the vertex positions, using only the offset
+//! * make vertices ride along an animated `ClothPath` polyline, for example for
+//!   the top edge of a parade banner
+//! * pin vertices on some axes only with `VertexAnchor::constrain_axes`,
+//!   letting them slide freely on the others, like curtain rings on a rod
 //!
 //! ### Configuration
 //!
@@ -170,18 +175,105 @@
 //!     .add_plugins((DefaultPlugins, ClothPlugin))
 //!     .insert_resource(ClothConfig {
 //!         gravity: Vec3::new(0.0, -9.81, 0.0),
-//!         friction: 0.02,
+//!         velocity_damping: 0.02,
 //!         sticks_computation_depth: 5,
-//!         acceleration_smoothing: AccelerationSmoothing::default()
+//!         acceleration_smoothing: AccelerationSmoothing::default(),
+//!         ..Default::default()
 //!     })
 //!     // ... Add your resources and systems
 //!     .run();
 //! }
 //! ```
 //!
+//! `ClothConfig::velocity_damping` (previously `friction`) controls how quickly
+//! the cloth motion dies out, while the sticks control the cloth shape
+//! rigidity.
+//!
 //! `ClothConfig` can also be used as a *component* to override the global
 //! configuration.
 //!
+//! ### Parallel update
+//!
+//! Enable the `parallel` feature to update every cloth on its own thread.
+//! Cloths are independent, making this ideal for scenes with many small cloths.
+//! The `cloth_systems` benchmark compares both modes on 50 flags: run it with
+//! `--features testing` and `--features testing,parallel`.
+//!
+//! ### Distance culling
+//!
+//! Add a `ClothCullDistance` component to a cloth entity to freeze it in its
+//! last pose when it is farther than a distance from the nearest active camera.
+//! Its simulation, mesh update and collisions are skipped entirely until a
+//! camera comes back within the distance, minus a hysteresis margin:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_silk::prelude::*;
+//! # fn spawn(mut commands: Commands, cloth: Entity) {
+//! commands
+//!     .entity(cloth)
+//!     .insert(ClothCullDistance::new(50.0).with_hysteresis(5.0));
+//! # }
+//! ```
+//!
+//! ### Statistics
+//!
+//! The `ClothStats` resource holds the number of cloths, points and sticks
+//! simulated, updated every frame. Enable the `stats_timing` feature to also
+//! measure the approximate wall-clock time spent in the cloth update and
+//! render systems.
+//!
+//! ### Diagnostics
+//!
+//! Enable the `diagnostics` feature to log a warning when a cloth is
+//! initialized or rebuilt with a common setup mistake:
+//!
+//! * *no anchored vertices*: the cloth has no pinned nor spring anchored
+//!   vertices and no `ClothConfig::floor_height`, it will fall indefinitely
+//!   unless it lands on colliders.
+//! * *non uniform scale*: the cloth entity scale differs between axes, the
+//!   cloth only follows uniform scale changes. Prefer scaling the mesh itself.
+//!
+//! Missing mesh attributes read by the `ClothBuilder` pins, like the
+//! `Vertex_Color` attribute for color pinning or the UV channel of the UV
+//! region anchors, are always reported: the matching pins are ignored.
+//!
+//! ### System ordering
+//!
+//! The cloth systems run in `PostUpdate`, after the transform propagation, so
+//! anchored points follow the final `GlobalTransform` of their anchors without
+//! a frame of lag, including animated skeleton joints. The systems are grouped
+//! in the `ClothSystems` sets, allowing to order custom systems relative to
+//! the cloth simulation:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_silk::prelude::*;
+//!
+//! fn read_cloth_points(query: Query<&bevy_silk::components::cloth::Cloth>) {}
+//!
+//! fn setup(app: &mut App) {
+//!     app.add_systems(PostUpdate, read_cloth_points.after(ClothSystems::Update));
+//! }
+//! ```
+//!
+//! Systems moving cloth anchors should run before the transform propagation,
+//! in `Update` or in `PostUpdate` before `TransformSystem::TransformPropagate`.
+//!
+//! `ClothFollower` meshes are deformed in `ClothSystems::Render`, after their
+//! primary cloth update: a follower always matches the current frame
+//! simulation, but its own `GlobalTransform` must be propagated before, so
+//! don't move follower entities in `PostUpdate` after the transform
+//! propagation.
+//!
+//! The cloth meshes are fully rewritten in `ClothSystems::Render`, before the
+//! visibility checks and before the `Last` schedule where the mesh
+//! modifications are flushed as asset events. The render world extracts a
+//! complete copy of every modified mesh once the main schedule is over, so it
+//! never reads a partially updated cloth mesh and no double buffering is
+//! required. Custom systems editing cloth meshes should run in `PostUpdate`
+//! too, after `ClothSystems::Render`, to be extracted on the same frame.
+//!
 //! ## Wind
 //!
 //! You may add wind forces to the simulation for a more dynamic clothing
@@ -190,6 +282,8 @@
 //! * `Wind::Constant` for constant wind force
 //! * `Wind::SinWave` for a sin wave following wind intensity with custom force
 //!   and frequency.
+//! * `Wind::FlowField` for a spatially varying wind sampled from a flow texture
+//!   tiled over the world XZ plane.
 //!
 //! `Wind` forces can be added as a resource to your app through the `Winds`
 //! container:
@@ -207,7 +301,8 @@
 //!             frequency: 3.0,
 //!             normalize: false,
 //!             abs: false
-//!         }]
+//!         }],
+//!         max_magnitude: None,
 //!     })
 //!     // ... Add your resources and systems
 //!     .run();
@@ -225,6 +320,17 @@
 //! > Note: Collision support is still experimental for now and is not suited
 //! > for production use. Feedback is welcome!
 //!
+//! Add a `ClothTouching` component to a cloth entity to track the entities of
+//! the colliders it is currently in contact with, for gameplay logic like a
+//! cloth covering an altar. It is refreshed in `FixedUpdate` alongside the
+//! collision handling.
+//!
+//! Heightfield colliders, like terrains, are sampled directly below each cloth
+//! point instead of using a general point projection, which is much cheaper on
+//! large heightfields. This assumes an upward heightfield: the cloth points are
+//! only pushed up along the Y axis. Heightfields with a rotated up axis fall
+//! back to the point projection.
+//!
 //! ### `bevy_rapier`
 //!
 //! Add `bevy_rapier3d::RapierPhysicsPlugin` to your app and a `ClothCollider`
@@ -286,6 +392,70 @@
 //! You can customize what collisions will be checked by specifying
 //! `CollisionLayers`. (See the [`avian` docs](https://docs.rs/avian3d/latest/avian3d/collision/struct.CollisionLayers.html)).
 //!
+//! ## Manual cloth data
+//!
+//! The cloth simulation doesn't require a mesh: use
+//! `ClothBuilder::from_points` to define explicit point positions and sticks
+//! instead. No mesh is updated, the simulated world space points are
+//! available in `Cloth::current_point_positions` for custom rendering (e.g
+//! instanced quads):
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_silk::prelude::*;
+//!
+//! fn spawn(mut commands: Commands) {
+//!     let points = (0..10).map(|i| Vec3::Y * -(i as f32)).collect();
+//!     let sticks = (0..9).map(|i| [i, i + 1]).collect();
+//!     commands.spawn((
+//!         Transform::default(),
+//!         ClothBuilder::from_points(points, sticks).with_pinned_vertex_id(0),
+//!     ));
+//! }
+//! ```
+//!
+//! ## Custom solvers
+//!
+//! The stick graph is exposed to run your own constraint solver (e.g. XPBD)
+//! while reusing the anchoring and mesh update of the crate. Set
+//! `ClothConfig::sticks_computation_depth` to 0 to disable the built-in stick
+//! solver, then solve the sticks from `Cloth::iter_sticks` in a system ordered
+//! between `ClothSystems::Update` and `ClothSystems::Render`, and write the
+//! result back with `Cloth::apply_solved_positions`:
+//!
+//! ```rust no_run
+//! use bevy::prelude::*;
+//! use bevy_silk::{components::cloth::Cloth, prelude::*};
+//!
+//! fn solve(mut query: Query<&mut Cloth>) {
+//!     for mut cloth in &mut query {
+//!         let mut positions = cloth.current_point_positions.clone();
+//!         for ([a, b], len, mode) in cloth.iter_sticks() {
+//!             // Solve the stick constraint on `positions`
+//!         }
+//!         cloth.apply_solved_positions(&positions);
+//!     }
+//! }
+//!
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((DefaultPlugins, ClothPlugin))
+//!         .insert_resource(ClothConfig {
+//!             sticks_computation_depth: 0,
+//!             ..Default::default()
+//!         })
+//!         .add_systems(
+//!             PostUpdate,
+//!             solve
+//!                 .after(ClothSystems::Update)
+//!                 .before(ClothSystems::Render),
+//!         );
+//! }
+//! ```
+//!
+//! Only `Cloth::current_point_positions` should be modified: the previous
+//! positions are kept so the solver displacement carries over as velocity.
+//!
 //! ## Mesh utils
 //!
 //! `bevy_silk` provides a plane mesh generation function `rectangle_mesh`
@@ -333,12 +503,21 @@ pub mod components;
 pub mod config;
 /// error module
 pub mod error;
+/// event module
+pub mod event;
 /// mesh module
 pub mod mesh;
+/// point grid module
+pub(crate) mod point_grid;
+/// statistics module
+pub mod stats;
 /// stick module
 pub mod stick;
 /// systems module
 mod systems;
+/// headless test utilities module
+#[cfg(any(test, feature = "testing"))]
+pub mod test_util;
 /// vertex anchor module
 pub mod vertex_anchor;
 /// wind module
@@ -350,46 +529,190 @@ use bevy::prelude::*;
 /// Prelude module, providing every public type of the lib
 pub mod prelude {
     #[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
-    pub use crate::components::collider::ClothCollider;
+    pub use crate::components::collider::{ClothCollider, ClothTouching, LiveShape};
     pub use crate::{
-        components::{cloth_builder::ClothBuilder, cloth_rendering::NormalComputing},
-        config::{AccelerationSmoothing, ClothConfig},
+        components::{
+            cloth_builder::{ClothBuilder, ColorChannel},
+            cloth_cull::ClothCullDistance,
+            cloth_follower::ClothFollower,
+            cloth_path::ClothPath,
+            cloth_rendering::{NormalComputing, UvChannel},
+            cloth_skin::ClothSkin,
+        },
+        config::{
+            AccelerationSmoothing, ClothConfig, GravityOverride, SolveOrder,
+            VelocityDampingOverride,
+        },
         error::Error,
-        mesh::rectangle_mesh,
+        event::ClothEvent,
+        mesh::{grid_mesh_with_mask, rectangle_mesh, rectangle_quads},
+        stats::ClothStats,
         stick::{StickGeneration, StickLen, StickMode},
-        vertex_anchor::VertexAnchor,
-        wind::{Wind, Winds},
-        ClothPlugin,
+        vertex_anchor::{AnchorRegion, AnchorUvRegion, PinGradient, VertexAnchor},
+        wind::{Wind, WindReadout, Winds},
+        ClothPlugin, ClothSystems,
     };
 }
 
+/// Cloth system sets, allowing to order custom systems relative to the cloth
+/// systems.
+///
+/// [`Self::Init`], [`Self::Update`] and [`Self::Render`] run in order in the
+/// `PostUpdate` schedule after the transform propagation, and before the
+/// visibility checks and the render world extraction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub enum ClothSystems {
+    /// Cloth initialization from the [`ClothBuilder`] components
+    Init,
+    /// Cloth simulation step and events
+    Update,
+    /// Cloth mesh update, including the skins and followers meshes
+    Render,
+    /// Cloth collisions, solved in the `FixedUpdate` schedule. The collider
+    /// initialization runs in the `Update` schedule. The collisions with the
+    /// physics backend colliders are skipped until its plugin is set up.
+    Collisions,
+}
+
 /// Plugin for cloth physics
 #[derive(Copy, Clone, Default)]
 pub struct ClothPlugin;
 
 impl Plugin for ClothPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ClothConfig>();
+        app.init_resource::<ClothConfig>()
+            .init_resource::<ClothStats>();
+        app.add_event::<ClothEvent>();
         app.register_type::<ClothConfig>()
+            .register_type::<GravityOverride>()
+            .register_type::<VelocityDampingOverride>()
             .register_type::<Wind>()
             .register_type::<Winds>()
-            .register_type::<ClothBuilder>();
+            .register_type::<WindReadout>()
+            .register_type::<ClothBuilder>()
+            .register_type::<ClothPath>()
+            .register_type::<ClothCullDistance>()
+            .register_type::<ClothFollower>()
+            .register_type::<ClothSkin>()
+            .register_type::<ClothStats>()
+            .register_type::<ColorChannel>()
+            .register_type::<UvChannel>()
+            .register_type::<SolveOrder>()
+            .register_type::<StickGeneration>()
+            .register_type::<StickLen>()
+            .register_type::<StickMode>()
+            .register_type::<VertexAnchor>()
+            .register_type::<AnchorRegion>()
+            .register_type::<AnchorUvRegion>()
+            .register_type::<PinGradient>();
+        app.configure_sets(
+            PostUpdate,
+            (
+                ClothSystems::Init,
+                ClothSystems::Update,
+                ClothSystems::Render,
+            )
+                .chain()
+                .after(bevy::transform::TransformSystem::TransformPropagate)
+                .before(bevy::render::view::VisibilitySystems::CheckVisibility),
+        );
         app.add_systems(
-            Update,
+            PostUpdate,
             (
-                systems::cloth::init,
-                (systems::cloth::update, systems::cloth::render).chain(),
+                (systems::cloth::init, systems::cloth::resolve_bones)
+                    .chain()
+                    .in_set(ClothSystems::Init),
+                (
+                    systems::cloth::cull,
+                    systems::cloth::update,
+                    systems::cloth::send_events,
+                    systems::stats::count_cloths,
+                )
+                    .chain()
+                    .in_set(ClothSystems::Update),
+                (
+                    systems::cloth::render,
+                    systems::cloth::render_skins,
+                    systems::cloth::render_followers,
+                )
+                    .chain()
+                    .in_set(ClothSystems::Render),
             ),
         );
-
-        #[cfg(feature = "rapier_collisions")]
-        app.register_type::<ClothCollider>()
-            .add_systems(Update, systems::collisions::rapier::init_cloth_collider)
-            .add_systems(FixedUpdate, systems::collisions::rapier::handle_collisions);
-        #[cfg(feature = "avian_collisions")]
-        app.register_type::<ClothCollider>()
-            .add_systems(Update, systems::collisions::avian::init_cloth_collider)
-            .add_systems(FixedUpdate, systems::collisions::avian::handle_collisions);
+        #[cfg(feature = "stats_timing")]
+        add_stats_timing(app);
+        #[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+        add_collisions(app);
         bevy::log::info!("Loaded Cloth Plugin");
     }
 }
+
+/// Registers the cloth collision types and systems of the enabled physics
+/// backend
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+fn add_collisions(app: &mut App) {
+    #[cfg(feature = "rapier_collisions")]
+    app.register_type::<ClothCollider>()
+        .register_type::<ClothTouching>()
+        .register_type::<LiveShape>()
+        .add_systems(
+            Update,
+            (
+                systems::collisions::rapier::init_cloth_collider,
+                systems::collisions::rapier::remove_disabled_cloth_collider,
+            )
+                .in_set(ClothSystems::Collisions),
+        )
+        .add_systems(
+            FixedUpdate,
+            systems::collisions::rapier::handle_collisions
+                .run_if(any_with_component::<bevy_rapier3d::prelude::DefaultRapierContext>)
+                .in_set(ClothSystems::Collisions),
+        );
+    #[cfg(feature = "avian_collisions")]
+    app.register_type::<ClothCollider>()
+        .register_type::<ClothTouching>()
+        .register_type::<LiveShape>()
+        .add_systems(
+            Update,
+            (
+                systems::collisions::avian::init_cloth_collider,
+                systems::collisions::avian::remove_disabled_cloth_collider,
+            )
+                .in_set(ClothSystems::Collisions),
+        )
+        .add_systems(
+            FixedUpdate,
+            systems::collisions::avian::handle_collisions
+                .run_if(resource_exists::<avian3d::prelude::Collisions>)
+                .in_set(ClothSystems::Collisions),
+        );
+    app.add_systems(
+        FixedUpdate,
+        systems::collisions::handle_cloth_collisions.in_set(ClothSystems::Collisions),
+    );
+}
+
+/// Registers the [`ClothStats`] timing systems around the cloth update and
+/// render systems
+#[cfg(feature = "stats_timing")]
+fn add_stats_timing(app: &mut App) {
+    app.init_resource::<systems::stats::StatsTimer>()
+        .add_systems(
+            PostUpdate,
+            (
+                systems::stats::start_timer
+                    .before(systems::cloth::update)
+                    .in_set(ClothSystems::Update),
+                systems::stats::stop_update_timer
+                    .after(systems::cloth::send_events)
+                    .in_set(ClothSystems::Update),
+                systems::stats::start_timer
+                    .before(systems::cloth::render)
+                    .in_set(ClothSystems::Render),
+                systems::stats::stop_render_timer
+                    .after(systems::cloth::render_followers)
+                    .in_set(ClothSystems::Render),
+            ),
+        );
+}
@@ -1,11 +1,12 @@
 // TODO: mesh generation utils
 
 use bevy::{
-    math::Vec3,
+    math::{Vec2, Vec3},
     render::{
         mesh::{Indices, Mesh, PrimitiveTopology},
         render_asset::RenderAssetUsages,
     },
+    utils::HashMap,
 };
 
 #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
@@ -71,6 +72,178 @@ pub fn rectangle_mesh(
     .with_inserted_indices(Indices::U32(indices))
 }
 
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+#[must_use]
+/// Creates a cloth ready grid mesh with authored holes, like a lattice banner
+///
+/// The grid is made of `size_x` by `size_y` vertices, and each cell between
+/// four vertices is made of two triangles unless it is masked. Vertices only
+/// belonging to masked cells are not included, and the remaining ones keep the
+/// [`rectangle_mesh`] grid order and UVs.
+///
+/// The normal of every vertex is computed from the steps to match the front
+/// face of the triangles: `step_y.cross(step_x)`.
+///
+/// # Params
+///
+/// * `size_x` - the size of the cloth in the X axis (should be above 1)
+/// * `size_y` - the size of the cloth in the Y axis (should be above 1)
+/// * `step_x` - the direction of the cloth propagation in the X axis
+/// * `step_y` - the direction of the cloth propagation in the Y axis
+/// * `mask` - function returning `true` if the cell at the given `x` and `y`
+///   coordinates is a hole. The coordinates of a cell are the ones of its first
+///   vertex, up to `size_x - 2` and `size_y - 2`
+pub fn grid_mesh_with_mask(
+    (size_x, size_y): (usize, usize),
+    (step_x, step_y): (Vec3, Vec3),
+    mask: impl Fn(usize, usize) -> bool,
+) -> Mesh {
+    let cells: Vec<usize> = (0..size_y.saturating_sub(1))
+        .flat_map(|y| (0..size_x.saturating_sub(1)).map(move |x| (x, y)))
+        .filter(|(x, y)| !mask(*x, *y))
+        .map(|(x, y)| y * size_x + x)
+        .collect();
+    let mut used = vec![false; size_x * size_y];
+    for i in &cells {
+        for id in [*i, i + 1, i + size_x, i + size_x + 1] {
+            used[id] = true;
+        }
+    }
+    let mut vertex_ids = vec![0_u32; used.len()];
+    let mut points: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    for (i, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let (x, y) = (i % size_x, i / size_x);
+        vertex_ids[i] = points.len() as u32;
+        points.push((x as f32 * step_x + y as f32 * step_y).to_array());
+        uvs.push([x as f32 / size_x as f32, y as f32 / size_y as f32]);
+    }
+    let indices: Vec<u32> = cells
+        .into_iter()
+        .flat_map(|i| {
+            let [a, b, c, d] = [i + 1, i, i + size_x, i + size_x + 1].map(|id| vertex_ids[id]);
+            [a, b, c, c, d, a]
+        })
+        .collect();
+    let normal = step_y.cross(step_x).normalize_or_zero().to_array();
+    let normals: Vec<[f32; 3]> = vec![normal; points.len()];
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, points)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Creates the quads of a [`rectangle_mesh`] with the same `size`, matching
+/// its vertex ids and winding (See [`ClothBuilder::with_quads`])
+///
+/// # Params
+///
+/// * `size_x` - the size of the cloth in the X axis (should be above 1)
+/// * `size_y` - the size of the cloth in the Y axis (should be above 1)
+///
+/// [`ClothBuilder::with_quads`]: crate::components::cloth_builder::ClothBuilder::with_quads
+#[must_use]
+pub fn rectangle_quads((size_x, size_y): (usize, usize)) -> Vec<[usize; 4]> {
+    (0..size_y.saturating_sub(1))
+        .flat_map(|y| {
+            (0..size_x.saturating_sub(1)).map(move |x| {
+                let i = y * size_x + x;
+                [i + 1, i, i + size_x, i + size_x + 1]
+            })
+        })
+        .collect()
+}
+
+/// Retrieves the boundary vertices of a triangle list: the vertices of every
+/// edge belonging to a single triangle.
+///
+/// # Params
+///
+/// * `indices` - the triangle list indices
+///
+/// # Returns
+///
+/// The sorted boundary vertex ids
+#[must_use]
+pub fn boundary_vertices(indices: &[usize]) -> Vec<usize> {
+    let mut edges: HashMap<[usize; 2], usize> = HashMap::default();
+    for triangle in indices.chunks_exact(3) {
+        for (a, b) in [
+            (triangle[0], triangle[1]),
+            (triangle[1], triangle[2]),
+            (triangle[2], triangle[0]),
+        ] {
+            *edges.entry([a.min(b), a.max(b)]).or_default() += 1;
+        }
+    }
+    let mut vertices: Vec<usize> = edges
+        .into_iter()
+        .filter(|(_, count)| *count == 1)
+        .flat_map(|(edge, _)| edge)
+        .collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    vertices
+}
+
+/// Retrieves the four corner vertices of a planar mesh, from its bounds.
+///
+/// The cloth plane is defined by the two axes with the largest extent, `u`
+/// and `v` in X, Y, Z order (e.g. X and Z for a horizontal cloth). The corners
+/// are the vertices closest to the bounds corners on that plane, ordered as:
+///
+/// 0. min `u`, min `v`
+/// 1. max `u`, min `v`
+/// 2. min `u`, max `v`
+/// 3. max `u`, max `v`
+///
+/// # Params
+///
+/// * `positions` - the vertex positions
+///
+/// # Returns
+///
+/// The corner vertex ids, or `None` if `positions` is empty
+#[must_use]
+pub fn corner_vertices(positions: &[Vec3]) -> Option<[usize; 4]> {
+    if positions.is_empty() {
+        return None;
+    }
+    let (min, max) = positions
+        .iter()
+        .fold((Vec3::MAX, Vec3::MIN), |(min, max), p| {
+            (min.min(*p), max.max(*p))
+        });
+    let extent = max - min;
+    let flat_axis = (0..3)
+        .min_by(|a, b| extent[*a].total_cmp(&extent[*b]))
+        .unwrap_or(2);
+    let [u, v] = match flat_axis {
+        0 => [1, 2],
+        1 => [0, 2],
+        _ => [0, 1],
+    };
+    Some(std::array::from_fn(|corner| {
+        let target = Vec2::new(
+            if corner % 2 == 0 { min[u] } else { max[u] },
+            if corner < 2 { min[v] } else { max[v] },
+        );
+        positions
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let dist = |p: &Vec3| Vec2::new(p[u], p[v]).distance_squared(target);
+                dist(a).total_cmp(&dist(b))
+            })
+            .map_or(0, |(i, _)| i)
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +253,75 @@ mod tests {
         let mesh = rectangle_mesh((100, 100), (Vec3::X, -Vec3::Y), Vec3::Z);
         assert_eq!(mesh.count_vertices(), 100 * 100);
     }
+
+    #[test]
+    fn masked_grid_mesh() {
+        use crate::{
+            components::cloth::Cloth,
+            prelude::{StickGeneration, StickLen, StickMode},
+        };
+        use bevy::{math::Mat4, render::mesh::VertexAttributeValues};
+
+        // 4x4 cells with a hole in the middle and a missing corner
+        let mesh = grid_mesh_with_mask((5, 5), (Vec3::X, Vec3::Z), |x, y| {
+            (x, y) == (0, 0) || ((1..3).contains(&x) && (1..3).contains(&y))
+        });
+        // The first vertex and the vertex in the middle of the hole are dropped
+        assert_eq!(mesh.count_vertices(), 23);
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("Invalid indices");
+        };
+        assert_eq!(indices.len(), (16 - 5) * 6);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Invalid positions");
+        };
+        let positions: Vec<Vec3> = positions.iter().copied().map(Vec3::from).collect();
+        // No triangle lies in a masked cell
+        for triangle in indices.chunks_exact(3) {
+            let center = triangle
+                .iter()
+                .map(|i| positions[*i as usize])
+                .sum::<Vec3>()
+                / 3.0;
+            let (x, z) = (center.x.floor(), center.z.floor());
+            assert!((x, z) != (0.0, 0.0), "{center}");
+            assert!(
+                !((1.0..3.0).contains(&x) && (1.0..3.0).contains(&z)),
+                "{center}"
+            );
+        }
+        // The cloth stays in one piece around the hole
+        let cloth = Cloth::new(
+            &positions,
+            indices,
+            HashMap::default(),
+            StickGeneration::Quads,
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert_eq!(cloth.components().len(), 1);
+    }
+
+    #[test]
+    fn pinned_boundary() {
+        use crate::prelude::ClothBuilder;
+
+        let (size_x, size_y) = (5, 4);
+        let mesh = rectangle_mesh((size_x, size_y), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let anchored = ClothBuilder::new()
+            .with_pinned_boundary()
+            .anchored_vertex_ids(&mesh);
+        let mut anchored: Vec<usize> = anchored.into_keys().collect();
+        anchored.sort_unstable();
+        let perimeter: Vec<usize> = (0..size_x * size_y)
+            .filter(|i| {
+                let (x, y) = (i % size_x, i / size_x);
+                x == 0 || y == 0 || x == size_x - 1 || y == size_y - 1
+            })
+            .collect();
+        assert_eq!(anchored, perimeter);
+    }
 }
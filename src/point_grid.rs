@@ -0,0 +1,236 @@
+use bevy::{
+    math::{I64Vec3, IVec3, Vec3},
+    utils::HashMap,
+};
+
+/// Uniform grid spatial hash over a set of points, answering "points within
+/// radius" queries without comparing every pair of points.
+///
+/// The grid is meant to be built once per frame from the cloth point
+/// positions (See [`Cloth::current_point_positions`]) and shared by every
+/// feature requiring neighbor lookups. [`Self::rebuild`] reuses the
+/// allocations of the cells which are still occupied.
+///
+/// For best results the `cell_size` should be close to the most common query
+/// radius.
+///
+/// [`Cloth::current_point_positions`]: crate::components::cloth::Cloth::current_point_positions
+#[derive(Debug, Clone)]
+pub struct PointGrid {
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<(usize, Vec3)>>,
+    point_count: usize,
+}
+
+impl PointGrid {
+    /// Builds a new grid from `positions`
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - the points to bin, their index is used as id
+    /// * `cell_size` - the size of each grid cell, must be positive
+    #[must_use]
+    pub fn new(positions: &[Vec3], cell_size: f32) -> Self {
+        let mut grid = Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::default(),
+            point_count: 0,
+        };
+        grid.rebuild(positions);
+        grid
+    }
+
+    /// Builds a new grid from surface like `positions`, like cloth points,
+    /// with a cell size holding about one point per cell.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_surface(positions: &[Vec3]) -> Self {
+        let (min, max) = positions
+            .iter()
+            .fold((Vec3::MAX, Vec3::MIN), |(min, max), p| {
+                (min.min(*p), max.max(*p))
+            });
+        let extent = (max - min).max_element().max(0.0);
+        Self::new(positions, extent / (positions.len() as f32).sqrt())
+    }
+
+    /// Re-bins `positions`, reusing the allocations of the cells which are
+    /// still occupied. Cells left empty are removed.
+    pub fn rebuild(&mut self, positions: &[Vec3]) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        for (i, pos) in positions.iter().enumerate() {
+            self.cells
+                .entry(self.cell_coords(*pos))
+                .or_default()
+                .push((i, *pos));
+        }
+        self.cells.retain(|_, cell| !cell.is_empty());
+        self.point_count = positions.len();
+    }
+
+    /// Retrieves the number of binned points
+    #[inline]
+    #[must_use]
+    pub const fn point_count(&self) -> usize {
+        self.point_count
+    }
+
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn cell_coords(&self, pos: Vec3) -> IVec3 {
+        (pos / self.cell_size).floor().as_ivec3()
+    }
+
+    /// Retrieves the ids and positions of every point within `radius` of
+    /// `pos`
+    ///
+    /// Only the occupied cells are visited when the query covers more cells
+    /// than the grid holds.
+    pub fn query_radius(&self, pos: Vec3, radius: f32) -> impl Iterator<Item = (usize, Vec3)> + '_ {
+        let min = self.cell_coords(pos - radius);
+        let max = self.cell_coords(pos + radius);
+        let radius_squared = radius * radius;
+        let box_cells = (max.as_i64vec3() - min.as_i64vec3() + I64Vec3::ONE)
+            .max(I64Vec3::ZERO)
+            .to_array()
+            .into_iter()
+            .try_fold(1_i64, i64::checked_mul)
+            .unwrap_or(i64::MAX);
+        let walk_box = box_cells <= i64::try_from(self.cells.len()).unwrap_or(i64::MAX);
+        let box_walk = walk_box.then(|| {
+            (min.x..=max.x)
+                .flat_map(move |x| (min.y..=max.y).map(move |y| (x, y)))
+                .flat_map(move |(x, y)| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+                .filter_map(|coords| self.cells.get(&coords))
+        });
+        let cell_scan = (!walk_box).then(|| {
+            self.cells
+                .iter()
+                .filter(move |(coords, _)| coords.cmpge(min).all() && coords.cmple(max).all())
+                .map(|(_, cell)| cell)
+        });
+        box_walk
+            .into_iter()
+            .flatten()
+            .chain(cell_scan.into_iter().flatten())
+            .flatten()
+            .filter(move |(_, p)| p.distance_squared(pos) <= radius_squared)
+            .copied()
+    }
+
+    /// Retrieves the ids and distances of the `count` points nearest to
+    /// `pos`, sorted by distance.
+    ///
+    /// The query radius starts at the cell size and doubles until enough points
+    /// are found. A non finite `pos` returns no points, and non finite binned
+    /// points are never returned.
+    #[must_use]
+    pub fn query_nearest(&self, pos: Vec3, count: usize) -> Vec<(usize, f32)> {
+        let count = count.min(self.point_count);
+        if count == 0 || !pos.is_finite() {
+            return Vec::new();
+        }
+        let mut radius = self.cell_size;
+        loop {
+            let mut result: Vec<(usize, f32)> = self
+                .query_radius(pos, radius)
+                .map(|(i, p)| (i, p.distance(pos)))
+                .collect();
+            // An infinite radius covers every finite point
+            if result.len() >= count || !radius.is_finite() {
+                result.sort_unstable_by(|(_, a), (_, b)| a.total_cmp(b));
+                result.truncate(count);
+                return result;
+            }
+            radius *= 2.0;
+        }
+    }
+
+    /// Retrieves the ids and normalized inverse distance weights of the
+    /// `count` points nearest to `pos` (See [`Self::query_nearest`]).
+    ///
+    /// A position matching exactly a point is bound to that single point.
+    #[must_use]
+    pub fn nearest_weights(&self, pos: Vec3, count: usize) -> Vec<(usize, f32)> {
+        let distances = self.query_nearest(pos, count);
+        match distances.first() {
+            Some((i, d)) if *d <= f32::EPSILON => vec![(*i, 1.0)],
+            _ => {
+                let total: f32 = distances.iter().map(|(_, d)| d.recip()).sum();
+                distances
+                    .into_iter()
+                    .map(|(i, d)| (i, d.recip() / total))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_brute_force() {
+        let positions: Vec<Vec3> = (0..1000_u16)
+            .map(|i| {
+                let i = f32::from(i);
+                Vec3::new((i * 0.37).sin() * 10.0, (i * 0.11).cos() * 10.0, i * 0.01)
+            })
+            .collect();
+        let grid = PointGrid::new(&positions, 1.5);
+        for (center, radius) in [
+            (Vec3::ZERO, 2.0),
+            (Vec3::new(5.0, -3.0, 4.0), 3.5),
+            // Covers more cells than the grid holds
+            (Vec3::ZERO, 100.0),
+        ] {
+            let mut result: Vec<usize> =
+                grid.query_radius(center, radius).map(|(i, _)| i).collect();
+            result.sort_unstable();
+            let expected: Vec<usize> = positions
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.distance_squared(center) <= radius * radius)
+                .map(|(i, _)| i)
+                .collect();
+            assert_eq!(result, expected);
+        }
+        let nearest = grid.query_nearest(Vec3::new(2.0, 1.0, 3.0), 4);
+        let mut expected: Vec<(usize, f32)> = positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.distance(Vec3::new(2.0, 1.0, 3.0))))
+            .collect();
+        expected.sort_unstable_by(|(_, a), (_, b)| a.total_cmp(b));
+        expected.truncate(4);
+        assert_eq!(nearest, expected);
+    }
+
+    #[test]
+    fn nearest_non_finite() {
+        let grid = PointGrid::new(&[Vec3::ZERO, Vec3::NAN, Vec3::X], 1.0);
+        assert!(grid.query_nearest(Vec3::NAN, 2).is_empty());
+        assert!(grid.query_nearest(Vec3::INFINITY, 2).is_empty());
+        // The NaN point is never found
+        let nearest: Vec<usize> = grid
+            .query_nearest(Vec3::ZERO, 3)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(nearest, [0, 2]);
+    }
+
+    #[test]
+    fn rebuild_removes_empty_cells() {
+        let mut grid = PointGrid::new(&[Vec3::ZERO, Vec3::X * 10.0], 1.0);
+        assert_eq!(grid.cells.len(), 2);
+        for i in 1..100_u8 {
+            let offset = Vec3::Y * f32::from(i) * 5.0;
+            grid.rebuild(&[offset, Vec3::X * 10.0 + offset]);
+            assert_eq!(grid.cells.len(), 2);
+        }
+    }
+}
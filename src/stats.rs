@@ -0,0 +1,41 @@
+use bevy::{
+    ecs::{reflect::ReflectResource, system::Resource},
+    reflect::Reflect,
+};
+use std::time::Duration;
+
+/// Aggregated cloth simulation statistics, updated every frame.
+///
+/// Allows to display the cloth simulation complexity in performance
+/// dashboards or inspectors, and to spot runaway cloth complexity in big
+/// scenes.
+///
+/// The counts include every cloth entity, paused cloths included (See
+/// [`ClothConfig::pause_when_invisible`]).
+///
+/// # Timing
+///
+/// The system durations are only measured with the `stats_timing` feature,
+/// and are zero otherwise. They are approximate wall-clock durations of the
+/// cloth systems, measured from systems running before and after them, which
+/// can include some scheduling overhead.
+///
+/// [`ClothConfig::pause_when_invisible`]: crate::config::ClothConfig::pause_when_invisible
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct ClothStats {
+    /// Number of cloth entities
+    pub cloths: usize,
+    /// Total number of cloth points
+    pub points: usize,
+    /// Total number of cloth sticks
+    pub sticks: usize,
+    /// Time spent in the [`ClothSystems::Update`] systems on the last frame
+    ///
+    /// [`ClothSystems::Update`]: crate::ClothSystems::Update
+    pub update_time: Duration,
+    /// Time spent in the [`ClothSystems::Render`] systems on the last frame
+    ///
+    /// [`ClothSystems::Render`]: crate::ClothSystems::Render
+    pub render_time: Duration,
+}
@@ -1,17 +1,42 @@
-use bevy::{math::Vec3, reflect::Reflect};
+use crate::components::cloth::StickId;
+use bevy::{math::Vec3, prelude::ReflectDefault, reflect::Reflect};
 
 /// Defines how the cloth will compute sticks from mesh indices.
-#[derive(Debug, Copy, Clone, Default, Reflect, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, Default, Reflect, PartialEq)]
+#[reflect(Default)]
 pub enum StickGeneration {
     #[default]
     /// 2 sticks will be generated by triangle, following the actual quad edges
     Quads,
     /// 3 sticks will be generated by triangle
     Triangles,
+    /// Structural, shear and bend sticks will be generated, producing the
+    /// most believable cloth at a higher cost:
+    ///
+    /// * structural sticks follow the quad edges, like [`Self::Quads`]
+    /// * shear sticks follow both diagonals of every quad
+    /// * bend sticks connect points two cells apart
+    ///
+    /// The shear and bend sticks use their own stiffness (See
+    /// [`Cloth::stick_stiffness`]), structural sticks are fully stiff.
+    ///
+    /// Note: The quads and cells are detected from the mesh triangles, this
+    /// mode works best with grid-like meshes made of quads split in two
+    /// triangles, like the ones generated by [`rectangle_mesh`]
+    ///
+    /// [`Cloth::stick_stiffness`]: crate::components::cloth::Cloth::stick_stiffness
+    /// [`rectangle_mesh`]: crate::mesh::rectangle_mesh
+    Full {
+        /// Stiffness of the shear sticks, between 0.0 and 1.0
+        shear_stiffness: f32,
+        /// Stiffness of the bend sticks, between 0.0 and 1.0
+        bend_stiffness: f32,
+    },
 }
 
 /// Defines the target length of cloth sticks
 #[derive(Debug, Copy, Clone, Default, Reflect)]
+#[reflect(Default)]
 pub enum StickLen {
     #[default]
     /// The target length will be the actual distance between the vertices
@@ -22,10 +47,21 @@ pub enum StickLen {
     Offset(f32),
     /// Same as [`StickLen::Auto`] with a custom coefficient
     Coefficient(f32),
+    /// Same as [`StickLen::Auto`] multiplied by a per stick pseudo random
+    /// factor between `1.0 - amount` and `1.0 + amount`, giving a subtle
+    /// irregularity to natural fabrics or cobwebs.
+    ///
+    /// The factor is deterministic, computed from a hash of the stick point
+    /// ids: the same stick always gets the same length.
+    AutoJittered {
+        /// Maximum relative length variation, between 0.0 and 1.0
+        amount: f32,
+    },
 }
 
 /// Defines cloth stick behaviour
 #[derive(Debug, Copy, Clone, Default, Reflect)]
+#[reflect(Default)]
 pub enum StickMode {
     /// The stick will attempt to always remain at the same length (See
     /// [`StickLen`]). This is the default behaviour and the fastest to
@@ -41,6 +77,7 @@ pub enum StickMode {
     /// let mode = StickMode::Spring {
     ///     min_percent: 0.0,
     ///     max_percent: 1.0,
+    ///     damping: 0.0,
     /// };
     /// ```
     /// will behave like a [`StickMode::Fixed`].
@@ -68,20 +105,75 @@ pub enum StickMode {
         /// The stick will attempt to be at most this percent of its expected
         /// length.
         max_percent: f32,
+        /// Ratio of the correction held back on every sticks computation
+        /// iteration, between 0.0 and 1.0:
+        /// - 0 meaning the stick snaps back to its clamped length (default)
+        /// - above 0 meaning the stick eases toward its clamped length over
+        ///   several iterations
+        ///
+        /// Tight `max_percent` values with large corrections can overshoot
+        /// and oscillate at low [`ClothConfig::sticks_computation_depth`],
+        /// a damping around 0.3 to 0.5 stabilizes them.
+        ///
+        /// [`ClothConfig::sticks_computation_depth`]: crate::config::ClothConfig::sticks_computation_depth
+        damping: f32,
     },
 }
 
 impl StickLen {
     /// Retrieves the stick length from the two points it connects
+    ///
+    /// [`Self::AutoJittered`] lengths depend on the stick point ids and are
+    /// not jittered, use [`Self::get_stick_len`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_a` - the position of the first stick point
+    /// * `point_b` - the position of the second stick point
     #[must_use]
     pub fn get_len(&self, point_a: Vec3, point_b: Vec3) -> f32 {
         match self {
-            Self::Auto => point_a.distance(point_b),
+            Self::Auto | Self::AutoJittered { .. } => point_a.distance(point_b),
             Self::Fixed(v) => *v,
             Self::Offset(offset) => point_a.distance(point_b) + offset,
             Self::Coefficient(coeff) => point_a.distance(point_b) * coeff,
         }
     }
+
+    /// Retrieves the length of the `stick_id` stick from the two points it
+    /// connects, including the [`Self::AutoJittered`] variation
+    ///
+    /// # Arguments
+    ///
+    /// * `stick_id` - the stick point ids
+    /// * `point_a` - the position of the first stick point
+    /// * `point_b` - the position of the second stick point
+    #[must_use]
+    pub fn get_stick_len(&self, stick_id: StickId, point_a: Vec3, point_b: Vec3) -> f32 {
+        let len = self.get_len(point_a, point_b);
+        match self {
+            Self::AutoJittered { amount } => {
+                len * Self::stick_hash(stick_id)
+                    .mul_add(2.0, -1.0)
+                    .mul_add(*amount, 1.0)
+            }
+            _ => len,
+        }
+    }
+
+    /// Hashes the stick point ids, regardless of their order, into a value
+    /// between 0.0 and 1.0
+    #[allow(clippy::cast_precision_loss)]
+    fn stick_hash([a, b]: StickId) -> f32 {
+        // SplitMix64 finalizer
+        let mut hash =
+            ((a.min(b) as u64) << 32 | a.max(b) as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash ^= hash >> 31;
+        // Keep the 24 most significant bits, exactly representable as `f32`
+        (hash >> 40) as f32 / (1_u64 << 24) as f32
+    }
 }
 
 impl From<[f32; 2]> for StickMode {
@@ -89,6 +181,7 @@ impl From<[f32; 2]> for StickMode {
         Self::Spring {
             min_percent: min,
             max_percent: max,
+            damping: 0.0,
         }
     }
 }
@@ -98,6 +191,31 @@ impl From<(f32, f32)> for StickMode {
         Self::Spring {
             min_percent: min,
             max_percent: max,
+            damping: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_jittered_len() {
+        let amount = 0.2;
+        let stick_len = StickLen::AutoJittered { amount };
+        let lengths: Vec<f32> = (0..100)
+            .map(|i| stick_len.get_stick_len([i, i + 1], Vec3::ZERO, Vec3::X * 2.0))
+            .collect();
+        for (i, len) in lengths.iter().enumerate() {
+            assert!((2.0 * (1.0 - amount)..=2.0 * (1.0 + amount)).contains(len));
+            // Reproducible, regardless of the point order
+            assert!(
+                (stick_len.get_stick_len([i + 1, i], Vec3::ZERO, Vec3::X * 2.0) - len).abs()
+                    < f32::EPSILON
+            );
         }
+        // The lengths vary
+        assert!(lengths.windows(2).any(|w| (w[0] - w[1]).abs() > 0.01));
     }
 }
@@ -4,36 +4,227 @@
     clippy::option_if_let_else
 )]
 use crate::{
-    components::{cloth::Cloth, cloth_builder::ClothBuilder, cloth_rendering::ClothRendering},
-    config::ClothConfig,
-    wind::Winds,
+    components::{
+        cloth::Cloth,
+        cloth_builder::ClothBuilder,
+        cloth_cull::ClothCullDistance,
+        cloth_follower::ClothFollower,
+        cloth_path::ClothPath,
+        cloth_rendering::{ClothRendering, NormalComputing},
+        cloth_skin::ClothSkin,
+    },
+    config::{ClothConfig, GravityOverride, VelocityDampingOverride},
+    error::Error,
+    event::ClothEvent,
+    systems::collisions::{project_box_point, TargetColliders},
+    wind::{Wind, WindReadout, Winds},
 };
-use bevy::{log, math::Vec3, prelude::*, render::primitives::Aabb};
+use bevy::{
+    log,
+    math::Vec3,
+    prelude::*,
+    render::{
+        mesh::{Indices, MeshVertexAttribute, VertexAttributeValues},
+        primitives::Aabb,
+        render_resource::PrimitiveTopology,
+    },
+};
+use std::time::Duration;
+
+pub fn cull(
+    mut query: Query<
+        (
+            Entity,
+            &mut ClothCullDistance,
+            &GlobalTransform,
+            Option<&Aabb>,
+        ),
+        With<Cloth>,
+    >,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    for (entity, mut cull, transform, aabb) in &mut query {
+        let center = aabb.map_or_else(
+            || transform.translation(),
+            |aabb| transform.transform_point(aabb.center.into()),
+        );
+        let camera_distance = camera_query
+            .iter()
+            .filter(|(camera, _)| camera.is_active)
+            .map(|(_, camera_transform)| camera_transform.translation().distance(center))
+            .reduce(f32::min);
+        if cull
+            .bypass_change_detection()
+            .update_culled(camera_distance)
+        {
+            cull.set_changed();
+            log::debug!("Cloth {:?} culled state set to {}", entity, cull.culled);
+        }
+    }
+}
 
+#[allow(clippy::too_many_lines)]
 pub fn update(
-    mut query: Query<(&mut Cloth, &GlobalTransform, Option<&ClothConfig>)>,
+    mut query: Query<(
+        &mut Cloth,
+        &GlobalTransform,
+        Option<&ClothConfig>,
+        (Option<&GravityOverride>, Option<&VelocityDampingOverride>),
+        (Option<&ViewVisibility>, Option<&ClothCullDistance>),
+        Option<&Aabb>,
+        Option<&mut WindReadout>,
+    )>,
     anchor_query: Query<&GlobalTransform, Without<Cloth>>,
+    path_query: Query<&ClothPath>,
     config: Res<ClothConfig>,
     wind: Option<Res<Winds>>,
-    time: Res<Time>,
+    images: Res<Assets<Image>>,
+    (time, fixed_time, mut last_fixed_elapsed): (Res<Time>, Res<Time<Fixed>>, Local<Duration>),
 ) {
     let delta_time = time.delta_secs();
-    let wind_force = wind.map_or(Vec3::ZERO, |w| w.current_velocity(time.elapsed_secs()));
-    for (mut cloth, transform, custom_config) in &mut query {
+    let fixed_delta = fixed_time.timestep();
+    let fixed_steps = elapsed_fixed_steps(&fixed_time, &mut last_fixed_elapsed);
+    // Flow field textures are resolved once and sampled for every cloth point
+    let flow_fields: Vec<(&Image, f32, f32)> = wind
+        .as_ref()
+        .into_iter()
+        .flat_map(|w| w.flow_fields())
+        .filter_map(|(handle, world_scale, strength)| {
+            images
+                .get(handle)
+                .map(|image| (image, world_scale, strength))
+        })
+        .collect();
+    let flow_wind = |point: Vec3| {
+        flow_fields
+            .iter()
+            .map(|(image, world_scale, strength)| {
+                Wind::sample_flow_field(image, *world_scale, *strength, point)
+            })
+            .sum::<Vec3>()
+    };
+    // The summed wind of every point is clamped (See `Winds::max_magnitude`)
+    let point_wind = |uniform: Vec3, point: Vec3| {
+        let velocity = uniform + flow_wind(point);
+        wind.as_ref()
+            .map_or(velocity, |w| w.clamp_magnitude(velocity))
+    };
+    let update_cloth = |(
+        mut cloth,
+        transform,
+        custom_config,
+        (gravity, damping),
+        (visibility, cull),
+        aabb,
+        readout,
+    ): (
+        Mut<Cloth>,
+        &GlobalTransform,
+        Option<&ClothConfig>,
+        (Option<&GravityOverride>, Option<&VelocityDampingOverride>),
+        (Option<&ViewVisibility>, Option<&ClothCullDistance>),
+        Option<&Aabb>,
+        Option<Mut<WindReadout>>,
+    )| {
+        let config = custom_config
+            .unwrap_or(&config)
+            .with_overrides(gravity, damping);
+        let config: &ClothConfig = &config;
+        if config.is_paused(visibility) || cull.is_some_and(|c| c.culled) {
+            return;
+        }
+        cloth.follow_scale(transform.scale());
+        let (steps, step_delta) = if config.fixed_timestep {
+            (fixed_steps, fixed_delta.as_secs_f32())
+        } else {
+            (1, delta_time)
+        };
+        let mut readout = readout;
+        for _ in 0..steps {
+            cloth.sim_time += step_delta;
+            let wind_force = match &wind {
+                Some(w) if !config.external_forces_only => {
+                    w.uniform_velocity(cloth.sim_time + cloth.phase_offset + config.phase_offset)
+                }
+                _ => Vec3::ZERO,
+            };
+            if let Some(readout) = readout.as_mut() {
+                let center = aabb.map_or_else(
+                    || transform.translation(),
+                    |aabb| transform.transform_point(aabb.center.into()),
+                );
+                readout.0 = if config.external_forces_only {
+                    Vec3::ZERO
+                } else {
+                    point_wind(wind_force, center)
+                };
+            }
+            cloth.step(
+                config,
+                |point| point_wind(wind_force, point),
+                step_delta,
+                transform,
+                |entity| {
+                    if let Ok(t) = anchor_query.get(entity) {
+                        Some(t)
+                    } else {
+                        log::error!("Could not find cloth anchor target entity {:?}", entity);
+                        None
+                    }
+                },
+                |entity| {
+                    if let Ok(p) = path_query.get(entity) {
+                        Some(p)
+                    } else {
+                        log::error!("Could not find cloth anchor path on entity {:?}", entity);
+                        None
+                    }
+                },
+            );
+        }
+    };
+    // Cloths are independent, each one can be updated on its own thread
+    #[cfg(feature = "parallel")]
+    query.par_iter_mut().for_each(update_cloth);
+    #[cfg(not(feature = "parallel"))]
+    query.iter_mut().for_each(update_cloth);
+}
+
+/// Retrieves the number of fixed steps elapsed since `last_elapsed`, driving
+/// the cloths with a fixed timestep (See `ClothConfig::fixed_timestep`)
+fn elapsed_fixed_steps(fixed_time: &Time<Fixed>, last_elapsed: &mut Duration) -> u32 {
+    let elapsed = fixed_time.elapsed().saturating_sub(*last_elapsed);
+    *last_elapsed = fixed_time.elapsed();
+    u32::try_from(elapsed.as_nanos() / fixed_time.timestep().as_nanos()).unwrap_or(u32::MAX)
+}
+
+pub fn send_events(
+    mut query: Query<(
+        Entity,
+        &mut Cloth,
+        Option<&ClothConfig>,
+        Option<&ViewVisibility>,
+        Option<&ClothCullDistance>,
+    )>,
+    config: Res<ClothConfig>,
+    mut events: EventWriter<ClothEvent>,
+) {
+    for (entity, mut cloth, custom_config, visibility, cull) in &mut query {
         let config: &ClothConfig = custom_config.unwrap_or(&config);
-        cloth.update_points(
-            config.friction_coefficient(),
-            config.smoothed_acceleration(wind_force + config.gravity, delta_time),
+        events.send_batch(
+            cloth
+                .released_anchors
+                .drain(..)
+                .map(|point| ClothEvent::AnchorReleased { entity, point }),
         );
-        cloth.update_anchored_points(transform, |entity| {
-            if let Ok(t) = anchor_query.get(entity) {
-                Some(t)
-            } else {
-                log::error!("Could not find cloth anchor target entity {:?}", entity);
-                None
-            }
-        });
-        cloth.update_sticks(config.sticks_computation_depth);
+        if config.is_paused(visibility) || cull.is_some_and(|c| c.culled) {
+            continue;
+        }
+        match cloth.update_settled_state(config.settle_threshold) {
+            Some(true) => events.send(ClothEvent::Settled { entity }),
+            Some(false) => events.send(ClothEvent::Reactivated { entity }),
+            None => continue,
+        };
     }
 }
 
@@ -44,13 +235,28 @@ pub fn render(
         &mut Aabb,
         &GlobalTransform,
         &Mesh3d,
+        Option<&ClothConfig>,
+        (Option<&ViewVisibility>, Option<&ClothCullDistance>),
     )>,
+    config: Res<ClothConfig>,
     mut meshes: ResMut<Assets<Mesh>>,
+    time: Res<Time>,
 ) {
-    for (cloth, mut rendering, mut aabb, transform, handle) in &mut cloth_query {
+    let delta_time = time.delta_secs();
+    for (cloth, mut rendering, mut aabb, transform, handle, custom_config, (visibility, cull)) in
+        &mut cloth_query
+    {
+        if custom_config.unwrap_or(&config).is_paused(visibility) || cull.is_some_and(|c| c.culled)
+        {
+            continue;
+        }
         if let Some(mesh) = meshes.get_mut(handle) {
-            rendering.update_positions(cloth.compute_vertex_positions(transform));
+            rendering.update_positions_from(cloth, transform);
+            rendering.update_normals(delta_time);
             rendering.apply(mesh);
+            if cloth.settled {
+                rendering.apply_once_normals(mesh);
+            }
             // TODO set_if_neq
             *aabb = rendering.compute_aabb();
         } else {
@@ -59,16 +265,508 @@ pub fn render(
     }
 }
 
+pub fn render_skins(
+    cloth_query: Query<(
+        &Cloth,
+        &ClothSkin,
+        Option<&ClothConfig>,
+        (Option<&ViewVisibility>, Option<&ClothCullDistance>),
+    )>,
+    skin_query: Query<(&Mesh3d, &GlobalTransform)>,
+    config: Res<ClothConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (cloth, skin, custom_config, (visibility, cull)) in &cloth_query {
+        if custom_config.unwrap_or(&config).is_paused(visibility) || cull.is_some_and(|c| c.culled)
+        {
+            continue;
+        }
+        let Ok((handle, transform)) = skin_query.get(skin.target) else {
+            log::error!("Could not find cloth skin target entity {:?}", skin.target);
+            continue;
+        };
+        let positions = skin.compute_positions(&cloth.current_point_positions);
+        deform_mesh("ClothSkin", meshes.get_mut(handle), transform, positions);
+    }
+}
+
+pub fn render_followers(
+    follower_query: Query<(&ClothFollower, &Mesh3d, &GlobalTransform)>,
+    cloth_query: Query<(
+        &Cloth,
+        Option<&ClothConfig>,
+        (Option<&ViewVisibility>, Option<&ClothCullDistance>),
+    )>,
+    config: Res<ClothConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (follower, handle, transform) in &follower_query {
+        let Ok((cloth, custom_config, (visibility, cull))) = cloth_query.get(follower.primary)
+        else {
+            log::error!(
+                "Could not find cloth follower primary entity {:?}",
+                follower.primary
+            );
+            continue;
+        };
+        if custom_config.unwrap_or(&config).is_paused(visibility) || cull.is_some_and(|c| c.culled)
+        {
+            continue;
+        }
+        let positions = follower.compute_positions(&cloth.current_point_positions);
+        deform_mesh(
+            "ClothFollower",
+            meshes.get_mut(handle),
+            transform,
+            positions,
+        );
+    }
+}
+
+/// Moves the `mesh` vertices to their world space `positions`, expressed in
+/// the mesh entity local space, and recomputes its normals. Used by the
+/// [`ClothSkin`] and [`ClothFollower`] meshes, identified by `source` in the
+/// logs.
+fn deform_mesh(
+    source: &str,
+    mesh: Option<&mut Mesh>,
+    transform: &GlobalTransform,
+    positions: impl Iterator<Item = (usize, Vec3)>,
+) {
+    let Some(mesh) = mesh else {
+        log::warn!("A `{source}` targets an entity without a loaded mesh handle");
+        return;
+    };
+    let Some(VertexAttributeValues::Float32x3(vertices)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    else {
+        log::error!("A `{source}` target mesh has no valid vertex positions");
+        return;
+    };
+    let matrix = transform.compute_matrix().inverse();
+    for (vertex, position) in positions {
+        if let Some(v) = vertices.get_mut(vertex) {
+            *v = matrix.transform_point3(position).to_array();
+        }
+    }
+    if mesh.contains_attribute(Mesh::ATTRIBUTE_NORMAL)
+        && mesh.primitive_topology() == PrimitiveTopology::TriangleList
+    {
+        mesh.compute_normals();
+    }
+}
+
+/// State of an initialized cloth, used to rebuild it when its
+/// [`ClothBuilder`] changes
+#[derive(Component)]
+pub(crate) struct ClothRestState {
+    /// The builder used for the last initialization
+    builder: ClothBuilder,
+    /// The original mesh data overwritten by the cloth rendering
+    mesh: Option<RestMesh>,
+}
+
+/// Original values of the mesh data overwritten by the cloth rendering,
+/// restored before rebuilding the cloth
+struct RestMesh {
+    /// The overwritten attributes, `None` if the mesh didn't have them
+    attributes: Vec<(MeshVertexAttribute, Option<VertexAttributeValues>)>,
+    /// The original indices, if the rendering duplicates the vertices
+    indices: Option<Indices>,
+}
+
+impl RestMesh {
+    /// Stores the `mesh` data overwritten by `rendering`
+    fn new(mesh: &Mesh, rendering: &ClothRendering) -> Self {
+        let flat = matches!(rendering.normal_computing, NormalComputing::FlatNormals);
+        let mut attributes = vec![Mesh::ATTRIBUTE_POSITION];
+        if !matches!(rendering.normal_computing, NormalComputing::None) {
+            attributes.push(Mesh::ATTRIBUTE_NORMAL);
+        }
+        if flat || rendering.fold_ao {
+            attributes.push(Mesh::ATTRIBUTE_COLOR);
+        }
+        if flat {
+            attributes.extend([Mesh::ATTRIBUTE_UV_0, Mesh::ATTRIBUTE_UV_1]);
+        }
+        if rendering.vertex_velocities.is_some() {
+            attributes.push(ClothRendering::ATTRIBUTE_VELOCITY);
+        }
+        Self {
+            attributes: attributes
+                .into_iter()
+                .map(|attribute| (attribute, mesh.attribute(attribute.id).cloned()))
+                .collect(),
+            indices: mesh.indices().filter(|_| flat).cloned(),
+        }
+    }
+
+    /// Restores the stored data in `mesh`
+    fn restore(&self, mesh: &mut Mesh) {
+        for (attribute, values) in &self.attributes {
+            if let Some(values) = values {
+                mesh.insert_attribute(*attribute, values.clone());
+            } else {
+                mesh.remove_attribute(attribute.id);
+            }
+        }
+        if let Some(indices) = &self.indices {
+            mesh.insert_indices(indices.clone());
+        }
+    }
+}
+
+/// Marks a cloth with anchor bones which couldn't be found yet (See
+/// [`VertexAnchor::bone_name`])
+///
+/// [`VertexAnchor::bone_name`]: crate::vertex_anchor::VertexAnchor::bone_name
+#[derive(Component)]
+pub(crate) struct UnresolvedBones;
+
+/// Builds the cloth of the builder explicit points and sticks
+fn build_points_cloth(builder: &ClothBuilder, matrix: &Mat4) -> Cloth {
+    let mut cloth = Cloth::from_sticks(
+        &builder.points,
+        &builder.sticks,
+        builder.anchored_point_ids(),
+        builder.stick_length,
+        builder.default_stick_mode,
+        matrix,
+    );
+    let gradient_anchors = builder.pin_gradient_point_ids(&cloth);
+    cloth.insert_spring_anchors(&builder.points, gradient_anchors);
+    cloth
+}
+
+fn build_rendering(builder: &ClothBuilder, mesh: &Mesh) -> Result<ClothRendering, Error> {
+    let mut rendering =
+        ClothRendering::init_with_uv_channel(mesh, builder.normals_computing, builder.uv_channel)?;
+    if builder.flip_winding {
+        rendering.flip_winding();
+    }
+    if let Some(normal) = builder.fallback_normal {
+        rendering.fallback_normal = normal;
+    }
+    rendering.fold_ao = builder.fold_ao;
+    rendering.area_weighted_normals = !builder.equal_weight_normals;
+    if builder.velocity_attribute {
+        rendering.vertex_velocities = Some(Vec::new());
+    }
+    Ok(rendering)
+}
+
+/// Builds the cloth of a mesh, from its triangles or from the builder quads
+fn build_mesh_cloth(
+    builder: &ClothBuilder,
+    mesh: &Mesh,
+    rendering: &ClothRendering,
+    matrix: &Mat4,
+) -> Cloth {
+    let mut cloth = if builder.quads.is_empty() {
+        Cloth::new(
+            &rendering.vertex_positions,
+            &rendering.indices,
+            builder.anchored_vertex_ids(mesh),
+            builder.stick_generation,
+            builder.stick_length,
+            builder.default_stick_mode,
+            matrix,
+        )
+    } else {
+        Cloth::from_quads(
+            &rendering.vertex_positions,
+            &builder.quads,
+            builder.anchored_vertex_ids(mesh),
+            builder.stick_length,
+            builder.default_stick_mode,
+            matrix,
+        )
+    };
+    cloth.insert_spring_anchors(
+        &rendering.vertex_positions,
+        builder.weighted_pin_vertex_ids(mesh),
+    );
+    let gradient_anchors = builder.pin_gradient_point_ids(&cloth);
+    cloth.insert_spring_anchors(&rendering.vertex_positions, gradient_anchors);
+    let painted_stiffness = builder.stick_stiffness(mesh, cloth.stick_lengths.keys());
+    cloth.stick_stiffness.extend(painted_stiffness);
+    cloth
+}
+
+/// Common cloth setup mistake, found on initialization (See `diagnose`)
+#[derive(Debug, Clone, PartialEq)]
+enum SetupIssue {
+    /// The `ClothBuilder` pins vertices from a mesh attribute, like the vertex
+    /// colors or a UV channel, which the mesh doesn't have
+    MissingPinAttribute(String),
+    /// The cloth has no anchor nor floor and will fall indefinitely. Requires
+    /// the `diagnostics` feature
+    NoAnchors,
+    /// The cloth entity has a non uniform scale, whose changes aren't followed
+    /// by the cloth (See `Cloth::follow_scale`). Requires the `diagnostics`
+    /// feature
+    NonUniformScale(Vec3),
+}
+
+/// Finds the common setup mistakes of a newly initialized or rebuilt cloth,
+/// from the cloth `builder` and its `mesh`, if any
+fn setup_issues(
+    builder: &ClothBuilder,
+    mesh: Option<&Mesh>,
+    cloth: &Cloth,
+    transform: &GlobalTransform,
+    config: &ClothConfig,
+) -> Vec<SetupIssue> {
+    let mut issues: Vec<_> = mesh
+        .map(|mesh| builder.missing_pin_attributes(mesh))
+        .unwrap_or_default()
+        .into_iter()
+        .map(SetupIssue::MissingPinAttribute)
+        .collect();
+    if !cfg!(feature = "diagnostics") {
+        return issues;
+    }
+    if cloth.anchors().next().is_none()
+        && cloth.spring_anchored_points.is_empty()
+        && config.floor_height.is_none()
+    {
+        issues.push(SetupIssue::NoAnchors);
+    }
+    let scale = transform.scale();
+    if scale.max_element() - scale.min_element() > 1e-4 {
+        issues.push(SetupIssue::NonUniformScale(scale));
+    }
+    issues
+}
+
+/// Logs a warning for each setup mistake of a newly initialized or rebuilt
+/// cloth (See [`SetupIssue`])
+fn diagnose(entity: Entity, issues: Vec<SetupIssue>) {
+    for issue in issues {
+        match issue {
+            SetupIssue::MissingPinAttribute(name) => log::warn!(
+                "Cloth entity {entity:?} pins vertices from the {name} mesh attribute but its \
+                 mesh doesn't have a valid {name} attribute, these pins are ignored"
+            ),
+            SetupIssue::NoAnchors => log::warn!(
+                "Cloth entity {entity:?} has no anchored vertices and will fall indefinitely: pin \
+                 some vertices with the `ClothBuilder` anchor methods, or set a \
+                 `ClothConfig::floor_height`"
+            ),
+            SetupIssue::NonUniformScale(scale) => log::warn!(
+                "Cloth entity {entity:?} has a non uniform scale ({scale}): the cloth only \
+                 follows uniform scale changes. Prefer scaling the mesh itself"
+            ),
+        }
+    }
+}
+
+/// Conforms `cloth` to the `target` collider shape, or to its `Aabb` without
+/// collider (See [`ClothBuilder::with_shrinkwrap`])
+fn shrinkwrap(
+    cloth: &mut Cloth,
+    config: &ClothConfig,
+    target: Entity,
+    target_query: &Query<(&GlobalTransform, Option<&Aabb>)>,
+    colliders: &TargetColliders,
+) {
+    let Ok((transform, aabb)) = target_query.get(target) else {
+        log::warn!("Failed to shrink-wrap cloth: {target:?} has no `GlobalTransform`");
+        return;
+    };
+    let box_matrix = transform.compute_matrix();
+    let project = |point: Vec3| {
+        colliders
+            .project(target, transform, point)
+            .or_else(|| aabb.map(|aabb| project_box_point(point, &box_matrix, aabb, 0.0).0))
+    };
+    if project(Vec3::ZERO).is_none() {
+        log::warn!("Failed to shrink-wrap cloth: {target:?} has no collider nor `Aabb`");
+        return;
+    }
+    cloth.shrinkwrap(
+        ClothBuilder::SHRINKWRAP_ITERATIONS,
+        config.effective_sticks_computation_depth(),
+        |point| project(*point).unwrap_or(*point),
+    );
+}
+
+/// Retrieves the entity named `bone_name` among `root` and its descendants
+fn find_bone(
+    name_query: &Query<&Name>,
+    children_query: &Query<&Children>,
+    root: Entity,
+    bone_name: &str,
+) -> Option<Entity> {
+    std::iter::once(root)
+        .chain(children_query.iter_descendants(root))
+        .find(|entity| {
+            name_query
+                .get(*entity)
+                .is_ok_and(|n| n.as_str() == bone_name)
+        })
+}
+
+/// Resolves the anchor bones of a newly initialized cloth, returning whether
+/// every bone was found
+fn resolve_init_bones(
+    entity: Entity,
+    cloth: &mut Cloth,
+    name_query: &Query<&Name>,
+    children_query: &Query<&Children>,
+) -> bool {
+    let missing_bones = cloth.resolve_bone_anchors(|name, root| {
+        find_bone(name_query, children_query, root.unwrap_or(entity), name)
+    });
+    if !missing_bones.is_empty() {
+        log::warn!(
+            "Could not find the cloth {entity:?} anchor bones named {missing_bones:?}, retrying \
+             when the hierarchy changes"
+        );
+    }
+    missing_bones.is_empty()
+}
+
+/// Retries resolving the anchor bones which couldn't be found when the cloth
+/// was initialized, whenever an entity gains a `Name` or its children change.
+/// This supports hierarchies spawned after the cloth, like a loading glTF
+/// scene.
+pub fn resolve_bones(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Cloth), With<UnresolvedBones>>,
+    hierarchy_changes: Query<(), Or<(Added<Name>, Changed<Children>)>>,
+    (name_query, children_query): (Query<&Name>, Query<&Children>),
+) {
+    if hierarchy_changes.is_empty() {
+        return;
+    }
+    for (entity, mut cloth) in &mut query {
+        let missing = cloth.resolve_bone_anchors(|name, root| {
+            find_bone(&name_query, &children_query, root.unwrap_or(entity), name)
+        });
+        if missing.is_empty() {
+            log::debug!("Resolved the anchor bones of cloth {entity:?}");
+            commands.entity(entity).remove::<UnresolvedBones>();
+        }
+    }
+}
+
+/// Delta time of the first cloth update, seeding the initial velocity: the
+/// fixed timestep if enabled, otherwise the frame delta time. The frame delta
+/// time is zero on the first frame, which would discard the initial velocity
+/// of the startup cloths, the warmup delta time is used instead.
+fn initial_velocity_delta(config: &ClothConfig, time: &Time, fixed_time: &Time<Fixed>) -> f32 {
+    if config.fixed_timestep {
+        return fixed_time.timestep().as_secs_f32();
+    }
+    Some(time.delta_secs())
+        .filter(|dt| *dt > 0.0)
+        .unwrap_or(ClothBuilder::WARMUP_DELTA_TIME)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn init(
     mut commands: Commands,
-    mut query: Query<(Entity, &ClothBuilder, &GlobalTransform, &Mesh3d), Added<ClothBuilder>>,
-    meshes: Res<Assets<Mesh>>,
+    mut query: Query<
+        (
+            Entity,
+            &ClothBuilder,
+            &GlobalTransform,
+            Option<&Mesh3d>,
+            (Option<&ClothRestState>, Option<&Cloth>),
+            Option<&ClothConfig>,
+            (Option<&GravityOverride>, Option<&VelocityDampingOverride>),
+        ),
+        Changed<ClothBuilder>,
+    >,
+    (target_query, colliders): (Query<(&GlobalTransform, Option<&Aabb>)>, TargetColliders),
+    path_query: Query<&ClothPath>,
+    (name_query, children_query): (Query<&Name>, Query<&Children>),
+    config: Res<ClothConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    (time, fixed_time): (Res<Time>, Res<Time<Fixed>>),
 ) {
-    for (entity, builder, transform, handle) in &mut query {
+    // Returns whether every anchor bone was found
+    let finalize = |entity: Entity,
+                    cloth: &mut Cloth,
+                    (builder, previous): (&ClothBuilder, Option<&Cloth>),
+                    config: &ClothConfig,
+                    transform| {
+        inherit_previous_state(cloth, builder, previous);
+        // Bone names are resolved once, the anchors then target the bone entities
+        let bones_resolved = resolve_init_bones(entity, cloth, &name_query, &children_query);
+        // Anchors are resolved as in the cloth update, without any wind
+        for _ in 0..builder.warmup_steps {
+            cloth.step(
+                config,
+                |_| Vec3::ZERO,
+                ClothBuilder::WARMUP_DELTA_TIME,
+                transform,
+                |entity| target_query.get(entity).ok().map(|(t, _)| t),
+                |entity| path_query.get(entity).ok(),
+            );
+        }
+        if let Some(target) = builder.shrinkwrap_target {
+            shrinkwrap(cloth, config, target, &target_query, &colliders);
+        }
+        if let Some(velocity) = &builder.initial_velocity {
+            let delta_time = initial_velocity_delta(config, &time, &fixed_time);
+            cloth.set_initial_velocity(velocity.as_ref(), delta_time);
+        }
+        cloth.phase_offset = builder.wind_phase;
+        bones_resolved
+    };
+    for (entity, builder, transform, handle, (rest_state, previous), custom_config, overrides) in
+        &mut query
+    {
+        let config = custom_config
+            .unwrap_or(&config)
+            .with_overrides(overrides.0, overrides.1);
+        let config: &ClothConfig = &config;
+        if let Some(state) = rest_state {
+            // Mutable access flags the builder as changed even if no value
+            // was modified
+            if builder.reflect_partial_eq(&state.builder) == Some(true) {
+                continue;
+            }
+            log::debug!("Rebuilding Cloth entity {entity:?} after a `ClothBuilder` change");
+            if let Some((mesh, rest_mesh)) = handle
+                .and_then(|h| meshes.get_mut(h))
+                .zip(state.mesh.as_ref())
+            {
+                rest_mesh.restore(mesh);
+            }
+        }
+        let matrix = transform.compute_matrix();
+        if !builder.points.is_empty() {
+            log::debug!(
+                "Initializing Cloth entity {:?} from explicit points",
+                entity
+            );
+            let mut cloth = build_points_cloth(builder, &matrix);
+            let bones_resolved =
+                finalize(entity, &mut cloth, (builder, previous), config, transform);
+            diagnose(
+                entity,
+                setup_issues(builder, None, &cloth, transform, config),
+            );
+            commands.entity(entity).insert((
+                cloth,
+                ClothRestState {
+                    builder: builder.clone(),
+                    mesh: None,
+                },
+            ));
+            mark_unresolved_bones(&mut commands, entity, bones_resolved);
+            continue;
+        }
+        let Some(handle) = handle else {
+            log::error!("Failed to setup cloth on {entity:?}: no mesh handle nor explicit points");
+            continue;
+        };
         if let Some(mesh) = meshes.get(handle) {
-            let matrix = transform.compute_matrix();
             log::debug!("Initializing Cloth entity {:?}", entity);
-            let rendering = match ClothRendering::init(mesh, builder.normals_computing) {
+            let rendering = match build_rendering(builder, mesh) {
                 Ok(r) => r,
                 Err(e) => {
                     log::error!("Failed to setup cloth on {entity:?}: `{e}`");
@@ -76,16 +774,762 @@ pub fn init(
                 }
             };
             let aabb = rendering.compute_aabb();
-            let cloth = Cloth::new(
-                &rendering.vertex_positions,
-                &rendering.indices,
-                builder.anchored_vertex_ids(mesh),
-                builder.stick_generation,
-                builder.stick_length,
-                builder.default_stick_mode,
-                &matrix,
+            let mut cloth = build_mesh_cloth(builder, mesh, &rendering, &matrix);
+            let bones_resolved =
+                finalize(entity, &mut cloth, (builder, previous), config, transform);
+            diagnose(
+                entity,
+                setup_issues(builder, Some(mesh), &cloth, transform, config),
+            );
+            let rest_state = ClothRestState {
+                builder: builder.clone(),
+                mesh: Some(RestMesh::new(mesh, &rendering)),
+            };
+            commands
+                .entity(entity)
+                .insert((rendering, cloth, aabb, rest_state));
+            mark_unresolved_bones(&mut commands, entity, bones_resolved);
+        }
+    }
+}
+
+/// Sets the point masses of a new cloth from its builder. A rebuilt cloth
+/// keeps the simulation time of the `previous` cloth, and its masses unless
+/// the builder sets them
+fn inherit_previous_state(cloth: &mut Cloth, builder: &ClothBuilder, previous: Option<&Cloth>) {
+    if let Some(previous) = previous {
+        cloth.sim_time = previous.sim_time;
+    }
+    if !builder.point_masses.is_empty() {
+        cloth.point_masses.clone_from(&builder.point_masses);
+    } else if let Some(previous) = previous {
+        cloth.point_masses.clone_from(&previous.point_masses);
+    }
+}
+
+/// Inserts or removes the [`UnresolvedBones`] marker of a cloth entity
+fn mark_unresolved_bones(commands: &mut Commands, entity: Entity, bones_resolved: bool) {
+    if bones_resolved {
+        commands.entity(entity).remove::<UnresolvedBones>();
+    } else {
+        commands.entity(entity).insert(UnresolvedBones);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mesh::rectangle_mesh, prelude::*, test_util::*};
+    use bevy::time::TimeUpdateStrategy;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn pinned_flag_hangs() {
+        let mut app = TestAppBuilder::new().build();
+        // Horizontal flag, pinned along its first row
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new().with_pinned_vertex_ids(0..10),
+            Transform::from_xyz(0.0, 5.0, 0.0),
+        );
+        step_cloth_app(&mut app, 300);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        let points = &cloth.current_point_positions;
+        // The pinned row didn't move
+        for point in &points[0..10] {
+            assert!((point.y - 5.0).abs() < 1e-4, "{point}");
+        }
+        // The free edge hangs below the pinned row
+        for point in &points[90..100] {
+            assert!(point.y < -2.0, "{point}");
+        }
+        assert!(points.iter().all(|p| p.is_finite()));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn initial_velocity_on_first_frame() {
+        let config = ClothConfig {
+            velocity_damping: 0.0,
+            ..ClothConfig::no_gravity()
+        };
+        let mut app = TestAppBuilder::new().with_config(config).build();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        // Spawned before the first update, like a startup cloth
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new().with_initial_velocity(|_, _| Vec3::Y * 3.0),
+            Transform::default(),
+        );
+        step_cloth_app(&mut app, 10);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        // Every point moved up at the initial velocity, without gravity
+        for point in &cloth.current_point_positions {
+            assert!(point.y > 0.4, "{point}");
+        }
+    }
+
+    #[test]
+    fn setup_issues_missing_pin_attributes() {
+        let mut mesh = rectangle_mesh((3, 3), (Vec3::X, -Vec3::Y), Vec3::Z);
+        let builder = ClothBuilder::new()
+            .with_pinned_vertex_color(Color::WHITE)
+            .with_pinned_uv_region(Vec2::ZERO, Vec2::ONE)
+            .with_uv_channel(UvChannel::Uv1)
+            .with_pins_from_attribute("Pin", 0.5);
+        // The floor avoids the no anchors diagnostic
+        let config = ClothConfig {
+            floor_height: Some(0.0),
+            ..Default::default()
+        };
+        let issues = |mesh: &Mesh| {
+            setup_issues(
+                &builder,
+                Some(mesh),
+                &Cloth::default(),
+                &GlobalTransform::IDENTITY,
+                &config,
+            )
+        };
+        let missing = |attribute: MeshVertexAttribute| {
+            SetupIssue::MissingPinAttribute(attribute.name.to_string())
+        };
+        assert_eq!(
+            issues(&mesh),
+            [
+                missing(Mesh::ATTRIBUTE_COLOR),
+                missing(Mesh::ATTRIBUTE_UV_1),
+                SetupIssue::MissingPinAttribute("Pin".to_string())
+            ]
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.0_f32; 4]; 9]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, vec![[0.0_f32; 2]; 9]);
+        assert_eq!(
+            issues(&mesh),
+            [SetupIssue::MissingPinAttribute("Pin".to_string())]
+        );
+        // Explicit points cloths don't read any mesh attribute
+        let points_issues = setup_issues(
+            &builder,
+            None,
+            &Cloth::default(),
+            &GlobalTransform::IDENTITY,
+            &config,
+        );
+        assert!(points_issues.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "diagnostics")]
+    fn setup_issues_diagnostics() {
+        let builder = ClothBuilder::new();
+        let issues = |cloth: &Cloth, scale: Vec3| {
+            setup_issues(
+                &builder,
+                None,
+                cloth,
+                &GlobalTransform::from_scale(scale),
+                &ClothConfig::default(),
+            )
+        };
+        let scale = Vec3::new(1.0, 2.0, 1.0);
+        assert_eq!(
+            issues(&Cloth::default(), scale),
+            [SetupIssue::NoAnchors, SetupIssue::NonUniformScale(scale)]
+        );
+        // Uniform scale changes are followed by the cloth
+        let cloth = Cloth::from_sticks(
+            &[Vec3::ZERO],
+            &[],
+            std::iter::once((0, VertexAnchor::default())).collect(),
+            StickLen::Auto,
+            StickMode::Fixed,
+            &Mat4::IDENTITY,
+        );
+        assert!(issues(&cloth, Vec3::splat(2.0)).is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn invisible_cloth_skips_skins_and_followers() {
+        let config = ClothConfig {
+            pause_when_invisible: true,
+            ..Default::default()
+        };
+        let mut app = TestAppBuilder::new().with_config(config).build();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        let cloth = spawn_cloth(
+            &mut app,
+            mesh.clone(),
+            ClothBuilder::new().with_pinned_vertex_ids(0..10),
+            Transform::default(),
+        );
+        let spawn_mesh = |app: &mut App| {
+            let handle = app
+                .world_mut()
+                .resource_mut::<Assets<Mesh>>()
+                .add(mesh.clone());
+            app.world_mut()
+                .spawn((Mesh3d(handle), Transform::default()))
+                .id()
+        };
+        let skin = spawn_mesh(&mut app);
+        let follower = spawn_mesh(&mut app);
+        app.world_mut().entity_mut(cloth).insert(ClothSkin {
+            target: skin,
+            bindings: (0..100).map(|i| (i, vec![(i, 1.0)])).collect(),
+        });
+        app.world_mut().entity_mut(follower).insert(ClothFollower {
+            primary: cloth,
+            vertex_map: (0..100).collect(),
+        });
+        let positions = |app: &App, entity: Entity| {
+            let handle = &app.world().get::<Mesh3d>(entity).unwrap().0;
+            let mesh = app.world().resource::<Assets<Mesh>>().get(handle).unwrap();
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap()
+                .to_vec()
+        };
+        // The visibility systems don't run in the test app
+        app.world_mut()
+            .get_mut::<ViewVisibility>(cloth)
+            .unwrap()
+            .set();
+        step_cloth_app(&mut app, 10);
+        let hidden_positions = positions(&app, skin);
+        assert!(hidden_positions[99][1] < -0.1);
+        assert_eq!(positions(&app, follower), hidden_positions);
+        app.world_mut()
+            .entity_mut(cloth)
+            .insert(ViewVisibility::HIDDEN);
+        app.world_mut()
+            .get_mut::<Cloth>(cloth)
+            .unwrap()
+            .current_point_positions[99] += Vec3::Y;
+        step_cloth_app(&mut app, 10);
+        assert_eq!(positions(&app, skin), hidden_positions);
+        assert_eq!(positions(&app, follower), hidden_positions);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn warmup_settles_on_init() {
+        let mut app = TestAppBuilder::new().build();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new()
+                .with_pinned_vertex_ids(0..10)
+                .with_warmup(300),
+            Transform::from_xyz(0.0, 5.0, 0.0),
+        );
+        app.update();
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        // The free edge already hangs after the first frame
+        for point in &cloth.current_point_positions[90..100] {
+            assert!(point.y < -2.0, "{point}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn sim_time_advances_per_cloth() {
+        let delta_time = 1.0 / 30.0;
+        let mut app = TestAppBuilder::new().with_delta_time(delta_time).build();
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        step_cloth_app(&mut app, 10);
+        let sim_time = app.world().get::<Cloth>(entity).unwrap().sim_time;
+        assert!(
+            sim_time > 0.0 && sim_time <= delta_time.mul_add(10.0, 1e-4),
+            "{sim_time}"
+        );
+        // A cloth spawned later starts from its own zero time
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        let other = spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        step_cloth_app(&mut app, 10);
+        let world = app.world();
+        let first = world.get::<Cloth>(entity).unwrap().sim_time;
+        let second = world.get::<Cloth>(other).unwrap().sim_time;
+        assert!((first - second - sim_time).abs() < 1e-4);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn deterministic_wind() {
+        let run = |idle_frames| {
+            let mut app = TestAppBuilder::new().with_delta_time(1.0 / 50.0).build();
+            app.insert_resource(Winds::from(Wind::SinWave {
+                max_velocity: Vec3::new(5.0, 0.0, 2.0),
+                frequency: 2.0,
+                normalize: false,
+                abs: false,
+            }));
+            // The wall clock keeps running before the cloth exists
+            step_cloth_app(&mut app, idle_frames);
+            let mesh = rectangle_mesh((5, 5), (Vec3::X, Vec3::NEG_Y), Vec3::Z);
+            let entity = spawn_cloth(
+                &mut app,
+                mesh,
+                ClothBuilder::new().with_pinned_vertex_ids(0..5),
+                Transform::default(),
+            );
+            step_cloth_app(&mut app, 40);
+            app.world()
+                .get::<Cloth>(entity)
+                .unwrap()
+                .current_point_positions
+                .clone()
+        };
+        // The first app update has a null delta time, skip it in every run
+        let first = run(1);
+        // Gravity alone would keep the cloth in its plane
+        assert!(first.iter().any(|p| p.z.abs() > 1e-3));
+        assert_eq!(first, run(1));
+        assert_eq!(first, run(13));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn fixed_timestep_wind() {
+        #[derive(Resource, Default)]
+        struct FixedSteps(u32);
+
+        let run = |frame_deltas: &[u64]| {
+            let mut app = TestAppBuilder::new()
+                .with_config(ClothConfig {
+                    fixed_timestep: true,
+                    ..Default::default()
+                })
+                .build();
+            app.insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(20)))
+                .insert_resource(Winds::from(Wind::SinWave {
+                    max_velocity: Vec3::new(5.0, 0.0, 2.0),
+                    frequency: 2.0,
+                    normalize: false,
+                    abs: false,
+                }))
+                .init_resource::<FixedSteps>()
+                .add_systems(FixedUpdate, |mut steps: ResMut<FixedSteps>| steps.0 += 1);
+            let mesh = rectangle_mesh((5, 5), (Vec3::X, Vec3::NEG_Y), Vec3::Z);
+            let entity = spawn_cloth(
+                &mut app,
+                mesh,
+                ClothBuilder::new().with_pinned_vertex_ids(0..5),
+                Transform::default(),
             );
-            commands.entity(entity).insert((rendering, cloth, aabb));
+            for delta in frame_deltas {
+                app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                    *delta,
+                )));
+                app.update();
+            }
+            let cloth = app.world().get::<Cloth>(entity).unwrap();
+            let steps = app.world().resource::<FixedSteps>().0;
+            (cloth.current_point_positions.clone(), cloth.sim_time, steps)
+        };
+        // 40 fixed steps of 20ms, with even and uneven frame deltas
+        let (first, sim_time, steps) = run(&[20; 41]);
+        assert_eq!(steps, 40);
+        assert!((sim_time - 0.8).abs() < 1e-4, "{sim_time}");
+        assert!(first.iter().any(|p| p.z.abs() > 1e-3));
+        let uneven: Vec<u64> = std::iter::once(20)
+            .chain([5, 35, 20, 12, 28].repeat(8))
+            .collect();
+        let (second, sim_time, steps) = run(&uneven);
+        assert_eq!(steps, 40);
+        assert!((sim_time - 0.8).abs() < 1e-4, "{sim_time}");
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn mesh_modified_before_extraction() {
+        use bevy::asset::{AssetEvent, AssetPlugin};
+
+        let mut app = TestAppBuilder::new().build();
+        app.add_plugins(AssetPlugin::default()).init_asset::<Mesh>();
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        app.update();
+        let handle = app
+            .world_mut()
+            .query::<&Mesh3d>()
+            .single(app.world())
+            .0
+            .id();
+        app.world_mut()
+            .resource_mut::<Events<AssetEvent<Mesh>>>()
+            .clear();
+        app.update();
+        // The simulated mesh is flagged as modified within the same frame, so
+        // the render world extracts it at the end of this frame
+        let events = app.world().resource::<Events<AssetEvent<Mesh>>>();
+        assert!(events
+            .iter_current_update_events()
+            .any(|event| *event == AssetEvent::Modified { id: handle }));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn wind_phase() {
+        let mut app = TestAppBuilder::new().build();
+        let [first, second] = [0.0, 2.5].map(|phase| {
+            let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+            let builder = ClothBuilder::new().with_wind_phase(phase);
+            spawn_cloth(&mut app, mesh, builder, Transform::default())
+        });
+        step_cloth_app(&mut app, 5);
+        let world = app.world();
+        let [first, second] = [first, second].map(|e| world.get::<Cloth>(e).unwrap());
+        // The phase doesn't affect the simulation time
+        assert!((second.sim_time - first.sim_time).abs() < 1e-6);
+        assert!((second.phase_offset - first.phase_offset - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn floor_height() {
+        let config = ClothConfig {
+            floor_height: Some(-2.0),
+            ..Default::default()
+        };
+        let mut app = TestAppBuilder::new().with_config(config).build();
+        let mesh = rectangle_mesh((5, 5), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        step_cloth_app(&mut app, 120);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        let lowest = cloth
+            .current_point_positions
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::MAX, f32::min);
+        // The cloth fell onto the floor but not below it
+        assert!((lowest - -1.99).abs() < 1e-4, "{lowest}");
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn cull_distance() {
+        let mut app = TestAppBuilder::new().build();
+        let camera = app
+            .world_mut()
+            .spawn((Camera::default(), Transform::default()))
+            .id();
+        let mesh = rectangle_mesh((5, 5), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new(),
+            Transform::from_xyz(100.0, 0.0, 0.0),
+        );
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(ClothCullDistance::new(50.0));
+        step_cloth_app(&mut app, 2);
+        let frozen = app.world().get::<Cloth>(entity).unwrap().clone();
+        step_cloth_app(&mut app, 10);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        assert!(app.world().get::<ClothCullDistance>(entity).unwrap().culled);
+        assert_eq!(
+            cloth.current_point_positions,
+            frozen.current_point_positions
+        );
+        // Within the hysteresis margin, the cloth stays frozen
+        app.world_mut()
+            .get_mut::<Transform>(camera)
+            .unwrap()
+            .translation
+            .x = 52.0;
+        step_cloth_app(&mut app, 10);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        assert_eq!(
+            cloth.current_point_positions,
+            frozen.current_point_positions
+        );
+        app.world_mut()
+            .get_mut::<Transform>(camera)
+            .unwrap()
+            .translation
+            .x = 80.0;
+        step_cloth_app(&mut app, 10);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        assert!(!app.world().get::<ClothCullDistance>(entity).unwrap().culled);
+        assert_ne!(
+            cloth.current_point_positions,
+            frozen.current_point_positions
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn bone_anchor() {
+        let mut app = TestAppBuilder::new().build();
+        let mut bone = Entity::PLACEHOLDER;
+        let root = app
+            .world_mut()
+            .spawn(Transform::from_xyz(0.0, 2.0, 0.0))
+            .with_children(|parent| {
+                parent
+                    .spawn((Name::new("Spine"), Transform::default()))
+                    .with_children(|parent| {
+                        bone = parent
+                            .spawn((Name::new("Hand"), Transform::from_xyz(3.0, 0.0, 0.0)))
+                            .id();
+                    });
+            })
+            .id();
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        let anchor = VertexAnchor {
+            bone_name: Some("Hand".to_owned()),
+            skeleton_root: Some(root),
+            ignore_vertex_position: true,
+            ..Default::default()
+        };
+        let builder = ClothBuilder::new().with_anchored_vertex_ids(0..1, anchor);
+        let entity = spawn_cloth(&mut app, mesh, builder, Transform::default());
+        step_cloth_app(&mut app, 5);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        let (_, anchor, _) = cloth.anchors().find(|(id, _, _)| *id == 0).unwrap();
+        assert_eq!(anchor.custom_target, Some(bone));
+        assert!(cloth.current_point_positions[0].abs_diff_eq(Vec3::new(3.0, 2.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn late_bone_anchor() {
+        let mut app = TestAppBuilder::new().build();
+        let root = app.world_mut().spawn(Transform::default()).id();
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        let anchor = VertexAnchor {
+            bone_name: Some("Hand".to_owned()),
+            skeleton_root: Some(root),
+            ..Default::default()
+        };
+        let builder = ClothBuilder::new().with_anchored_vertex_ids(0..1, anchor);
+        let entity = spawn_cloth(&mut app, mesh, builder, Transform::default());
+        step_cloth_app(&mut app, 2);
+        // The skeleton is spawned after the cloth
+        let bone = app
+            .world_mut()
+            .spawn((Name::new("Hand"), Transform::default()))
+            .set_parent(root)
+            .id();
+        step_cloth_app(&mut app, 2);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        let (_, anchor, _) = cloth.anchors().find(|(id, _, _)| *id == 0).unwrap();
+        assert_eq!(anchor.custom_target, Some(bone));
+        assert!(app.world().get::<UnresolvedBones>(entity).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn set_positions_rendered() {
+        let config = ClothConfig {
+            gravity: Vec3::ZERO,
+            ..Default::default()
+        };
+        let mut app = TestAppBuilder::new().with_config(config).build();
+        let mesh = rectangle_mesh((4, 4), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        step_cloth_app(&mut app, 2);
+        let mut cloth = app.world_mut().get_mut::<Cloth>(entity).unwrap();
+        let positions: Vec<Vec3> = cloth
+            .current_point_positions
+            .iter()
+            .map(|p| *p + Vec3::Y * 3.0)
+            .collect();
+        cloth.set_positions(&positions, false);
+        step_cloth_app(&mut app, 1);
+        let handle = app.world().get::<Mesh3d>(entity).unwrap();
+        let mesh = app.world().resource::<Assets<Mesh>>().get(handle).unwrap();
+        let rendered = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        for (vertex, expected) in rendered.iter().zip(&positions) {
+            assert!(Vec3::from(*vertex).abs_diff_eq(*expected, 1e-5));
         }
     }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn shrinkwrap_on_init() {
+        let mut app = TestAppBuilder::new().build();
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(4.5, 0.0, 4.5),
+                Aabb::from_min_max(Vec3::new(-2.0, -0.5, -2.0), Vec3::new(2.0, 0.5, 2.0)),
+            ))
+            .id();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new().with_shrinkwrap(target),
+            Transform::from_xyz(0.0, 3.0, 0.0),
+        );
+        app.update();
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        // The center of the cloth lies on the top face of the target
+        let point = cloth.current_point_positions[44];
+        assert!((point.y - 0.5).abs() < 0.1, "{point}");
+        // The cloth edges are pulled onto the edges of the target
+        for point in &cloth.current_point_positions[0..10] {
+            assert!(point.y <= 0.5 + 1e-3, "{point}");
+            assert!((2.3..=6.7).contains(&point.x), "{point}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rapier_collisions")]
+    #[allow(clippy::unwrap_used)]
+    fn shrinkwrap_on_collider() {
+        use bevy_rapier3d::prelude::Collider;
+
+        let mut app = TestAppBuilder::new().build();
+        let center = Vec3::new(4.5, 0.0, 4.5);
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::from_translation(center),
+                Collider::ball(2.0),
+                Aabb::from_min_max(Vec3::splat(-2.0), Vec3::splat(2.0)),
+            ))
+            .id();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new().with_shrinkwrap(target),
+            Transform::from_xyz(0.0, 3.0, 0.0),
+        );
+        app.update();
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        // The cloth lies on the sphere, not on the top face of its bounding box.
+        // The stick solving order varies between runs, so every point above the
+        // sphere flanks is checked rather than a given point
+        let on_flanks: Vec<Vec3> = cloth
+            .current_point_positions
+            .iter()
+            .copied()
+            .filter(|p| (1.2..1.8).contains(&p.xz().distance(center.xz())))
+            .collect();
+        assert!(!on_flanks.is_empty());
+        for point in on_flanks {
+            assert!((point.distance(center) - 2.0).abs() < 0.2, "{point}");
+            assert!(point.y < 1.8, "{point}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn gravity_override() {
+        let mut app = TestAppBuilder::new().build();
+        let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        app.world_mut()
+            .entity_mut(entity)
+            .insert((ClothConfig::default(), GravityOverride(Vec3::X)));
+        step_cloth_app(&mut app, 30);
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        // The override takes precedence over the config component gravity
+        for point in &cloth.current_point_positions {
+            assert!(point.y.abs() < 1e-4, "{point}");
+        }
+        assert!(cloth.current_point_positions[0].x > 0.1);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rebuilds_on_builder_change() {
+        let mut app = TestAppBuilder::new().build();
+        let mesh = rectangle_mesh((5, 5), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new().with_pinned_vertex_ids(0..5),
+            Transform::default(),
+        );
+        step_cloth_app(&mut app, 30);
+        let fallen = |app: &App| {
+            let cloth = app.world().get::<Cloth>(entity).unwrap();
+            cloth.current_point_positions[24].y < -0.5
+        };
+        assert!(fallen(&app));
+        // Mutable access without modification doesn't rebuild
+        app.world_mut()
+            .get_mut::<ClothBuilder>(entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+        assert!(fallen(&app));
+        // Modifying the builder rebuilds the cloth from its rest shape, keeping
+        // its masses
+        app.world_mut()
+            .get_mut::<Cloth>(entity)
+            .unwrap()
+            .point_masses = vec![2.0; 25];
+        app.world_mut()
+            .get_mut::<ClothBuilder>(entity)
+            .unwrap()
+            .anchored_vertex_ids
+            .extend((20..25).map(|i| (i, VertexAnchor::default())));
+        app.update();
+        let cloth = app.world().get::<Cloth>(entity).unwrap();
+        assert_eq!(cloth.anchors().count(), 10);
+        assert!(cloth.current_point_positions[24].y.abs() < 1e-4);
+        assert_eq!(cloth.point_masses, [2.0; 25]);
+        // The simulation time is kept
+        assert!(cloth.sim_time > 0.4, "{}", cloth.sim_time);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rebuild_restores_mesh() {
+        let mut app = TestAppBuilder::new().build();
+        let mesh = rectangle_mesh((4, 4), (Vec3::X, Vec3::Z), Vec3::Y);
+        let entity = spawn_cloth(
+            &mut app,
+            mesh.clone(),
+            ClothBuilder::new()
+                .with_pinned_vertex_ids(0..4)
+                .with_flat_normals(),
+            Transform::default(),
+        );
+        step_cloth_app(&mut app, 10);
+        let rest_state = app.world().get::<ClothRestState>(entity).unwrap();
+        let rest_mesh = rest_state.mesh.as_ref().unwrap();
+        // The flat normals duplicate the vertices, the simulated mesh is rewritten
+        let handle = app.world().get::<Mesh3d>(entity).unwrap().0.clone();
+        let mut simulated = app
+            .world()
+            .resource::<Assets<Mesh>>()
+            .get(&handle)
+            .unwrap()
+            .clone();
+        assert_eq!(simulated.count_vertices(), mesh.indices().unwrap().len());
+        rest_mesh.restore(&mut simulated);
+        assert_eq!(simulated.count_vertices(), mesh.count_vertices());
+        assert_eq!(
+            simulated
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3(),
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+        );
+        assert_eq!(
+            simulated.indices().unwrap().iter().collect::<Vec<_>>(),
+            mesh.indices().unwrap().iter().collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,4 +1,374 @@
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+use crate::{
+    components::{cloth::Cloth, cloth_cull::ClothCullDistance, collider::ClothCollider},
+    config::ClothConfig,
+};
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+use bevy::math::Vec2;
+use bevy::{
+    ecs::system::SystemParam,
+    math::{Mat4, Vec3},
+    prelude::*,
+    render::primitives::Aabb,
+};
+#[cfg(not(any(feature = "rapier_collisions", feature = "avian_collisions")))]
+use std::marker::PhantomData;
+
 #[cfg(feature = "avian_collisions")]
 pub mod avian;
 #[cfg(feature = "rapier_collisions")]
 pub mod rapier;
+
+/// Computes the new position of a cloth `point` colliding with a collider
+///
+/// # Arguments
+///
+/// * `point` - the cloth point
+/// * `projected_point` - the projection of `point` on the collider shape
+/// * `is_inside` - whether `point` is inside the collider shape
+/// * `collider` - the cloth collider options
+/// * `velocity` - the collider velocity contribution
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+#[allow(clippy::suboptimal_flops)]
+pub fn solve_point(
+    point: Vec3,
+    projected_point: Vec3,
+    is_inside: bool,
+    collider: &ClothCollider,
+    velocity: f32,
+) -> Option<Vec3> {
+    let normal: Vec3 = (projected_point - point).try_normalize().unwrap_or(Vec3::Y);
+    let target = if is_inside {
+        projected_point + (normal * collider.offset) + (normal * velocity)
+    } else if point.distance_squared(projected_point) < collider.offset * collider.offset {
+        projected_point - (normal * collider.offset)
+    } else {
+        return None;
+    };
+    Some(point.lerp(target, collider.push_strength.clamp(0.0, 1.0)))
+}
+
+/// Computes the new position of a cloth point whose movement from `previous`
+/// to `point` crossed a collider surface
+///
+/// # Arguments
+///
+/// * `previous` - the previous position of the cloth point
+/// * `point` - the current position of the cloth point
+/// * `time_of_impact` - the normalized position of the entry point along the
+///   `previous` to `point` segment, if any
+/// * `collider` - the cloth collider options
+///
+/// # Returns
+///
+/// `None` if there is no entry point or if `previous` was already inside the
+/// collider (`time_of_impact` of zero), letting the discrete collision
+/// solving apply.
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+pub fn solve_swept_point(
+    previous: Vec3,
+    point: Vec3,
+    time_of_impact: Option<f32>,
+    collider: &ClothCollider,
+) -> Option<Vec3> {
+    let movement = point - previous;
+    let entry = previous + movement * time_of_impact.filter(|t| *t > 0.0)?.min(1.0);
+    let target = entry - movement.normalize_or_zero() * collider.offset;
+    Some(point.lerp(target, collider.push_strength.clamp(0.0, 1.0)))
+}
+
+/// Computes the new position of a cloth `point` colliding with a heightfield,
+/// only pushing the point upward above the surface `height`
+///
+/// # Arguments
+///
+/// * `point` - the world space cloth point
+/// * `height` - the world space height of the heightfield surface below the
+///   point
+/// * `collider` - the cloth collider options
+/// * `velocity` - the collider velocity contribution
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+pub fn solve_height_point(
+    point: Vec3,
+    height: f32,
+    collider: &ClothCollider,
+    velocity: f32,
+) -> Option<Vec3> {
+    let target_height = if point.y < height {
+        height + collider.offset + velocity
+    } else if point.y < height + collider.offset {
+        height + collider.offset
+    } else {
+        return None;
+    };
+    let target = Vec3::new(point.x, target_height, point.z);
+    Some(point.lerp(target, collider.push_strength.clamp(0.0, 1.0)))
+}
+
+/// Computes the height of a `triangle` at the horizontal `point` position,
+/// used to sample heightfield colliders without a full point projection
+///
+/// # Arguments
+///
+/// * `triangle` - the triangle vertices
+/// * `point` - the XZ position to sample
+///
+/// # Returns
+///
+/// `None` if `point` is outside of the triangle XZ projection
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+#[must_use]
+pub fn triangle_height([a, b, c]: [Vec3; 3], point: Vec2) -> Option<f32> {
+    let (ab, ac, ap) = (b.xz() - a.xz(), c.xz() - a.xz(), point - a.xz());
+    let det = ab.perp_dot(ac);
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    let (weight_b, weight_c) = (ap.perp_dot(ac) / det, ab.perp_dot(ap) / det);
+    let tolerance = 1e-5;
+    (weight_b >= -tolerance && weight_c >= -tolerance && weight_b + weight_c <= 1.0 + tolerance)
+        .then_some(weight_c.mul_add(c.y - a.y, weight_b.mul_add(b.y - a.y, a.y)))
+}
+
+/// Projects `point` on the surface of a box
+///
+/// # Arguments
+///
+/// * `point` - the world space point
+/// * `box_matrix` - the box local to world space matrix
+/// * `aabb` - the local space box
+/// * `offset` - the distance the box is extended by
+///
+/// # Returns
+///
+/// The world space projected point, and whether `point` is inside the box
+#[must_use]
+pub fn project_box_point(point: Vec3, box_matrix: &Mat4, aabb: &Aabb, offset: f32) -> (Vec3, bool) {
+    let center = Vec3::from(aabb.center);
+    let half_extents = Vec3::from(aabb.half_extents) + offset;
+    let delta = box_matrix.inverse().transform_point3(point) - center;
+    let mut projected = delta.clamp(-half_extents, half_extents);
+    let is_inside = projected == delta;
+    if is_inside {
+        // The point is moved to the closest face
+        let penetration = half_extents - delta.abs();
+        let axis = if penetration.x <= penetration.y && penetration.x <= penetration.z {
+            0
+        } else if penetration.y <= penetration.z {
+            1
+        } else {
+            2
+        };
+        projected[axis] = half_extents[axis].copysign(delta[axis]);
+    }
+    (box_matrix.transform_point3(center + projected), is_inside)
+}
+
+/// Computes the position of a cloth `point` pushed out of a box, or `None` if
+/// the point is outside of the box
+///
+/// # Arguments
+///
+/// * `point` - the world space cloth point
+/// * `box_matrix` - the box local to world space matrix
+/// * `aabb` - the local space box
+/// * `collider` - the cloth collider options, its `offset` extends the box
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+pub fn solve_box_point(
+    point: Vec3,
+    box_matrix: &Mat4,
+    aabb: &Aabb,
+    collider: &ClothCollider,
+) -> Option<Vec3> {
+    let (target, is_inside) = project_box_point(point, box_matrix, aabb, collider.offset);
+    is_inside.then(|| point.lerp(target, collider.push_strength.clamp(0.0, 1.0)))
+}
+
+/// Collision backend colliders of the entities cloths are shrink-wrapped on
+/// (See [`ClothBuilder::with_shrinkwrap`])
+///
+/// [`ClothBuilder::with_shrinkwrap`]: crate::components::cloth_builder::ClothBuilder::with_shrinkwrap
+#[derive(SystemParam)]
+pub struct TargetColliders<'w, 's> {
+    #[cfg(feature = "rapier_collisions")]
+    rapier: Query<'w, 's, &'static bevy_rapier3d::prelude::Collider>,
+    #[cfg(feature = "avian_collisions")]
+    avian: Query<'w, 's, &'static avian3d::prelude::Collider>,
+    #[cfg(not(any(feature = "rapier_collisions", feature = "avian_collisions")))]
+    _marker: PhantomData<(&'w (), &'s ())>,
+}
+
+impl TargetColliders<'_, '_> {
+    /// Projects `point` on the surface of the `target` entity collider
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - the collider entity
+    /// * `transform` - the `target` global transform
+    /// * `point` - the world space point
+    ///
+    /// # Returns
+    ///
+    /// `None` if `target` has no collider, or without collision backend
+    #[allow(unused_variables, clippy::unused_self, clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn project(
+        &self,
+        target: Entity,
+        transform: &GlobalTransform,
+        point: Vec3,
+    ) -> Option<Vec3> {
+        #[cfg(feature = "rapier_collisions")]
+        if let Ok(collider) = self.rapier.get(target) {
+            return Some(rapier::project_on_surface(collider, transform, point));
+        }
+        #[cfg(feature = "avian_collisions")]
+        if let Ok(collider) = self.avian.get(target) {
+            return Some(avian::project_on_surface(collider, transform, point));
+        }
+        None
+    }
+}
+
+/// Retrieves the decimated cloth points used to build a convex hull collider
+/// (See [`LiveShape::ConvexHull`]).
+///
+/// At most `max_points` points are kept, evenly picked by index, expressed in
+/// the cloth entity local space and pushed away from their centroid by
+/// `offset`.
+///
+/// # Arguments
+///
+/// * `cloth` - the cloth
+/// * `transform` - the cloth entity `GlobalTransform`
+/// * `max_points` - the maximum number of points to keep
+/// * `offset` - the distance the hull is extended by
+///
+/// [`LiveShape::ConvexHull`]: crate::components::collider::LiveShape::ConvexHull
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+#[must_use]
+pub fn live_hull_points(
+    cloth: &Cloth,
+    transform: &GlobalTransform,
+    max_points: usize,
+    offset: f32,
+) -> Vec<Vec3> {
+    let positions = &cloth.current_point_positions;
+    let step = positions.len().div_ceil(max_points.max(1)).max(1);
+    let matrix = transform.compute_matrix().inverse();
+    let points: Vec<Vec3> = positions
+        .iter()
+        .step_by(step)
+        .map(|p| matrix.transform_point3(*p))
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let centroid = points.iter().sum::<Vec3>() / points.len().max(1) as f32;
+    points
+        .into_iter()
+        .map(|p| p + (p - centroid).normalize_or_zero() * offset)
+        .collect()
+}
+
+/// Pushes the points of cloths with [`ClothCollider::collide_with_cloths`]
+/// out of the AABB of the other cloths with the option.
+///
+/// Each pair is resolved in a single direction: the cloth with the greater
+/// entity is pushed out of the other one, which is left untouched. Resolving
+/// both directions would make the two cloths push each other back and forth.
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+pub fn handle_cloth_collisions(
+    mut cloth_query: Query<(
+        Entity,
+        &mut Cloth,
+        &Aabb,
+        &GlobalTransform,
+        &ClothCollider,
+        Option<&ClothConfig>,
+        Option<&ClothCullDistance>,
+    )>,
+    config: Res<ClothConfig>,
+) {
+    let boxes: Vec<(Entity, Mat4, Aabb)> = cloth_query
+        .iter()
+        .filter(|(_, _, _, _, c, _, _)| c.enabled && c.collide_with_cloths)
+        .map(|(entity, _, aabb, transform, _, _, _)| (entity, transform.compute_matrix(), *aabb))
+        .collect();
+    if boxes.len() < 2 {
+        return;
+    }
+    for (entity, mut cloth, _, _, collider, custom_config, cull) in &mut cloth_query {
+        if !collider.enabled || !collider.collide_with_cloths || cull.is_some_and(|c| c.culled) {
+            continue;
+        }
+        let locked_axes = custom_config.unwrap_or(&config).locked_axes;
+        for (_, box_matrix, aabb) in boxes.iter().filter(|(other, _, _)| *other < entity) {
+            cloth.solve_collisions_on_free_axes(locked_axes, |point| {
+                solve_box_point(*point, box_matrix, aabb, collider)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "rapier_collisions", feature = "avian_collisions"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_hull_points() {
+        let mut cloth = Cloth::default();
+        cloth.current_point_positions = (0..10_u8).map(|i| Vec3::X * f32::from(i)).collect();
+        let transform = GlobalTransform::from_translation(Vec3::X);
+        let points = super::live_hull_points(&cloth, &transform, 4, 0.0);
+        assert_eq!(points, [-1.0, 2.0, 5.0, 8.0].map(|x| Vec3::X * x));
+        let points = super::live_hull_points(&cloth, &transform, 2, 1.0);
+        assert_eq!(points, [Vec3::X * -2.0, Vec3::X * 5.0]);
+    }
+
+    #[test]
+    fn solve_box_point() {
+        let collider = ClothCollider {
+            offset: 0.5,
+            ..Default::default()
+        };
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let matrix = Mat4::from_translation(Vec3::Y * 10.0);
+        let solve = |point| super::solve_box_point(point, &matrix, &aabb, &collider);
+        assert_eq!(solve(Vec3::new(0.0, 12.0, 0.0)), None);
+        assert_eq!(
+            solve(Vec3::new(0.2, 11.0, 0.1)),
+            Some(Vec3::new(0.2, 11.5, 0.1))
+        );
+        assert_eq!(
+            solve(Vec3::new(-1.2, 10.0, 0.0)),
+            Some(Vec3::new(-1.5, 10.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn heightfield_points() {
+        let triangle = [
+            Vec3::ZERO,
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 2.0, 1.0),
+        ];
+        let height = super::triangle_height(triangle, Vec2::new(0.5, 0.5));
+        assert!(height.is_some_and(|h| (h - 1.5).abs() < 1e-5));
+        assert_eq!(super::triangle_height(triangle, Vec2::new(1.0, 1.0)), None);
+        let collider = ClothCollider {
+            offset: 0.5,
+            ..Default::default()
+        };
+        let solve = |point| super::solve_height_point(point, 1.0, &collider, 0.0);
+        assert_eq!(solve(Vec3::new(3.0, 2.0, 1.0)), None);
+        assert_eq!(
+            solve(Vec3::new(3.0, 1.2, 1.0)),
+            Some(Vec3::new(3.0, 1.5, 1.0))
+        );
+        assert_eq!(
+            solve(Vec3::new(3.0, -4.0, 1.0)),
+            Some(Vec3::new(3.0, 1.5, 1.0))
+        );
+    }
+}
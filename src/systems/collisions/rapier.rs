@@ -4,9 +4,19 @@
     clippy::option_if_let_else,
     clippy::suboptimal_flops
 )]
-use crate::components::{cloth::Cloth, collider::ClothCollider};
+use crate::{
+    components::{
+        cloth::Cloth,
+        cloth_cull::ClothCullDistance,
+        collider::{ClothCollider, ClothTouching, LiveShape},
+    },
+    config::ClothConfig,
+    systems::collisions::{
+        live_hull_points, solve_height_point, solve_point, solve_swept_point, triangle_height,
+    },
+};
 use bevy::{log, prelude::*, render::primitives::Aabb};
-use bevy_rapier3d::prelude::*;
+use bevy_rapier3d::{parry::shape::HeightField, prelude::*};
 
 fn get_collider(aabb: &Aabb, collider: &ClothCollider) -> Collider {
     let extents = aabb.half_extents + collider.offset;
@@ -17,14 +27,33 @@ fn get_collider(aabb: &Aabb, collider: &ClothCollider) -> Collider {
     )])
 }
 
+fn get_live_collider(
+    aabb: &Aabb,
+    collider: &ClothCollider,
+    cloth: &Cloth,
+    transform: &GlobalTransform,
+) -> Collider {
+    match collider.live_shape {
+        LiveShape::Aabb => get_collider(aabb, collider),
+        LiveShape::ConvexHull { max_points } => {
+            let points = live_hull_points(cloth, transform, max_points, collider.offset);
+            Collider::convex_hull(&points).unwrap_or_else(|| get_collider(aabb, collider))
+        }
+    }
+}
+
 pub fn handle_collisions(
     mut cloth_query: Query<(
         Entity,
         &mut Cloth,
         &Aabb,
+        &GlobalTransform,
         &ClothCollider,
         &mut Collider,
         Option<&RapierContextEntityLink>,
+        Option<&ClothConfig>,
+        Option<&ClothCullDistance>,
+        Option<&mut ClothTouching>,
     )>,
     defaukt_rapier_context: Query<&RapierContext, With<DefaultRapierContext>>,
     rapier_contexts: Query<&RapierContext, Without<DefaultRapierContext>>,
@@ -32,59 +61,175 @@ pub fn handle_collisions(
         (&Collider, &GlobalTransform, Option<&mut Velocity>),
         Without<Cloth>,
     >,
+    config: Res<ClothConfig>,
     time: Res<Time>,
+    mut fixed_step: Local<u32>,
 ) {
+    *fixed_step = fixed_step.wrapping_add(1);
     let Ok(default_context) = defaukt_rapier_context.get_single() else {
         panic!("No default rapier context set up");
     };
     let delta_time = time.delta_secs();
-    for (entity, mut cloth, aabb, collider, mut rapier_collider, context_link) in &mut cloth_query {
+    for (
+        entity,
+        mut cloth,
+        aabb,
+        transform,
+        collider,
+        mut rapier_collider,
+        context_link,
+        custom_config,
+        cull,
+        touching,
+    ) in &mut cloth_query
+    {
+        if !collider.enabled {
+            if let Some(mut touching) = touching.filter(|t| !t.0.is_empty()) {
+                touching.0.clear();
+            }
+            continue;
+        }
+        if cull.is_some_and(|c| c.culled) {
+            continue;
+        }
+        *rapier_collider = get_live_collider(aabb, collider, &cloth, transform);
+        if !collider.is_check_step(*fixed_step, entity) {
+            continue;
+        }
+        let locked_axes = custom_config.unwrap_or(&config).locked_axes;
         let context = context_link
             .and_then(|l| rapier_contexts.get(l.0).ok())
             .unwrap_or(default_context);
-        for contact_pair in context.contact_pairs_with(entity) {
-            let other_entity = if contact_pair.collider1() == entity {
-                contact_pair.collider2()
-            } else {
-                contact_pair.collider1()
-            };
-            let Ok((other_collider, other_transform, other_velocity)) =
-                colliders_query.get_mut(other_entity)
-            else {
-                log::error!("Couldn't find collider on entity {:?}", entity);
-                continue;
-            };
-            let vel = other_velocity.as_ref().map_or(0.0, |v| {
-                v.linvel.length_squared() * delta_time * delta_time * collider.velocity_coefficient
-            });
-            cloth.solve_collisions(|point| {
-                let other_transform = other_transform.compute_transform();
-                let projected_point = other_collider.project_point(
-                    other_transform.translation,
-                    other_transform.rotation,
-                    *point,
-                    false,
-                );
-                let normal: Vec3 = (projected_point.point - *point)
-                    .try_normalize()
-                    .unwrap_or(Vec3::Y);
-                if projected_point.is_inside {
-                    Some(projected_point.point + (normal * collider.offset) + (normal * vel))
-                } else if point.distance_squared(projected_point.point)
-                    < collider.offset * collider.offset
-                {
-                    Some(projected_point.point - (normal * collider.offset))
+        let contacts: Vec<(Entity, Transform, f32)> = context
+            .contact_pairs_with(entity)
+            .filter_map(|contact_pair| {
+                let other_entity = if contact_pair.collider1() == entity {
+                    contact_pair.collider2()
                 } else {
-                    None
-                }
-            });
-            if let Some((ref mut vel, dampen_coef)) = other_velocity.zip(collider.dampen_others) {
+                    contact_pair.collider1()
+                };
+                let Ok((_, other_transform, other_velocity)) = colliders_query.get(other_entity)
+                else {
+                    log::error!("Couldn't find collider on entity {:?}", entity);
+                    return None;
+                };
+                let vel = other_velocity.map_or(0.0, |v| {
+                    v.linvel.length_squared()
+                        * delta_time
+                        * delta_time
+                        * collider.velocity_coefficient
+                });
+                Some((other_entity, other_transform.compute_transform(), vel))
+            })
+            .collect();
+        if let Some(mut touching) = touching {
+            let entities = contacts.iter().map(|(e, _, _)| *e);
+            if !touching.0.iter().copied().eq(entities.clone()) {
+                touching.0.clear();
+                touching.0.extend(entities);
+            }
+        }
+        {
+            let solvers: Vec<_> = contacts
+                .iter()
+                .filter_map(|(other_entity, other_transform, vel)| {
+                    let (other_collider, _, _) = colliders_query.get(*other_entity).ok()?;
+                    Some(contact_solver(
+                        collider,
+                        other_collider,
+                        other_transform,
+                        *vel,
+                    ))
+                })
+                .collect();
+            for _ in 0..collider.contact_relaxation.max(1) {
+                cloth.solve_contacts(&solvers, collider.solve_iterations, locked_axes);
+            }
+        }
+        let Some(dampen_coef) = collider.dampen_others else {
+            continue;
+        };
+        for (other_entity, _, _) in &contacts {
+            if let Ok((_, _, Some(mut vel))) = colliders_query.get_mut(*other_entity) {
                 let damp = 1.0 - dampen_coef;
                 vel.linvel *= damp;
                 vel.angvel *= damp;
             }
         }
-        *rapier_collider = get_collider(aabb, collider);
+    }
+}
+
+/// Samples the local space height of a `heightfield` below `local_point`
+fn heightfield_height(heightfield: &HeightField, local_point: Vec3) -> Option<f32> {
+    let (i, j) = heightfield.cell_at_point(&local_point.into())?;
+    let (first, second) = heightfield.triangles_at(i, j);
+    first.into_iter().chain(second).find_map(|triangle| {
+        let vertices = [triangle.a, triangle.b, triangle.c].map(Vec3::from);
+        triangle_height(vertices, local_point.xz())
+    })
+}
+
+/// Projects `point` on the surface of a `collider` shape, inside points
+/// included, used to shrink-wrap cloths
+pub fn project_on_surface(collider: &Collider, transform: &GlobalTransform, point: Vec3) -> Vec3 {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    collider
+        .project_point(translation, rotation, point, false)
+        .point
+}
+
+/// Builds the function solving the cloth points against a contacting collider
+fn contact_solver<'a>(
+    collider: &'a ClothCollider,
+    other_collider: &'a Collider,
+    other_transform: &'a Transform,
+    vel: f32,
+) -> impl Fn(&Vec3, &Vec3) -> Option<Vec3> + 'a {
+    // Upward heightfields are sampled directly, cheaper than a projection
+    let heightfield = other_collider
+        .as_heightfield()
+        .map(|view| view.raw)
+        .filter(|_| (other_transform.rotation * Vec3::Y).abs_diff_eq(Vec3::Y, 1e-4));
+    move |previous: &Vec3, point: &Vec3| {
+        if let Some(heightfield) = heightfield {
+            let local_point =
+                other_transform.rotation.inverse() * (*point - other_transform.translation);
+            return heightfield_height(heightfield, local_point).and_then(|height| {
+                solve_height_point(
+                    *point,
+                    height + other_transform.translation.y,
+                    collider,
+                    vel,
+                )
+            });
+        }
+        let projected_point = other_collider.project_point(
+            other_transform.translation,
+            other_transform.rotation,
+            *point,
+            false,
+        );
+        let time_of_impact = if collider.swept {
+            other_collider.cast_ray(
+                other_transform.translation,
+                other_transform.rotation,
+                *previous,
+                *point - *previous,
+                1.0,
+                true,
+            )
+        } else {
+            None
+        };
+        solve_swept_point(*previous, *point, time_of_impact, collider).or_else(|| {
+            solve_point(
+                *point,
+                projected_point.point,
+                projected_point.is_inside,
+                collider,
+                vel,
+            )
+        })
     }
 }
 
@@ -92,7 +237,7 @@ pub fn init_cloth_collider(
     mut commands: Commands,
     cloth_query: Query<(Entity, &Aabb, &ClothCollider), (With<Cloth>, Without<Collider>)>,
 ) {
-    for (entity, aabb, collider) in cloth_query.iter() {
+    for (entity, aabb, collider) in cloth_query.iter().filter(|(_, _, c)| c.enabled) {
         log::debug!("Initializing Cloth collisions for {:?}", entity);
         commands.entity(entity).insert((
             RigidBody::KinematicPositionBased,
@@ -101,3 +246,137 @@ pub fn init_cloth_collider(
         ));
     }
 }
+
+pub fn remove_disabled_cloth_collider(
+    mut commands: Commands,
+    mut cloth_query: Query<
+        (Entity, &ClothCollider, Option<&mut ClothTouching>),
+        (With<Collider>, Changed<ClothCollider>),
+    >,
+) {
+    for (entity, collider, touching) in &mut cloth_query {
+        if !collider.enabled {
+            log::debug!("Disabling Cloth collisions for {:?}", entity);
+            commands
+                .entity(entity)
+                .remove::<(RigidBody, Collider, SolverGroups)>();
+            // The collisions handling skips the cloths without a collider
+            if let Some(mut touching) = touching.filter(|t| !t.0.is_empty()) {
+                touching.0.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mesh::rectangle_mesh, prelude::*, test_util::*};
+
+    /// Spawns a static ball at the origin and a falling cloth above it, in a
+    /// headless app with the rapier physics
+    fn setup(collider: ClothCollider) -> (App, Entity, Entity) {
+        let mut app = TestAppBuilder::new().build();
+        // The scene spawner is required by the rapier async scene colliders
+        app.init_resource::<bevy::scene::SceneSpawner>()
+            .add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+        let ball = app
+            .world_mut()
+            .spawn((Transform::default(), Collider::ball(1.0)))
+            .id();
+        let mesh = rectangle_mesh((10, 10), (Vec3::X * 0.4, Vec3::Z * 0.4), Vec3::Y);
+        let cloth = spawn_cloth(
+            &mut app,
+            mesh,
+            ClothBuilder::new(),
+            Transform::from_xyz(-1.8, 2.0, -1.8),
+        );
+        app.world_mut()
+            .entity_mut(cloth)
+            .insert((collider, ClothTouching::default()));
+        (app, ball, cloth)
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn highest_point(app: &App, cloth: Entity) -> f32 {
+        let cloth = app.world().get::<Cloth>(cloth).unwrap();
+        cloth
+            .current_point_positions
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::MIN, f32::max)
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn set_enabled(app: &mut App, cloth: Entity, enabled: bool) {
+        app.world_mut()
+            .get_mut::<ClothCollider>(cloth)
+            .unwrap()
+            .enabled = enabled;
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn toggle_enabled() {
+        let (mut app, _, cloth) = setup(ClothCollider::new().with_enabled(false));
+        app.update();
+        let start = app
+            .world()
+            .get::<Cloth>(cloth)
+            .unwrap()
+            .current_point_positions
+            .clone();
+        let reset = |app: &mut App| {
+            let mut cloth = app.world_mut().get_mut::<Cloth>(cloth).unwrap();
+            cloth.current_point_positions.clone_from(&start);
+            cloth.previous_point_positions.clone_from(&start);
+        };
+        reset(&mut app);
+        // Disabled: no collider, the cloth falls through the ball
+        step_cloth_app(&mut app, 60);
+        assert!(app.world().get::<Collider>(cloth).is_none());
+        assert!(highest_point(&app, cloth) < 0.0);
+        // Enabled: the collider is restored, the cloth rests on the ball
+        set_enabled(&mut app, cloth, true);
+        reset(&mut app);
+        step_cloth_app(&mut app, 60);
+        assert!(app.world().get::<Collider>(cloth).is_some());
+        assert!(highest_point(&app, cloth) > 0.9);
+        // Disabled again: the collider is removed, the cloth falls through
+        set_enabled(&mut app, cloth, false);
+        step_cloth_app(&mut app, 60);
+        assert!(app.world().get::<Collider>(cloth).is_none());
+        assert!(highest_point(&app, cloth) < 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn disabling_clears_touching() {
+        let (mut app, ball, cloth) = setup(ClothCollider::default());
+        step_cloth_app(&mut app, 60);
+        assert!(app
+            .world()
+            .get::<ClothTouching>(cloth)
+            .unwrap()
+            .contains(ball));
+        // Disabled by gameplay logic, the collider is removed before the next
+        // collisions handling
+        app.add_systems(
+            Update,
+            (|mut query: Query<&mut ClothCollider>| {
+                for mut collider in &mut query {
+                    collider.enabled = false;
+                }
+            })
+            .before(ClothSystems::Collisions),
+        );
+        step_cloth_app(&mut app, 2);
+        assert!(app.world().get::<Collider>(cloth).is_none());
+        assert!(app
+            .world()
+            .get::<ClothTouching>(cloth)
+            .unwrap()
+            .0
+            .is_empty());
+    }
+}
@@ -1,3 +1,5 @@
 /// Base cloth systems
 pub mod cloth;
 pub mod collisions;
+/// Cloth statistics systems
+pub mod stats;
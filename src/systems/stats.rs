@@ -0,0 +1,62 @@
+#![allow(clippy::needless_pass_by_value)]
+use crate::{components::cloth::Cloth, stats::ClothStats};
+use bevy::prelude::*;
+#[cfg(feature = "stats_timing")]
+use bevy::utils::Instant;
+
+pub fn count_cloths(query: Query<&Cloth>, mut stats: ResMut<ClothStats>) {
+    let (cloths, points, sticks) = query.iter().fold((0, 0, 0), |(c, p, s), cloth| {
+        (
+            c + 1,
+            p + cloth.current_point_positions.len(),
+            s + cloth.stick_lengths.len(),
+        )
+    });
+    stats.cloths = cloths;
+    stats.points = points;
+    stats.sticks = sticks;
+}
+
+#[cfg(feature = "stats_timing")]
+#[derive(Default, Resource)]
+pub struct StatsTimer(Option<Instant>);
+
+#[cfg(feature = "stats_timing")]
+pub fn start_timer(mut timer: ResMut<StatsTimer>) {
+    timer.0 = Some(Instant::now());
+}
+
+#[cfg(feature = "stats_timing")]
+pub fn stop_update_timer(timer: Res<StatsTimer>, mut stats: ResMut<ClothStats>) {
+    if let Some(start) = timer.0 {
+        stats.update_time = start.elapsed();
+    }
+}
+
+#[cfg(feature = "stats_timing")]
+pub fn stop_render_timer(timer: Res<StatsTimer>, mut stats: ResMut<ClothStats>) {
+    if let Some(start) = timer.0 {
+        stats.render_time = start.elapsed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_util::*};
+    use bevy::prelude::*;
+
+    #[test]
+    fn cloth_stats() {
+        let mut app = TestAppBuilder::new().build();
+        for _ in 0..2 {
+            let mesh = rectangle_mesh((3, 3), (Vec3::X, Vec3::Z), Vec3::Y);
+            spawn_cloth(&mut app, mesh, ClothBuilder::new(), Transform::default());
+        }
+        app.update();
+        let stats = app.world().resource::<ClothStats>();
+        assert_eq!(stats.cloths, 2);
+        assert_eq!(stats.points, 18);
+        // 12 structural sticks per cloth
+        assert_eq!(stats.sticks, 24);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy, transform::TransformPlugin};
+use std::time::Duration;
+
+/// Builder for a minimal headless [`App`] running the [`ClothPlugin`], with a
+/// fixed delta time for deterministic cloth tests.
+///
+/// The app doesn't render anything: it only holds the mesh and image assets
+/// required by the cloth systems and propagates the transforms.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_silk::{prelude::*, test_util::*};
+///
+/// let mut app = TestAppBuilder::new().with_delta_time(1.0 / 30.0).build();
+/// let mesh = rectangle_mesh((10, 10), (Vec3::X, Vec3::Z), Vec3::Y);
+/// let entity = spawn_cloth(
+///     &mut app,
+///     mesh,
+///     ClothBuilder::new().with_pinned_vertex_ids(0..10),
+///     Transform::default(),
+/// );
+/// step_cloth_app(&mut app, 60);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct TestAppBuilder {
+    /// The fixed delta time of every update, in seconds
+    pub delta_time: f32,
+    /// The global cloth configuration
+    pub config: ClothConfig,
+}
+
+impl Default for TestAppBuilder {
+    fn default() -> Self {
+        Self {
+            delta_time: 1.0 / 60.0,
+            config: ClothConfig::default(),
+        }
+    }
+}
+
+#[allow(clippy::missing_const_for_fn)]
+impl TestAppBuilder {
+    /// Instantiates a new builder with a 60 FPS delta time and the default
+    /// cloth configuration
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the fixed delta time of every update, in seconds
+    #[inline]
+    pub fn with_delta_time(mut self, delta_time: f32) -> Self {
+        self.delta_time = delta_time;
+        self
+    }
+
+    /// Sets the global cloth configuration
+    #[inline]
+    pub fn with_config(mut self, config: ClothConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Builds the headless app
+    #[must_use]
+    pub fn build(self) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, ClothPlugin))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<Image>>()
+            .insert_resource(self.config)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                self.delta_time,
+            )));
+        app
+    }
+}
+
+/// Spawns a cloth entity in `app`, adding `mesh` to the mesh assets
+///
+/// # Arguments
+///
+/// * `app` - the app, built by [`TestAppBuilder`]
+/// * `mesh` - the cloth mesh
+/// * `builder` - the cloth builder
+/// * `transform` - the cloth entity transform
+///
+/// # Returns
+///
+/// The cloth entity
+pub fn spawn_cloth(
+    app: &mut App,
+    mesh: Mesh,
+    builder: ClothBuilder,
+    transform: Transform,
+) -> Entity {
+    let handle = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+    app.world_mut()
+        .spawn((Mesh3d(handle), builder, transform))
+        .id()
+}
+
+/// Runs `frames` updates of `app`
+pub fn step_cloth_app(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
@@ -1,6 +1,7 @@
+use crate::components::cloth_path::ClothPath;
 use bevy::{
-    math::Vec3,
-    prelude::{Entity, GlobalTransform},
+    math::{BVec3, Vec2, Vec3},
+    prelude::{Entity, GlobalTransform, ReflectDefault},
     reflect::Reflect,
 };
 
@@ -8,8 +9,17 @@ use bevy::{
 ///
 /// The default anchor will link the cloth vertices to the cloth entity's
 /// `GlobalTransform`, you can anchor them to a specific entity by defining a
-/// `custom_target`.
-#[derive(Debug, Copy, Clone, Default, Reflect)]
+/// `custom_target`, or make them ride along a [`ClothPath`] by defining a
+/// `path`.
+///
+/// # Scenes
+///
+/// The anchor is reflected and registered, so it can be authored in scene
+/// files. Note that [`Self::custom_target`] and [`Self::path`] entities are
+/// serialized as raw entity ids and are *not* remapped when a scene is
+/// spawned: prefer leaving them empty in scenes and setting them at runtime.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 #[must_use]
 pub struct VertexAnchor {
     /// Optional custom anchor target entity. If not set, the cloth entity will
@@ -21,9 +31,119 @@ pub struct VertexAnchor {
     /// If [`Self::custom_offset`] is defined, it will then override the vertex
     /// position
     pub ignore_vertex_position: bool,
+    /// Optional path to anchor the vertex to. The tuple is composed of:
+    /// - 0: the entity holding the [`ClothPath`] component
+    /// - 1: the normalized position along the path (See [`ClothPath::sample`])
+    ///
+    /// If set, the vertex follows the sampled path position in the path
+    /// entity's space and both [`Self::custom_target`] and the base vertex
+    /// position are ignored. [`Self::custom_offset`] is still applied.
+    pub path: Option<(Entity, f32)>,
+    /// Optional maximum pulling force the anchor can withstand. If set, the
+    /// vertex is released and becomes dynamic once the cloth pulls on it
+    /// harder than this value, sending a [`ClothEvent::AnchorReleased`]
+    /// event.
+    ///
+    /// The force is estimated from the tension of the sticks connected to
+    /// the vertex: it is the length of the sum of their elongations, in world
+    /// units, from the anchor target position.
+    ///
+    /// [`ClothEvent::AnchorReleased`]: crate::event::ClothEvent::AnchorReleased
+    pub max_force: Option<f32>,
+    /// Optional rate, per second, at which the vertex follows its anchor
+    /// position instead of snapping to it every frame.
+    ///
+    /// The vertex covers `1 - exp(-follow_speed * delta_time)` of the
+    /// remaining distance every frame, independently of the frame rate,
+    /// smoothing the pops of teleporting or jittery targets like network
+    /// interpolated joints. Higher values follow more closely.
+    ///
+    /// The vertex stays kinematic: it is moved toward its anchor but not
+    /// affected by gravity, winds or sticks, unlike the spring anchors (See
+    /// [`Cloth::spring_anchored_points`]).
+    ///
+    /// [`Cloth::spring_anchored_points`]: crate::components::cloth::Cloth::spring_anchored_points
+    pub follow_speed: Option<f32>,
+    /// Optional name of a bone to use as [`Self::custom_target`], for rigged
+    /// characters whose joint entities aren't known when spawning the cloth.
+    ///
+    /// The bone is the entity with a matching `Name` component among
+    /// [`Self::skeleton_root`] and its descendants. The lookup runs once,
+    /// when the cloth is initialized from its builder: the resolved entity is
+    /// cached as the [`Self::custom_target`] of the cloth anchors, so the
+    /// names are not compared every frame. Anchors sharing the same bone
+    /// name and root share a single lookup.
+    ///
+    /// If no bone matches, a warning is logged and the anchor keeps its
+    /// `custom_target`. The lookup is then retried whenever an entity gains a
+    /// `Name` or its children change, until every bone is found, supporting
+    /// skeletons spawned after the cloth like loading glTF scenes.
+    pub bone_name: Option<String>,
+    /// Root entity of the skeleton to search for [`Self::bone_name`]. If not
+    /// set, the cloth entity hierarchy is used
+    pub skeleton_root: Option<Entity>,
+    /// World space axes on which the vertex is pinned to its anchor. Defaults
+    /// to all axes.
+    ///
+    /// Anchors with free axes model vertices sliding along their anchor, like
+    /// curtain rings on a rod: `BVec3::new(false, true, true)` keeps the
+    /// vertex on the rod line while letting it slide along the X axis. Such
+    /// vertices are anchored but stay dynamic, simulated like regular points:
+    /// when applying the anchor, only the constrained components of the
+    /// vertex are set to the anchor position and the free ones keep their
+    /// simulated value, from gravity, winds and sticks. The constrained
+    /// components are restored after the sticks solving, keeping the vertex
+    /// exactly on the anchor line or plane.
+    ///
+    /// On spring anchors (See [`Cloth::spring_anchored_points`]), only the
+    /// constrained components are pulled toward the anchor.
+    ///
+    /// [`Cloth::spring_anchored_points`]: crate::components::cloth::Cloth::spring_anchored_points
+    pub constrain_axes: BVec3,
+}
+
+impl Default for VertexAnchor {
+    fn default() -> Self {
+        Self {
+            custom_target: None,
+            custom_offset: None,
+            ignore_vertex_position: false,
+            path: None,
+            max_force: None,
+            follow_speed: None,
+            bone_name: None,
+            skeleton_root: None,
+            constrain_axes: BVec3::TRUE,
+        }
+    }
 }
 
 impl VertexAnchor {
+    /// Whether the anchor leaves some axes free (See
+    /// [`Self::constrain_axes`])
+    #[inline]
+    #[must_use]
+    pub fn is_partial(&self) -> bool {
+        !self.constrain_axes.all()
+    }
+
+    /// Retrieves the new position of a vertex at `current` following its
+    /// anchor `target` position, according to [`Self::follow_speed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - the current vertex world space position
+    /// * `target` - the anchor world space position (See
+    ///   [`Self::get_position`])
+    /// * `delta_time` - elapsed time since last frame in seconds
+    #[inline]
+    #[must_use]
+    pub fn follow(&self, current: Vec3, target: Vec3, delta_time: f32) -> Vec3 {
+        self.follow_speed.map_or(target, |speed| {
+            current.lerp(target, 1.0 - (-speed.max(0.0) * delta_time).exp())
+        })
+    }
+
     /// Retrieves the anchor world space position.
     ///
     /// # Arguments
@@ -32,7 +152,8 @@ impl VertexAnchor {
     /// * `self_transform` - the `GlobalTransform` associated with the cloth
     ///   entity used without a custom target entity
     /// * `transform_query` - ECS query used in case of a set
-    ///   [`Self::custom_target`]
+    ///   [`Self::custom_target`] or [`Self::path`]
+    /// * `path_query` - ECS query used in case of a set [`Self::path`]
     #[inline]
     #[must_use]
     pub fn get_position<'a>(
@@ -40,7 +161,17 @@ impl VertexAnchor {
         original_pos: Vec3,
         self_transform: &GlobalTransform,
         query: &impl Fn(Entity) -> Option<&'a GlobalTransform>,
+        path_query: &impl Fn(Entity) -> Option<&'a ClothPath>,
     ) -> Vec3 {
+        if let Some((entity, t)) = self.path {
+            if let Some((transform, pos)) = query(entity)
+                .zip(path_query(entity))
+                .and_then(|(transform, path)| Some((transform, path.sample(t)?)))
+            {
+                let local_pos = pos + self.custom_offset.unwrap_or(Vec3::ZERO);
+                return transform.compute_matrix().transform_point3(local_pos);
+            }
+        }
         let transform = self.custom_target.and_then(query).unwrap_or(self_transform);
         let local_pos = if self.ignore_vertex_position {
             Vec3::ZERO
@@ -51,3 +182,88 @@ impl VertexAnchor {
         matrix.transform_point3(local_pos)
     }
 }
+
+/// Declarative anchor definition over a region of the cloth, anchoring every
+/// vertex whose local space position is inside the axis aligned box defined
+/// by `min` and `max` (inclusive).
+///
+/// Unlike position conditions closures, regions are reflected and can be
+/// authored in scene files.
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Default)]
+pub struct AnchorRegion {
+    /// Minimum corner of the region, in the cloth local space
+    pub min: Vec3,
+    /// Maximum corner of the region, in the cloth local space
+    pub max: Vec3,
+    /// The anchor to apply to the vertices inside the region
+    pub anchor: VertexAnchor,
+}
+
+impl AnchorRegion {
+    /// Returns `true` if `pos` is inside the region
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, pos: Vec3) -> bool {
+        pos.cmpge(self.min).all() && pos.cmple(self.max).all()
+    }
+}
+
+/// Declarative anchor definition over a UV region of the cloth, anchoring
+/// every vertex whose UV coordinates are inside the rectangle defined by `min`
+/// and `max` (inclusive).
+///
+/// The UVs are read from the [`ClothBuilder::uv_channel`], allowing to author
+/// the pinned areas in a dedicated UV set.
+///
+/// [`ClothBuilder::uv_channel`]: crate::components::cloth_builder::ClothBuilder::uv_channel
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Default)]
+pub struct AnchorUvRegion {
+    /// Minimum corner of the region, in UV space
+    pub min: Vec2,
+    /// Maximum corner of the region, in UV space
+    pub max: Vec2,
+    /// The anchor to apply to the vertices inside the region
+    pub anchor: VertexAnchor,
+}
+
+impl AnchorUvRegion {
+    /// Returns `true` if `uv` is inside the region
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, uv: Vec2) -> bool {
+        uv.cmpge(self.min).all() && uv.cmple(self.max).all()
+    }
+}
+
+/// Declarative pin gradient, pinning the `seed_ids` vertices and softening
+/// the attachment over the next `rows` of the stick network (See
+/// [`ClothBuilder::with_pin_gradient`]).
+///
+/// [`ClothBuilder::with_pin_gradient`]: crate::components::cloth_builder::ClothBuilder::with_pin_gradient
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Default)]
+pub struct PinGradient {
+    /// The fully pinned vertex ids
+    pub seed_ids: Vec<usize>,
+    /// The number of stick steps from the seeds receiving a spring anchor
+    pub rows: usize,
+    /// The stiffness ratio between two consecutive rows, between 0.0 and 1.0
+    pub falloff: f32,
+}
+
+impl PinGradient {
+    /// Retrieves the spring anchor stiffness of a vertex `hops` stick steps
+    /// away from the closest seed: `falloff` to the power of `hops`.
+    ///
+    /// Returns `None` for the seeds and for vertices further than
+    /// [`Self::rows`].
+    #[must_use]
+    pub fn stiffness(&self, hops: usize) -> Option<f32> {
+        let exponent = i32::try_from(hops).ok()?;
+        (1..=self.rows)
+            .contains(&hops)
+            .then(|| self.falloff.clamp(0.0, 1.0).powi(exponent))
+    }
+}
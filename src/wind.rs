@@ -1,4 +1,10 @@
-use bevy::{ecs::prelude::Resource, math::Vec3, reflect::Reflect};
+use bevy::{
+    asset::Handle,
+    ecs::prelude::{Component, ReflectComponent, Resource},
+    image::Image,
+    math::{Vec2, Vec3},
+    reflect::Reflect,
+};
 
 /// Wind definition for cloth physics
 #[derive(Debug, Clone, Reflect)]
@@ -20,15 +26,72 @@ pub enum Wind {
         /// Use absolute values, making the wave act as a bouncing signal
         abs: bool,
     },
+    /// Spatially varying wind sampled from a flow texture tiled over the
+    /// world XZ plane.
+    ///
+    /// The red and green channels of the texture respectively encode the X
+    /// and Z wind directions, remapped from `[0, 1]` to `[-1, 1]` (a
+    /// `(0.5, 0.5)` color means no wind). Use a linear texture format, `sRGB`
+    /// textures are converted to linear space before decoding.
+    ///
+    /// The wind is sampled for every cloth point from its world space
+    /// position, so [`Self::current_velocity`] ignores this variant.
+    ///
+    /// Note: the image data is read on the CPU, its `RenderAssetUsages` must
+    /// contain `MAIN_WORLD` (the default for loaded images)
+    FlowField {
+        /// Flow texture handle
+        handle: Handle<Image>,
+        /// World space size covered by a single tile of the texture
+        world_scale: f32,
+        /// Wind velocity for a fully saturated flow vector
+        strength: f32,
+    },
 }
 
 /// Wind forces resource for cloth physics
+///
+/// The time varying winds are sampled from each cloth [`Cloth::sim_time`],
+/// accumulated from the simulation step durations rather than read from the
+/// clock. With [`ClothConfig::fixed_timestep`] enabled, the cloths are stepped
+/// with the `Time<Fixed>` steps and the same inputs always produce the same
+/// wind driven motion, regardless of the frame rate or of when the cloth was
+/// spawned.
+///
+/// [`Cloth::sim_time`]: crate::components::cloth::Cloth::sim_time
+/// [`ClothConfig::fixed_timestep`]: crate::config::ClothConfig::fixed_timestep
 #[derive(Debug, Clone, Reflect, Resource, Default)]
 pub struct Winds {
     /// Array of wind forces
     pub wind_forces: Vec<Wind>,
+    /// Optional maximum magnitude of the summed wind velocity, preventing
+    /// stacked winds from spiking and launching the cloths.
+    ///
+    /// The length of the summed vector is clamped, preserving its direction.
+    /// The cloths clamp the wind of every point, the sum of the uniform winds
+    /// and of the [`Wind::FlowField`] winds sampled at the point position
+    /// (See [`Self::clamp_magnitude`]).
+    ///
+    /// Note: there is no minimum magnitude counterpart, as a null or
+    /// cancelled out wind has no direction to scale up, and raising the
+    /// oscillating winds, like [`Wind::SinWave`], would flip their direction
+    /// abruptly instead of letting them ease through zero.
+    pub max_magnitude: Option<f32>,
 }
 
+/// Opt-in cloth component holding the wind velocity applied to the cloth on
+/// the last update, sampled at the center of its bounding box.
+///
+/// Includes the cloth [`ClothConfig::phase_offset`] and the
+/// [`Wind::FlowField`] winds, allowing to orient VFX like rain streaks or
+/// leaves consistently with the cloth. Insert it on a cloth entity to enable
+/// the readout.
+///
+/// [`ClothConfig::phase_offset`]: crate::config::ClothConfig::phase_offset
+#[derive(Debug, Copy, Clone, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct WindReadout(pub Vec3);
+
 impl Default for Wind {
     fn default() -> Self {
         Self::SinWave {
@@ -62,32 +125,159 @@ impl Wind {
                 }
                 sin_value * *max_velocity
             }
+            Self::FlowField { .. } => Vec3::ZERO,
+        }
+    }
+
+    /// Samples the `image` flow texture at the world space `position` (See
+    /// [`Self::FlowField`])
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - the flow texture
+    /// * `world_scale` - world space size covered by a single tile of the
+    ///   texture
+    /// * `strength` - wind velocity for a fully saturated flow vector
+    /// * `position` - the world space sampling position
+    #[must_use]
+    pub fn sample_flow_field(
+        image: &Image,
+        world_scale: f32,
+        strength: f32,
+        position: Vec3,
+    ) -> Vec3 {
+        let size = image.size();
+        if size.min_element() == 0 || world_scale <= 0.0 {
+            return Vec3::ZERO;
         }
+        let uv = (Vec2::new(position.x, position.z) / world_scale).rem_euclid(Vec2::ONE);
+        let pixel = (uv * size.as_vec2()).as_uvec2().min(size - 1);
+        image
+            .get_color_at(pixel.x, pixel.y)
+            .map_or(Vec3::ZERO, |color| {
+                let color = color.to_linear();
+                Vec3::new(
+                    color.red.mul_add(2.0, -1.0),
+                    0.0,
+                    color.green.mul_add(2.0, -1.0),
+                ) * strength
+            })
     }
 }
 
 impl Winds {
     /// Retrieves the current winds velocity sum according to the elapsed time
-    /// since startup
+    /// since startup, its length clamped to [`Self::max_magnitude`].
+    ///
+    /// Note: the [`Wind::FlowField`] winds are sampled per point and not
+    /// included
     #[must_use]
     pub fn current_velocity(&self, elapsed_time: f32) -> Vec3 {
+        self.clamp_magnitude(self.uniform_velocity(elapsed_time))
+    }
+
+    /// Retrieves the current sum of the uniform winds velocity, without
+    /// clamping
+    pub(crate) fn uniform_velocity(&self, elapsed_time: f32) -> Vec3 {
         self.wind_forces
             .iter()
             .map(|w| w.current_velocity(elapsed_time))
             .sum()
     }
+
+    /// Clamps the length of a summed wind `velocity` to the
+    /// [`Self::max_magnitude`], preserving its direction
+    #[must_use]
+    pub fn clamp_magnitude(&self, velocity: Vec3) -> Vec3 {
+        self.max_magnitude
+            .map_or(velocity, |max| velocity.clamp_length_max(max))
+    }
+
+    /// Iterates over the [`Wind::FlowField`] winds, as `(handle, world_scale,
+    /// strength)` tuples
+    pub fn flow_fields(&self) -> impl Iterator<Item = (&Handle<Image>, f32, f32)> {
+        self.wind_forces.iter().filter_map(|w| match w {
+            Wind::FlowField {
+                handle,
+                world_scale,
+                strength,
+            } => Some((handle, *world_scale, *strength)),
+            _ => None,
+        })
+    }
 }
 
 impl From<Wind> for Winds {
     fn from(wind: Wind) -> Self {
-        Self {
-            wind_forces: vec![wind],
-        }
+        Self::from(vec![wind])
     }
 }
 
 impl From<Vec<Wind>> for Winds {
     fn from(wind_forces: Vec<Wind>) -> Self {
-        Self { wind_forces }
+        Self {
+            wind_forces,
+            max_magnitude: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+
+    #[test]
+    fn sample_flow_field() {
+        // 2x1 texture: +X wind on the left pixel, -Z wind on the right pixel
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![255, 128, 0, 255, 128, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        let sample = |position| Wind::sample_flow_field(&image, 10.0, 2.0, position);
+        assert!(sample(Vec3::new(2.0, 5.0, 0.0)).abs_diff_eq(Vec3::X * 2.0, 0.01));
+        assert!(sample(Vec3::new(7.0, 0.0, 3.0)).abs_diff_eq(Vec3::NEG_Z * 2.0, 0.01));
+        // The texture is tiled
+        assert!(sample(Vec3::new(-3.0, 0.0, 12.0)).abs_diff_eq(Vec3::NEG_Z * 2.0, 0.01));
+    }
+
+    #[test]
+    fn max_magnitude() {
+        let mut winds = Winds::from(vec![
+            Wind::ConstantWind {
+                velocity: Vec3::X * 30.0,
+            },
+            Wind::ConstantWind {
+                velocity: Vec3::new(10.0, 0.0, 40.0),
+            },
+        ]);
+        assert_eq!(winds.current_velocity(0.0), Vec3::new(40.0, 0.0, 40.0));
+        winds.max_magnitude = Some(10.0);
+        let velocity = winds.current_velocity(0.0);
+        assert!((velocity.length() - 10.0).abs() < 1e-4);
+        // The direction is preserved
+        assert!(velocity
+            .normalize()
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 1.0).normalize(), 1e-6));
+        // Weaker winds are untouched
+        winds.max_magnitude = Some(100.0);
+        assert_eq!(winds.current_velocity(0.0), Vec3::new(40.0, 0.0, 40.0));
+        // Per point winds, like flow fields, are clamped with the uniform sum
+        let flow = Vec3::NEG_X * 40.0;
+        let velocity = winds.clamp_magnitude(winds.uniform_velocity(0.0) + flow);
+        assert_eq!(velocity, Vec3::Z * 40.0);
+        winds.max_magnitude = Some(10.0);
+        let velocity = winds.clamp_magnitude(winds.uniform_velocity(0.0) + flow);
+        assert!(velocity.abs_diff_eq(Vec3::Z * 10.0, 1e-5), "{velocity}");
     }
 }